@@ -0,0 +1,198 @@
+use chrono::Utc;
+use configcat::bench::{cache_str, parse_cache_str};
+use configcat::{
+    evaluate_config, hash_comparison_value, Client, Config, OverrideBehavior, OverrideDataSource,
+    Setting, User,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::thread;
+
+const SALT: &str = "bench-salt";
+const RULES: usize = 3;
+const LOOKUPS_PER_THREAD: usize = 1000;
+
+struct SettingsSource(HashMap<String, Setting>);
+
+impl OverrideDataSource for SettingsSource {
+    fn settings(&self) -> &HashMap<String, Setting> {
+        &self.0
+    }
+}
+
+// `settings` boolean flags with `RULES` targeting rules that don't match the bench user, followed by 50/50 % options
+fn config_json(settings: usize, hashed: bool) -> String {
+    let mut flags = String::new();
+    for i in 0..settings {
+        let key = format!("flag{i}");
+        let mut rules = String::new();
+        for r in 0..RULES {
+            let cond = if hashed {
+                let emails = [
+                    hash_comparison_value(&format!("user{r}@example.com"), SALT, &key),
+                    hash_comparison_value(&format!("admin{r}@example.com"), SALT, &key),
+                ];
+                format!(
+                    r#"{{"u":{{"a":"Email","c":16,"l":["{}","{}"]}}}}"#,
+                    emails[0], emails[1]
+                )
+            } else {
+                format!(r#"{{"u":{{"a":"Email","c":2,"l":["@rule{r}.example.com"]}}}}"#)
+            };
+            let sep = if r == 0 { "" } else { "," };
+            let _ = write!(rules, r#"{sep}{{"c":[{cond}],"s":{{"v":{{"b":true}}}}}}"#);
+        }
+        let sep = if i == 0 { "" } else { "," };
+        let _ = write!(
+            flags,
+            r#"{sep}"{key}":{{"t":0,"v":{{"b":false}},"r":[{rules}],"p":[{{"p":50,"v":{{"b":true}}}},{{"p":50,"v":{{"b":false}}}}]}}"#
+        );
+    }
+    format!(
+        r#"{{"p":{{"u":"https://cdn-global.configcat.com","r":0,"s":"{SALT}"}},"f":{{{flags}}}}}"#
+    )
+}
+
+fn config(settings: usize, hashed: bool) -> Arc<Config> {
+    let cached = cache_str(&config_json(settings, hashed), "etag", Utc::now());
+    parse_cache_str(&cached).unwrap()
+}
+
+fn client(config: &Config) -> Client {
+    let source = SettingsSource(config.settings.clone());
+    Client::builder("local")
+        .overrides(Box::new(source), OverrideBehavior::LocalOnly)
+        .evaluation_logging(false)
+        .build()
+        .unwrap()
+}
+
+fn user() -> User {
+    User::new("bench-user").email("bench@example.com")
+}
+
+fn get_value(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+    let client = client(&config(100, false));
+    let user = user();
+
+    c.bench_function("get_value", |b| {
+        b.iter(|| rt.block_on(client.get_value("flag50", false, Some(user.clone()))));
+    });
+    c.bench_function("cached_value", |b| {
+        b.iter(|| client.cached_value("flag50", false));
+    });
+}
+
+fn read_contention(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+    let client = Arc::new(client(&config(100, false)));
+
+    let mut group = c.benchmark_group("read_contention");
+    for threads in [1, 4, 8] {
+        group.throughput(Throughput::Elements((threads * LOOKUPS_PER_THREAD) as u64));
+        group.bench_with_input(
+            BenchmarkId::new("cached_value", threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    thread::scope(|scope| {
+                        for _ in 0..threads {
+                            scope.spawn(|| {
+                                for i in 0..LOOKUPS_PER_THREAD {
+                                    client.cached_value(&format!("flag{}", i % 100), false);
+                                }
+                            });
+                        }
+                    });
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("get_value", threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    rt.block_on(async {
+                        let tasks = (0..threads)
+                            .map(|_| {
+                                let client = Arc::clone(&client);
+                                tokio::spawn(async move {
+                                    for i in 0..LOOKUPS_PER_THREAD {
+                                        let key = format!("flag{}", i % 100);
+                                        client.get_value(&key, false, Some(user())).await;
+                                    }
+                                })
+                            })
+                            .collect::<Vec<_>>();
+                        for task in tasks {
+                            task.await.unwrap();
+                        }
+                    });
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn get_all_values(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let mut group = c.benchmark_group("get_all_values");
+    for settings in [100, 1000] {
+        let client = client(&config(settings, false));
+        let user = user();
+        group.throughput(Throughput::Elements(settings as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(settings), &settings, |b, _| {
+            b.iter(|| rt.block_on(client.get_all_values(Some(user.clone()))));
+        });
+    }
+    group.finish();
+}
+
+fn sensitive_comparators(c: &mut Criterion) {
+    let plain = config(10, false);
+    let hashed = config(10, true);
+    let user = user();
+
+    let mut group = c.benchmark_group("comparators");
+    group.bench_function("plain", |b| {
+        b.iter(|| evaluate_config(&plain, "flag5", Some(&user)));
+    });
+    group.bench_function("sensitive", |b| {
+        b.iter(|| evaluate_config(&hashed, "flag5", Some(&user)));
+    });
+    group.finish();
+}
+
+fn cache_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cache_parse");
+    for settings in [100, 1000] {
+        let cached = cache_str(&config_json(settings, false), "etag", Utc::now());
+        group.throughput(Throughput::Bytes(cached.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(settings),
+            &cached,
+            |b, cached| {
+                b.iter(|| parse_cache_str(cached).unwrap());
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    get_value,
+    read_contention,
+    get_all_values,
+    sensitive_comparators,
+    cache_parse
+);
+criterion_main!(benches);
@@ -0,0 +1,101 @@
+//! Procedural macros for the ConfigCat SDK for Rust.
+//!
+//! Enable the `macros` feature of the `configcat` crate to use them; they are re-exported from there.
+
+#![warn(missing_docs)]
+#![warn(clippy::pedantic)]
+#![allow(clippy::doc_markdown)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::quote;
+use serde_json::Value;
+use std::path::PathBuf;
+use syn::{parse_macro_input, LitStr};
+
+/// Reads and validates a feature flag override JSON file at compile time, and expands to a `configcat::MapDataSource`.
+///
+/// The file path is relative to the directory of the invoking crate's `Cargo.toml`. The file must use the
+/// simplified override format, where every value is a bool, a whole number, a decimal number or a text:
+///
+/// ```json
+/// {
+///   "flags": {
+///     "bool_flag": true,
+///     "string_setting": "example",
+///     "number_setting": 3.14
+///   }
+/// }
+/// ```
+///
+/// The build fails when the file doesn't exist or its content doesn't match the format above.
+///
+/// # Examples
+///
+/// ```ignore
+/// use configcat::{Client, OverrideBehavior};
+///
+/// let builder = Client::builder("sdk-key").overrides(
+///     Box::new(configcat::include_overrides!("overrides.json")),
+///     OverrideBehavior::LocalOnly,
+/// );
+/// ```
+#[proc_macro]
+pub fn include_overrides(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match expand(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(message) => syn::Error::new(path.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+fn expand(path: &LitStr) -> Result<TokenStream2, String> {
+    let mut full_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    full_path.push(path.value());
+    let content = std::fs::read_to_string(&full_path)
+        .map_err(|err| format!("Failed to read '{}': {err}", full_path.display()))?;
+    let json = serde_json::from_str::<Value>(content.as_str())
+        .map_err(|err| format!("Failed to parse '{}': {err}", full_path.display()))?;
+    let Some(flags) = json.get("flags").and_then(Value::as_object) else {
+        return Err(format!(
+            "'{}' must contain a 'flags' JSON object.",
+            full_path.display()
+        ));
+    };
+
+    let mut entries = Vec::with_capacity(flags.len());
+    for (key, value) in flags {
+        let value = match value {
+            Value::Bool(val) => quote! { ::configcat::Value::Bool(#val) },
+            Value::String(val) => quote! { ::configcat::Value::String(#val.to_owned()) },
+            Value::Number(val) => {
+                if let Some(int) = val.as_i64() {
+                    let lit = Literal::i64_suffixed(int);
+                    quote! { ::configcat::Value::Int(#lit) }
+                } else if let Some(float) = val.as_f64() {
+                    let lit = Literal::f64_suffixed(float);
+                    quote! { ::configcat::Value::Float(#lit) }
+                } else {
+                    return Err(format!("The value of '{key}' is not a valid number."));
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "The value of '{key}' must be a bool, a number or a text."
+                ))
+            }
+        };
+        entries.push(quote! { (#key, #value) });
+    }
+
+    // makes cargo rebuild the invoking crate when the file changes
+    let tracked = LitStr::new(&full_path.to_string_lossy(), Span::call_site());
+    Ok(quote! {
+        {
+            const _: &str = include_str!(#tracked);
+            ::configcat::MapDataSource::from([#(#entries),*])
+        }
+    })
+}
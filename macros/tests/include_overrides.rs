@@ -0,0 +1,24 @@
+use configcat::OverrideBehavior::LocalOnly;
+use configcat::{include_overrides, Client};
+
+#[tokio::test]
+async fn include_simple() {
+    let client = Client::builder("local")
+        .overrides(
+            Box::new(include_overrides!("../tests/data/test_json_simple.json")),
+            LocalOnly,
+        )
+        .build()
+        .unwrap();
+
+    assert!(client.get_value("enabledFeature", false, None).await);
+    assert!(!client.get_value("disabledFeature", true, None).await);
+    assert_eq!(client.get_value("intSetting", 0, None).await, 5);
+    assert_eq!(client.get_value("doubleSetting", 0.0, None).await, 1.2);
+    assert_eq!(
+        client
+            .get_value("stringSetting", String::default(), None)
+            .await,
+        "test".to_owned()
+    );
+}
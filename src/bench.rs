@@ -0,0 +1,23 @@
+//! Internals exposed for the criterion benchmarks in `benches/`, enabled by the `bench` feature.
+//! Not part of the public API, they may change in any release.
+
+use crate::model::config::{entry_from_cached_json, generate_cache_str};
+use crate::Config;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// Parses a [`crate::ConfigCache`] entry into a post-processed [`Config`], like the SDK does when it reads the cache.
+///
+/// # Errors
+///
+/// Returns the parse error's message when `cache_str` is not a valid cache entry.
+pub fn parse_cache_str(cache_str: &str) -> Result<Arc<Config>, String> {
+    entry_from_cached_json(cache_str)
+        .map(|entry| entry.config)
+        .map_err(|err| err.to_string())
+}
+
+/// Renders the [`crate::ConfigCache`] entry of `config_json`, like the SDK does when it writes the cache.
+pub fn cache_str(config_json: &str, etag: &str, fetch_time: DateTime<Utc>) -> String {
+    generate_cache_str(fetch_time, etag, config_json)
+}
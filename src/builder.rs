@@ -1,26 +1,100 @@
-use crate::cache::EmptyConfigCache;
+#[cfg(feature = "brotli")]
+use crate::cache::{CacheCompression, CompressedConfigCache};
+use crate::cache::{CacheEntry, EmptyConfigCache};
 use crate::constants::{SDK_KEY_PREFIX, SDK_KEY_PROXY_PREFIX, SDK_KEY_SECTION_LENGTH};
 use crate::errors::{ClientError, ErrorKind};
+#[cfg(feature = "network")]
+use crate::fetch::fetcher::IpPreference;
+use crate::fetch::fetcher::{DataGovernanceStatus, ProxySettings};
+#[cfg(feature = "test-util")]
+use crate::fetch::mock::MockFetcher;
+use crate::freeze::FreezeSchedule;
 use crate::model::enums::DataGovernance;
-use crate::modes::PollingMode;
+use crate::modes::{
+    BucketingStrategy, InitBehavior, KeyLookupMode, LogArea, LogLevels, MemoryMode,
+    NotReadyBehavior, PollingMode, SemverParsing, StaleBehavior, TextNormalization,
+    WhitespaceTrimming,
+};
 use crate::r#override::{FlagOverrides, OptionalOverrides};
-use crate::{Client, ConfigCache, OverrideBehavior, OverrideDataSource, User};
+use crate::{
+    AsyncOverrideDataSource, CircuitState, Client, ClientCacheState, ClientMode, ClientPool,
+    ConfigCache, ConfigCatOptions, DefaultValueProvider, EvalOptions, ExposureEvent, FetchCounters,
+    OverrideBehavior, OverrideDataSource, OverrideReport, RefreshOutcome, UsageReport, User,
+};
+use log::LevelFilter;
 use std::borrow::Borrow;
 use std::fmt::{Debug, Formatter};
+#[cfg(feature = "network")]
+use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct Options {
     sdk_key: String,
     offline: bool,
     base_url: Option<String>,
     data_governance: DataGovernance,
     http_timeout: Duration,
+    startup_http_timeout: Option<Duration>,
+    proxy: ProxySettings,
     cache: Box<dyn ConfigCache>,
-    overrides: Option<FlagOverrides>,
+    memory_mode: MemoryMode,
+    overrides: Option<Arc<FlagOverrides>>,
     polling_mode: PollingMode,
+    init_behavior: Option<InitBehavior>,
+    not_ready_behavior: NotReadyBehavior,
+    early_refresh_beta: Option<f64>,
     default_user: Option<User>,
+    default_user_provider: Option<DefaultUserProvider>,
+    prefixed_default_users: Vec<(String, Arc<User>)>,
+    default_user_resolver: Option<DefaultUserResolver>,
+    key_lookup_mode: KeyLookupMode,
+    text_normalization: TextNormalization,
+    whitespace_trimming: WhitespaceTrimming,
+    bucketing_strategy: BucketingStrategy,
+    semver_parsing: SemverParsing,
+    key_prefix: Option<String>,
+    max_config_age: Option<(Duration, StaleBehavior)>,
+    include_config_in_details: bool,
+    freeze_schedule: Option<FreezeSchedule>,
+    log_levels: LogLevels,
+    failover_urls: Vec<String>,
+    failover_cooldown: Duration,
+    circuit_breaker: Option<(u32, Duration)>,
+    evaluation_yield: (usize, usize),
+    cache_state_hook: Option<CacheStateHook>,
+    data_governance_hook: Option<DataGovernanceHook>,
+    override_report_hook: Option<OverrideReportHook>,
+    fetch_hook: Option<FetchHook>,
+    sdk_upgrade_hook: Option<SdkUpgradeHook>,
+    mode_hook: Option<ModeHook>,
+    refresh_hook: Option<RefreshHook>,
+    circuit_hook: Option<CircuitHook>,
+    track_usage: bool,
+    usage_report_hook: Option<(Duration, UsageReportHook)>,
+    flag_evaluated_hook: Option<FlagEvaluatedHook>,
+    exposure_dedup: Option<(usize, Duration)>,
+    default_value_provider: Option<Arc<dyn DefaultValueProvider>>,
+    preloaded_entry: Option<CacheEntry>,
+    pooled: bool,
+    #[cfg(feature = "test-util")]
+    mock_fetcher: Option<Arc<dyn MockFetcher>>,
 }
 
+pub(crate) type CacheStateHook = Arc<dyn Fn(ClientCacheState) + Send + Sync>;
+pub(crate) type DataGovernanceHook = Arc<dyn Fn(&DataGovernanceStatus) + Send + Sync>;
+pub(crate) type OverrideReportHook = Arc<dyn Fn(&OverrideReport) + Send + Sync>;
+pub(crate) type FetchHook = Arc<dyn Fn(&FetchCounters) + Send + Sync>;
+pub(crate) type SdkUpgradeHook = Arc<dyn Fn(&str) + Send + Sync>;
+pub(crate) type ModeHook = Arc<dyn Fn(ClientMode) + Send + Sync>;
+pub(crate) type RefreshHook = Arc<dyn Fn(&Result<RefreshOutcome, ClientError>) + Send + Sync>;
+pub(crate) type CircuitHook = Arc<dyn Fn(CircuitState) + Send + Sync>;
+pub(crate) type UsageReportHook = Arc<dyn Fn(&UsageReport) + Send + Sync>;
+pub(crate) type FlagEvaluatedHook = Arc<dyn Fn(&ExposureEvent) + Send + Sync>;
+pub(crate) type DefaultUserProvider = Arc<dyn Fn() -> Option<User> + Send + Sync>;
+pub(crate) type DefaultUserResolver = Arc<dyn Fn(&str) -> Option<User> + Send + Sync>;
+
 impl Options {
     pub(crate) fn sdk_key(&self) -> &str {
         &self.sdk_key
@@ -42,21 +116,243 @@ impl Options {
         &self.http_timeout
     }
 
+    pub(crate) fn startup_http_timeout(&self) -> Option<Duration> {
+        self.startup_http_timeout
+    }
+
+    pub(crate) fn proxy(&self) -> &ProxySettings {
+        &self.proxy
+    }
+
     pub(crate) fn cache(&self) -> &dyn ConfigCache {
         self.cache.borrow()
     }
 
+    pub(crate) fn memory_mode(&self) -> MemoryMode {
+        self.memory_mode
+    }
+
     pub(crate) fn polling_mode(&self) -> &PollingMode {
         &self.polling_mode
     }
 
+    pub(crate) fn init_behavior(&self) -> Option<InitBehavior> {
+        self.init_behavior
+    }
+
+    pub(crate) fn not_ready_behavior(&self) -> NotReadyBehavior {
+        self.not_ready_behavior
+    }
+
+    pub(crate) fn early_refresh_beta(&self) -> Option<f64> {
+        self.early_refresh_beta
+    }
+
     pub(crate) fn overrides(&self) -> Option<&FlagOverrides> {
-        self.overrides.as_ref()
+        self.overrides.as_deref()
     }
 
     pub(crate) fn default_user(&self) -> Option<&User> {
         self.default_user.as_ref()
     }
+
+    pub(crate) fn default_user_provider(&self) -> Option<&DefaultUserProvider> {
+        self.default_user_provider.as_ref()
+    }
+
+    /// Returns the default user registered for the longest prefix of `key`.
+    pub(crate) fn prefixed_default_user(&self, key: &str) -> Option<&Arc<User>> {
+        self.prefixed_default_users
+            .iter()
+            .filter(|(prefix, _)| key.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, user)| user)
+    }
+
+    pub(crate) fn default_user_resolver(&self) -> Option<&DefaultUserResolver> {
+        self.default_user_resolver.as_ref()
+    }
+
+    pub(crate) fn key_lookup_mode(&self) -> KeyLookupMode {
+        self.key_lookup_mode
+    }
+
+    /// Fills the evaluation settings that aren't set in `options` with the ones of the client.
+    pub(crate) fn eval_options(&self, options: &EvalOptions) -> EvalOptions {
+        EvalOptions {
+            text_normalization: options.text_normalization.or(Some(self.text_normalization)),
+            whitespace_trimming: options
+                .whitespace_trimming
+                .or(Some(self.whitespace_trimming)),
+            bucketing_strategy: options.bucketing_strategy.or(Some(self.bucketing_strategy)),
+            semver_parsing: options.semver_parsing.or(Some(self.semver_parsing)),
+            log_level: options
+                .log_level
+                .or(Some(self.log_level(LogArea::Evaluation))),
+            ..*options
+        }
+    }
+
+    pub(crate) fn key_prefix(&self) -> Option<&str> {
+        self.key_prefix.as_deref()
+    }
+
+    pub(crate) fn max_config_age(&self) -> Option<(Duration, StaleBehavior)> {
+        self.max_config_age
+    }
+
+    pub(crate) fn include_config_in_details(&self) -> bool {
+        self.include_config_in_details
+    }
+
+    pub(crate) fn freeze_schedule(&self) -> Option<&FreezeSchedule> {
+        self.freeze_schedule.as_ref()
+    }
+
+    pub(crate) fn log_level(&self, area: LogArea) -> LevelFilter {
+        self.log_levels.get(area)
+    }
+
+    pub(crate) fn failover_urls(&self) -> &[String] {
+        &self.failover_urls
+    }
+
+    pub(crate) fn failover_cooldown(&self) -> Duration {
+        self.failover_cooldown
+    }
+
+    pub(crate) fn circuit_breaker(&self) -> Option<(u32, Duration)> {
+        self.circuit_breaker
+    }
+
+    pub(crate) fn evaluation_yield(&self) -> (usize, usize) {
+        self.evaluation_yield
+    }
+
+    pub(crate) fn cache_state_hook(&self) -> Option<&CacheStateHook> {
+        self.cache_state_hook.as_ref()
+    }
+
+    pub(crate) fn data_governance_hook(&self) -> Option<&DataGovernanceHook> {
+        self.data_governance_hook.as_ref()
+    }
+
+    pub(crate) fn override_report_hook(&self) -> Option<&OverrideReportHook> {
+        self.override_report_hook.as_ref()
+    }
+
+    pub(crate) fn fetch_hook(&self) -> Option<&FetchHook> {
+        self.fetch_hook.as_ref()
+    }
+
+    pub(crate) fn sdk_upgrade_hook(&self) -> Option<&SdkUpgradeHook> {
+        self.sdk_upgrade_hook.as_ref()
+    }
+
+    pub(crate) fn mode_hook(&self) -> Option<&ModeHook> {
+        self.mode_hook.as_ref()
+    }
+
+    pub(crate) fn refresh_hook(&self) -> Option<&RefreshHook> {
+        self.refresh_hook.as_ref()
+    }
+
+    pub(crate) fn circuit_hook(&self) -> Option<&CircuitHook> {
+        self.circuit_hook.as_ref()
+    }
+
+    pub(crate) fn track_usage(&self) -> bool {
+        self.track_usage
+    }
+
+    pub(crate) fn usage_report_hook(&self) -> Option<&(Duration, UsageReportHook)> {
+        self.usage_report_hook.as_ref()
+    }
+
+    pub(crate) fn flag_evaluated_hook(&self) -> Option<&FlagEvaluatedHook> {
+        self.flag_evaluated_hook.as_ref()
+    }
+
+    pub(crate) fn exposure_dedup(&self) -> Option<(usize, Duration)> {
+        self.exposure_dedup
+    }
+
+    pub(crate) fn default_value_provider(&self) -> Option<&Arc<dyn DefaultValueProvider>> {
+        self.default_value_provider.as_ref()
+    }
+
+    #[cfg(feature = "test-util")]
+    pub(crate) fn mock_fetcher(&self) -> Option<&Arc<dyn MockFetcher>> {
+        self.mock_fetcher.as_ref()
+    }
+
+    pub(crate) fn preloaded_entry(&self) -> Option<&CacheEntry> {
+        self.preloaded_entry.as_ref()
+    }
+
+    pub(crate) fn swap_cache(&mut self, cache: Box<dyn ConfigCache>) -> Box<dyn ConfigCache> {
+        std::mem::replace(&mut self.cache, cache)
+    }
+
+    pub(crate) fn pooled(&self) -> bool {
+        self.pooled
+    }
+
+    /// Creates options for a pooled client that reads the config JSON from the given shared `cache`
+    /// instead of polling on its own. The hooks of the config JSON's lifecycle are left to the pool's primary client.
+    pub(crate) fn for_pooled_client(&self, cache: Box<dyn ConfigCache>) -> Options {
+        Options {
+            sdk_key: self.sdk_key.clone(),
+            offline: self.offline,
+            base_url: self.base_url.clone(),
+            data_governance: self.data_governance.clone(),
+            http_timeout: self.http_timeout,
+            startup_http_timeout: self.startup_http_timeout,
+            proxy: self.proxy.clone(),
+            cache,
+            memory_mode: self.memory_mode,
+            overrides: self.overrides.clone(),
+            polling_mode: self.polling_mode.clone(),
+            init_behavior: None,
+            not_ready_behavior: NotReadyBehavior::UseDefault,
+            early_refresh_beta: None,
+            default_user: self.default_user.clone(),
+            default_user_provider: self.default_user_provider.clone(),
+            prefixed_default_users: self.prefixed_default_users.clone(),
+            default_user_resolver: self.default_user_resolver.clone(),
+            key_lookup_mode: self.key_lookup_mode,
+            text_normalization: self.text_normalization,
+            whitespace_trimming: self.whitespace_trimming,
+            bucketing_strategy: self.bucketing_strategy,
+            semver_parsing: self.semver_parsing,
+            key_prefix: self.key_prefix.clone(),
+            max_config_age: self.max_config_age,
+            include_config_in_details: self.include_config_in_details,
+            freeze_schedule: self.freeze_schedule.clone(),
+            log_levels: self.log_levels,
+            failover_urls: self.failover_urls.clone(),
+            failover_cooldown: self.failover_cooldown,
+            circuit_breaker: self.circuit_breaker,
+            evaluation_yield: self.evaluation_yield,
+            cache_state_hook: None,
+            data_governance_hook: None,
+            override_report_hook: None,
+            fetch_hook: self.fetch_hook.clone(),
+            sdk_upgrade_hook: None,
+            mode_hook: self.mode_hook.clone(),
+            refresh_hook: self.refresh_hook.clone(),
+            circuit_hook: self.circuit_hook.clone(),
+            track_usage: self.track_usage,
+            usage_report_hook: None,
+            flag_evaluated_hook: self.flag_evaluated_hook.clone(),
+            exposure_dedup: self.exposure_dedup,
+            default_value_provider: self.default_value_provider.clone(),
+            preloaded_entry: self.preloaded_entry.clone(),
+            pooled: true,
+            #[cfg(feature = "test-util")]
+            mock_fetcher: self.mock_fetcher.clone(),
+        }
+    }
 }
 
 impl Debug for Options {
@@ -88,16 +384,59 @@ impl Debug for Options {
 ///
 /// let client = builder.build().unwrap();
 /// ```
+#[allow(clippy::struct_excessive_bools)]
 pub struct ClientBuilder {
     sdk_key: String,
     base_url: Option<String>,
     data_governance: Option<DataGovernance>,
     http_timeout: Option<Duration>,
+    startup_http_timeout: Option<Duration>,
+    proxy: ProxySettings,
     cache: Option<Box<dyn ConfigCache>>,
+    #[cfg(feature = "brotli")]
+    cache_compression: Option<CacheCompression>,
+    memory_mode: Option<MemoryMode>,
     overrides: Option<FlagOverrides>,
     offline: bool,
     polling_mode: Option<PollingMode>,
+    init_behavior: Option<InitBehavior>,
+    not_ready_behavior: Option<NotReadyBehavior>,
+    early_refresh_beta: Option<f64>,
     default_user: Option<User>,
+    default_user_provider: Option<DefaultUserProvider>,
+    prefixed_default_users: Vec<(String, Arc<User>)>,
+    default_user_resolver: Option<DefaultUserResolver>,
+    key_lookup_mode: Option<KeyLookupMode>,
+    text_normalization: Option<TextNormalization>,
+    whitespace_trimming: Option<WhitespaceTrimming>,
+    bucketing_strategy: Option<BucketingStrategy>,
+    semver_parsing: Option<SemverParsing>,
+    key_prefix: Option<String>,
+    max_config_age: Option<(Duration, StaleBehavior)>,
+    include_config_in_details: bool,
+    freeze_schedule: Option<FreezeSchedule>,
+    log_levels: LogLevels,
+    evaluation_logging: bool,
+    failover_urls: Vec<String>,
+    failover_cooldown: Option<Duration>,
+    circuit_breaker: Option<(u32, Duration)>,
+    evaluation_yield: Option<(usize, usize)>,
+    cache_state_hook: Option<CacheStateHook>,
+    data_governance_hook: Option<DataGovernanceHook>,
+    override_report_hook: Option<OverrideReportHook>,
+    fetch_hook: Option<FetchHook>,
+    sdk_upgrade_hook: Option<SdkUpgradeHook>,
+    mode_hook: Option<ModeHook>,
+    refresh_hook: Option<RefreshHook>,
+    circuit_hook: Option<CircuitHook>,
+    track_usage: bool,
+    usage_report_hook: Option<(Duration, UsageReportHook)>,
+    flag_evaluated_hook: Option<FlagEvaluatedHook>,
+    exposure_dedup: Option<(usize, Duration)>,
+    default_value_provider: Option<Arc<dyn DefaultValueProvider>>,
+    preloaded_entry: Option<CacheEntry>,
+    #[cfg(feature = "test-util")]
+    mock_fetcher: Option<Arc<dyn MockFetcher>>,
 }
 
 impl ClientBuilder {
@@ -106,18 +445,120 @@ impl ClientBuilder {
             sdk_key: sdk_key.to_owned(),
             offline: false,
             http_timeout: None,
+            startup_http_timeout: None,
+            proxy: ProxySettings::default(),
             base_url: None,
             cache: None,
+            #[cfg(feature = "brotli")]
+            cache_compression: None,
+            memory_mode: None,
             polling_mode: None,
+            init_behavior: None,
+            not_ready_behavior: None,
+            early_refresh_beta: None,
             data_governance: None,
             overrides: None,
             default_user: None,
+            default_user_provider: None,
+            prefixed_default_users: Vec::new(),
+            default_user_resolver: None,
+            key_lookup_mode: None,
+            text_normalization: None,
+            whitespace_trimming: None,
+            bucketing_strategy: None,
+            semver_parsing: None,
+            key_prefix: None,
+            max_config_age: None,
+            include_config_in_details: false,
+            freeze_schedule: None,
+            log_levels: LogLevels::default(),
+            evaluation_logging: true,
+            failover_urls: Vec::new(),
+            failover_cooldown: None,
+            circuit_breaker: None,
+            evaluation_yield: None,
+            cache_state_hook: None,
+            data_governance_hook: None,
+            override_report_hook: None,
+            fetch_hook: None,
+            sdk_upgrade_hook: None,
+            mode_hook: None,
+            refresh_hook: None,
+            circuit_hook: None,
+            track_usage: false,
+            usage_report_hook: None,
+            flag_evaluated_hook: None,
+            exposure_dedup: None,
+            default_value_provider: None,
+            preloaded_entry: None,
+            #[cfg(feature = "test-util")]
+            mock_fetcher: None,
         }
     }
 
+    /// Creates a [`ClientBuilder`] configured by environment variables.
+    ///
+    /// | Variable | Description |
+    /// |----------|-------------|
+    /// | `CONFIGCAT_SDK_KEY` | The SDK Key (required). |
+    /// | `CONFIGCAT_BASE_URL` | The base URL of the remote server providing the config JSON. |
+    /// | `CONFIGCAT_POLLING_MODE` | `auto`, `lazy` or `manual`. |
+    /// | `CONFIGCAT_POLL_INTERVAL_SECS` | The interval of `auto` polling or the cache TTL of `lazy` loading, 60 when not set. |
+    /// | `CONFIGCAT_HTTP_TIMEOUT_SECS` | The timeout of the HTTP requests. |
+    /// | `CONFIGCAT_DATA_GOVERNANCE` | `global` or `eu`. |
+    ///
+    /// Empty variables are treated as unset. The offline mode is controlled by the `CONFIGCAT_SDK_OFFLINE`
+    /// variable, which is honored by every [`Client`] (see [`ClientBuilder::offline`]).
+    /// The builder can be configured further before calling [`ClientBuilder::build`].
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`ErrorKind::InvalidConfiguration`] when `CONFIGCAT_SDK_KEY` is not set
+    /// or a variable has an invalid value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::ClientBuilder;
+    ///
+    /// let client = ClientBuilder::from_env()
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn from_env() -> Result<Self, ClientError> {
+        ConfigCatOptions::from_vars(|name| std::env::var(name).ok())?.into_builder()
+    }
+
+    /// Creates a [`ClientBuilder`] configured by a [`ConfigCatOptions`], e.g. deserialized from a configuration file.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`ErrorKind::InvalidConfiguration`] when the SDK Key is missing or an option
+    /// has an invalid value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{ClientBuilder, ConfigCatOptions};
+    ///
+    /// let builder = ClientBuilder::from_config(ConfigCatOptions {
+    ///     sdk_key: "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/HhOWfwVtZ0mb30i9wi17GQ".to_owned(),
+    ///     polling_mode: Some("manual".to_owned()),
+    ///     ..ConfigCatOptions::default()
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn from_config(options: ConfigCatOptions) -> Result<Self, ClientError> {
+        options.into_builder()
+    }
+
     /// Indicates whether the SDK should be initialized in offline mode or not.
     /// Default value is `false`.
     ///
+    /// Independently of this option, the SDK doesn't initiate HTTP requests while
+    /// the `CONFIGCAT_SDK_OFFLINE` environment variable is set to `1` or `true`.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -148,8 +589,141 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the timeout of the HTTP requests made by the first config JSON download (the cold start),
+    /// e.g. to keep the initialization short while allowing a longer [`ClientBuilder::http_timeout`] for the
+    /// background refreshes. Default is the value set with [`ClientBuilder::http_timeout`].
+    ///
+    /// When the first download times out, the evaluations fall back to the cached config JSON or to the default values,
+    /// and the later downloads use the general timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .startup_http_timeout(Duration::from_secs(1))
+    ///     .http_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn startup_http_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_http_timeout = Some(timeout);
+        self
+    }
+
+    /// Adds a [`reqwest::Proxy`] to the internal [`reqwest::Client`] used to download the config JSON.
+    /// Can be called multiple times to add more proxies.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .proxy(reqwest::Proxy::https("http://proxy.example.com:8080").unwrap());
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy.proxies.push(proxy);
+        self
+    }
+
+    /// Disables the proxies configured by the `HTTP_PROXY` / `HTTPS_PROXY` environment variables
+    /// for the internal [`reqwest::Client`] used to download the config JSON.
+    /// Proxies set with [`ClientBuilder::proxy`] are still used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .no_proxy();
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn no_proxy(mut self) -> Self {
+        self.proxy.no_proxy = true;
+        self
+    }
+
+    /// Sets a custom DNS resolver for the internal [`reqwest::Client`] used to download the config JSON,
+    /// replacing the system's resolver.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::net::{Ipv6Addr, SocketAddr};
+    /// use std::sync::Arc;
+    /// use configcat::Client;
+    /// use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+    ///
+    /// struct StaticResolver;
+    ///
+    /// impl Resolve for StaticResolver {
+    ///     fn resolve(&self, _name: Name) -> Resolving {
+    ///         let addr = SocketAddr::from((Ipv6Addr::LOCALHOST, 0));
+    ///         Box::pin(async move { Ok(Box::new(std::iter::once(addr)) as Addrs) })
+    ///     }
+    /// }
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .dns_resolver(Arc::new(StaticResolver));
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn dns_resolver<R: reqwest::dns::Resolve + 'static>(mut self, resolver: Arc<R>) -> Self {
+        self.proxy.resolver = Some(resolver);
+        self
+    }
+
+    /// Sets which IP address families are used to connect to the remote server providing the config JSON.
+    /// Default value is [`IpPreference::System`].
+    ///
+    /// The preference applies to the addresses returned by the resolver, the addresses set with
+    /// [`ClientBuilder::resolve`] are used as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, IpPreference};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .ip_preference(IpPreference::PreferIpv6);
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn ip_preference(mut self, preference: IpPreference) -> Self {
+        self.proxy.ip_preference = preference;
+        self
+    }
+
+    /// Makes the internal [`reqwest::Client`] connect to the given `addrs` instead of resolving `host`.
+    /// Can be called multiple times to set the addresses of more hosts.
+    ///
+    /// An explicit port in the requested URL takes precedence over the port of the addresses,
+    /// a port of `0` stands for the default port of the URL's scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::net::{Ipv4Addr, SocketAddr};
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .resolve("cdn-global.configcat.com", &[SocketAddr::from((Ipv4Addr::new(192, 0, 2, 10), 443))]);
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn resolve(mut self, host: &str, addrs: &[SocketAddr]) -> Self {
+        self.proxy
+            .resolve_overrides
+            .push((host.to_owned(), addrs.to_vec()));
+        self
+    }
+
     /// Sets a custom base URL.
     ///
+    /// The URL must be an absolute `http` or `https` URL without a query string or fragment. Trailing slashes are
+    /// removed, and the SDK appends the path of the config JSON to it, so it must not contain that path.
+    /// [`ClientBuilder::build`] returns an [`ErrorKind::InvalidBaseUrl`] error for invalid URLs.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -163,6 +737,48 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets an ordered list of base URLs to fail over to when downloading the config JSON keeps failing.
+    ///
+    /// After consecutive transient failures the SDK switches to the next URL in the list,
+    /// and it switches back to the original URL after `cooldown` has elapsed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .failover_urls(&["https://cdn-eu.configcat.com"], Duration::from_secs(300));
+    /// ```
+    pub fn failover_urls(mut self, urls: &[&str], cooldown: Duration) -> Self {
+        self.failover_urls = urls.iter().map(|url| (*url).to_owned()).collect();
+        self.failover_cooldown = Some(cooldown);
+        self
+    }
+
+    /// Sets a circuit breaker that pauses the config JSON downloads after `failure_threshold` consecutive transient failures,
+    /// so an unreachable server doesn't tie up a connection until the request times out on each poll.
+    ///
+    /// While the circuit is open, downloads are skipped (refreshes fail with [`ErrorKind::CircuitOpen`]) and the
+    /// config JSON held in memory or in the cache is served. After `cool_down` has elapsed, the next download probes
+    /// the server: the normal cadence resumes when it succeeds, the circuit opens again for another `cool_down` when it fails.
+    /// The state changes are reported to the hook set with [`ClientBuilder::on_circuit_state_changed`] and to [`Client::events`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .circuit_breaker(5, Duration::from_secs(300));
+    /// ```
+    pub fn circuit_breaker(mut self, failure_threshold: u32, cool_down: Duration) -> Self {
+        self.circuit_breaker = Some((failure_threshold, cool_down));
+        self
+    }
+
     /// Sets the [`DataGovernance`] option.
     /// Default value is [`DataGovernance::Global`].
     ///
@@ -207,91 +823,901 @@ impl ClientBuilder {
         self
     }
 
-    /// Sets the [`PollingMode`] of the SDK.
-    /// Default value is [`PollingMode::AutoPoll`] with `60` seconds poll interval.
+    /// Sets the [`CacheCompression`] of the entries written to the [`ConfigCache`].
+    /// Default value is [`CacheCompression::None`].
+    ///
+    /// Compressed entries are read back transparently regardless of this setting, so the compression
+    /// can be switched on and off while other clients share the same cache.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use std::time::Duration;
-    /// use configcat::{Client, PollingMode};
+    /// use configcat::{CacheCompression, Client};
     ///
     /// let builder = Client::builder("sdk-key")
-    ///     .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)));
+    ///     .cache_compression(CacheCompression::Brotli);
     /// ```
-    pub fn polling_mode(mut self, polling_mode: PollingMode) -> Self {
-        self.polling_mode = Some(polling_mode);
+    #[cfg(feature = "brotli")]
+    pub fn cache_compression(mut self, compression: CacheCompression) -> Self {
+        self.cache_compression = Some(compression);
         self
     }
 
-    /// Sets the default user, used as fallback when there's no user parameter is passed to the flag evaluation methods.
+    /// Sets the [`MemoryMode`] of the config JSON held by the [`Client`].
+    /// Default value is [`MemoryMode::Standard`].
+    ///
+    /// With [`MemoryMode::Bounded`], the downloaded config JSON is dropped from memory once it's written to the
+    /// [`ConfigCache`]. When the SDK needs it again (to refresh its fetch time in the cache, to re-apply changed
+    /// flag overrides or for [`Client::export_cache_entry`]), it reads it back from the [`ConfigCache`].
+    /// Without a cache holding the same config JSON version, the cache isn't refreshed, changed flag overrides
+    /// aren't re-applied until the next download and [`Client::export_cache_entry`] returns `None`.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use configcat::{Client, User};
+    /// use configcat::{Client, MemoryMode};
     ///
     /// let builder = Client::builder("sdk-key")
-    ///     .default_user(User::new("USER_IDENTIFIER"));
+    ///     .memory_mode(MemoryMode::Bounded);
     /// ```
-    pub fn default_user(mut self, user: User) -> Self {
-        self.default_user = Some(user);
+    pub fn memory_mode(mut self, mode: MemoryMode) -> Self {
+        self.memory_mode = Some(mode);
         self
     }
 
-    /// Sets feature flag and setting overrides for the SDK.
+    /// Replaces the HTTP requests downloading the config JSON with the responses of `fetcher`, so the polling
+    /// and caching behavior can be tested deterministically without sockets. See [`crate::ScriptedFetcher`].
     ///
-    /// With overrides, you can overwrite feature flag and setting values
-    /// downloaded from the ConfigCat CDN with local values.
+    /// Only available with the `test-util` feature, which is meant for tests, not for production builds.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use std::collections::HashMap;
-    /// use std::time::Duration;
-    /// use configcat::{Client, MapDataSource, OverrideBehavior, PollingMode, Value};
+    /// use std::sync::Arc;
+    /// use configcat::{Client, MockResponse, ScriptedFetcher};
     ///
-    /// let builder = Client::builder("sdk-key")
-    ///     .overrides(Box::new(MapDataSource::from([
-    ///         ("flag", Value::Bool(true))
-    ///     ])), OverrideBehavior::LocalOnly);
+    /// let fetcher = Arc::new(ScriptedFetcher::new([MockResponse::NotModified]));
+    /// let builder = Client::builder("configcat-sdk-1/abcdefghijklmnopqrstuv/abcdefghijklmnopqrstuv")
+    ///     .mock_fetcher(fetcher);
     /// ```
-    pub fn overrides(
-        mut self,
-        source: Box<dyn OverrideDataSource>,
-        behavior: OverrideBehavior,
-    ) -> Self {
-        self.overrides = Some(FlagOverrides::new(source, behavior));
+    #[cfg(feature = "test-util")]
+    pub fn mock_fetcher(mut self, fetcher: Arc<dyn MockFetcher>) -> Self {
+        self.mock_fetcher = Some(fetcher);
         self
     }
 
-    /// Creates a [`Client`] from the configuration made on the builder.
-    ///
-    /// # Errors
+    /// Preloads a [`CacheEntry`] exported by [`Client::export_cache_entry`] of another client, so the new
+    /// client starts with the config JSON held by the other one instead of a cold cache.
     ///
-    /// This method fails in the following cases:
-    /// - The given SDK key is empty or has an invalid format.
-    /// - The initialization of the internal [`reqwest::Client`] failed.
+    /// The preloaded config JSON is treated like one read from the [`ConfigCache`], it gets refreshed
+    /// when it's older than the polling interval or cache TTL. An entry that can't be parsed is ignored
+    /// with a warning.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use std::time::Duration;
-    /// use configcat::{DataGovernance, Client, PollingMode};
+    /// use configcat::{CacheEntry, Client};
     ///
-    /// let builder = Client::builder("sdk-key")
-    ///     .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)))
-    ///     .data_governance(DataGovernance::EU);
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // e.g. received from the previous deployment over a local channel
+    ///     let exported = std::fs::read_to_string("/run/app/configcat-entry.json").unwrap();
+    ///     let entry: CacheEntry = serde_json::from_str(&exported).unwrap();
     ///
-    /// let client = builder.build().unwrap();
+    ///     let client = Client::builder("sdk-key")
+    ///         .preload_cache_entry(entry)
+    ///         .build()
+    ///         .unwrap();
+    /// }
     /// ```
-    pub fn build(self) -> Result<Client, ClientError> {
-        if self.sdk_key.is_empty() {
-            return Err(ClientError::new(
-                ErrorKind::InvalidSdkKey,
-                "SDK Key cannot be empty".to_owned(),
-            ));
-        }
+    pub fn preload_cache_entry(mut self, entry: CacheEntry) -> Self {
+        self.preloaded_entry = Some(entry);
+        self
+    }
+
+    /// Sets the [`PollingMode`] of the SDK.
+    /// Default value is [`PollingMode::AutoPoll`] with `60` seconds poll interval,
+    /// or [`PollingMode::Manual`] when the `network` feature is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use configcat::{Client, PollingMode};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)));
+    /// ```
+    pub fn polling_mode(mut self, polling_mode: PollingMode) -> Self {
+        self.polling_mode = Some(polling_mode);
+        self
+    }
+
+    /// Sets the [`InitBehavior`] that controls how feature flags are evaluated in [`PollingMode::AutoPoll`]
+    /// before the first fetch of the config JSON has completed.
+    /// When not set, the evaluation fetches the config JSON itself when the cached one is expired.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, InitBehavior};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .init_behavior(InitBehavior::ServeCacheImmediately);
+    /// ```
+    pub fn init_behavior(mut self, init_behavior: InitBehavior) -> Self {
+        self.init_behavior = Some(init_behavior);
+        self
+    }
+
+    /// Sets the [`NotReadyBehavior`] that controls how feature flags are evaluated in [`PollingMode::Manual`]
+    /// before any config JSON is available.
+    /// Default value is [`NotReadyBehavior::UseDefault`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, NotReadyBehavior, PollingMode};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .polling_mode(PollingMode::Manual)
+    ///     .not_ready_behavior(NotReadyBehavior::RefreshOnce);
+    /// ```
+    pub fn not_ready_behavior(mut self, behavior: NotReadyBehavior) -> Self {
+        self.not_ready_behavior = Some(behavior);
+        self
+    }
+
+    /// Sets the default user, used as fallback when there's no user parameter is passed to the flag evaluation methods.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, User};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .default_user(User::new("USER_IDENTIFIER"));
+    /// ```
+    pub fn default_user(mut self, user: User) -> Self {
+        self.default_user = Some(user);
+        self
+    }
+
+    /// Enables probabilistic early refresh of the cached config JSON, so clients sharing a [`ConfigCache`]
+    /// don't all fetch at the same moment when the shared entry expires.
+    ///
+    /// Each expiration check treats the entry as expired `latency * beta * -ln(random)` earlier,
+    /// where `latency` is the duration of the last fetch and `random` is in the `(0, 1]` range.
+    /// Greater `beta` values refresh earlier. Non-positive or non-finite values disable the early refresh.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .early_refresh(1.0);
+    /// ```
+    pub fn early_refresh(mut self, beta: f64) -> Self {
+        self.early_refresh_beta = (beta.is_finite() && beta > 0.0).then_some(beta);
+        self
+    }
+
+    /// Sets a provider of the default user, called at evaluation time when there's no user parameter
+    /// passed to the flag evaluation methods.
+    ///
+    /// When the provider returns `None`, the default user set with [`ClientBuilder::default_user`]
+    /// (or [`Client::set_default_user`]) is used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, User};
+    ///
+    /// thread_local! {
+    ///     static TENANT_USER: Option<User> = Some(User::new("tenant-user"));
+    /// }
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .default_user_provider(|| TENANT_USER.with(Clone::clone));
+    /// ```
+    pub fn default_user_provider(
+        mut self,
+        provider: impl Fn() -> Option<User> + Send + Sync + 'static,
+    ) -> Self {
+        self.default_user_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Sets a default user for the feature flags and settings whose key starts with `prefix`, used when
+    /// there's no user parameter passed to the flag evaluation methods.
+    ///
+    /// Can be called multiple times, the user of the longest matching prefix is used. The keys are matched
+    /// as passed to the evaluation methods (without the prefix set with [`ClientBuilder::key_prefix`]).
+    /// The prefixed default users take precedence over [`ClientBuilder::default_user_provider`] and
+    /// [`ClientBuilder::default_user`], but not over [`ClientBuilder::default_user_resolver`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, User};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .default_user_for_prefix("batch.", User::new("batch-worker"))
+    ///     .default_user_for_prefix("web.", User::new("web-frontend"));
+    /// ```
+    pub fn default_user_for_prefix(mut self, prefix: &str, user: User) -> Self {
+        self.prefixed_default_users
+            .retain(|(registered, _)| registered != prefix);
+        self.prefixed_default_users
+            .push((prefix.to_owned(), Arc::new(user)));
+        self
+    }
+
+    /// Sets a resolver of the default user, called with the key of the evaluated feature flag or setting
+    /// when there's no user parameter passed to the flag evaluation methods.
+    ///
+    /// When the resolver returns `None`, the default users set with [`ClientBuilder::default_user_for_prefix`],
+    /// [`ClientBuilder::default_user_provider`] and [`ClientBuilder::default_user`] are consulted in this order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, User};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .default_user_resolver(|key| key.ends_with("_job").then(|| User::new("batch-worker")));
+    /// ```
+    pub fn default_user_resolver(
+        mut self,
+        resolver: impl Fn(&str) -> Option<User> + Send + Sync + 'static,
+    ) -> Self {
+        self.default_user_resolver = Some(Arc::new(resolver));
+        self
+    }
+
+    /// Sets the [`KeyLookupMode`] used to find feature flags and settings by their key.
+    /// Default value is [`KeyLookupMode::Exact`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, KeyLookupMode};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .key_lookup_mode(KeyLookupMode::CaseInsensitive);
+    /// ```
+    pub fn key_lookup_mode(mut self, mode: KeyLookupMode) -> Self {
+        self.key_lookup_mode = Some(mode);
+        self
+    }
+
+    /// Sets the [`TextNormalization`] applied to user attribute values and comparison values
+    /// before the text-based comparators compare them.
+    /// Default value is [`TextNormalization::None`] (byte-wise comparison).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, TextNormalization};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .text_normalization(TextNormalization::Nfc);
+    /// ```
+    pub fn text_normalization(mut self, normalization: TextNormalization) -> Self {
+        self.text_normalization = Some(normalization);
+        self
+    }
+
+    /// Sets the [`WhitespaceTrimming`] applied to user attribute values and comparison values
+    /// before the text-based comparators compare them.
+    /// Default value is [`WhitespaceTrimming::None`] (strings are compared as they are).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, WhitespaceTrimming};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .whitespace_trimming(WhitespaceTrimming::All);
+    /// ```
+    pub fn whitespace_trimming(mut self, trimming: WhitespaceTrimming) -> Self {
+        self.whitespace_trimming = Some(trimming);
+        self
+    }
+
+    /// Sets the [`BucketingStrategy`] that assigns users to the buckets of percentage options.
+    /// Default value is [`BucketingStrategy::Hash`], the bucketing of all ConfigCat SDKs.
+    ///
+    /// **Warning:** other strategies make the served percentage options inconsistent with the other
+    /// ConfigCat SDKs, use them only for interoperability with systems that bucket users the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{BucketingStrategy, Client};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .bucketing_strategy(BucketingStrategy::NumericModulo);
+    /// ```
+    pub fn bucketing_strategy(mut self, strategy: BucketingStrategy) -> Self {
+        self.bucketing_strategy = Some(strategy);
+        self
+    }
+
+    /// Sets how strictly the semantic version comparators parse the user attribute values and comparison values.
+    /// Default value is [`SemverParsing::Strict`], like in all ConfigCat SDKs.
+    ///
+    /// With [`SemverParsing::Lenient`], versions like `v1.2.3+build5` are accepted too, and a warning is logged
+    /// each time the leniency was needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, SemverParsing};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .semver_parsing(SemverParsing::Lenient);
+    /// ```
+    pub fn semver_parsing(mut self, parsing: SemverParsing) -> Self {
+        self.semver_parsing = Some(parsing);
+        self
+    }
+
+    /// Sets a prefix that is prepended to each key passed to the [`Client`]'s evaluation methods.
+    ///
+    /// Useful when a config is shared by multiple services, and each of them has its own prefixed keys.
+    /// The `get_all_*` methods return only the feature flags and settings whose key starts with the prefix,
+    /// and they return the keys without the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// // `client.get_value("enable_x", false, None)` evaluates `checkout__enable_x`
+    /// let builder = Client::builder("sdk-key")
+    ///     .key_prefix("checkout__");
+    /// ```
+    pub fn key_prefix(mut self, prefix: &str) -> Self {
+        self.key_prefix = Some(prefix.to_owned());
+        self
+    }
+
+    /// Sets the maximum age of the config JSON that feature flags are evaluated on.
+    ///
+    /// When the config JSON is older than `max_age` (e.g. because the ConfigCat CDN has been unreachable),
+    /// the evaluations return the default value instead of the stale value, as described by `behavior`.
+    /// Clients with a [`OverrideBehavior::LocalOnly`] override are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use configcat::{Client, StaleBehavior};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .max_config_age(Duration::from_secs(24 * 60 * 60), StaleBehavior::Error);
+    /// ```
+    pub fn max_config_age(mut self, max_age: Duration, behavior: StaleBehavior) -> Self {
+        self.max_config_age = Some((max_age, behavior));
+        self
+    }
+
+    /// Indicates whether the [`EvaluationDetails`](crate::EvaluationDetails) returned by the evaluation methods
+    /// should hold the config they were based on in [`EvaluationDetails::config`](crate::EvaluationDetails::config).
+    /// Default value is `false`.
+    ///
+    /// The config is shared, not copied, but it's kept in memory as long as the details that hold it exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .include_config_in_details(true);
+    /// ```
+    pub fn include_config_in_details(mut self, include: bool) -> Self {
+        self.include_config_in_details = include;
+        self
+    }
+
+    /// Sets the [`FreezeSchedule`] that defines the change-freeze windows during which new config JSON
+    /// versions are downloaded but not applied until the window ends.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{FixedOffset, NaiveTime};
+    /// use configcat::{Client, FreezeSchedule};
+    ///
+    /// let builder = Client::builder("sdk-key").freeze_schedule(
+    ///     FreezeSchedule::new(FixedOffset::east_opt(0).unwrap())
+    ///         .daily(NaiveTime::from_hms_opt(22, 0, 0).unwrap(), NaiveTime::from_hms_opt(6, 0, 0).unwrap()),
+    /// );
+    /// ```
+    pub fn freeze_schedule(mut self, schedule: FreezeSchedule) -> Self {
+        self.freeze_schedule = Some(schedule);
+        self
+    }
+
+    /// Sets the maximum level of the log messages emitted by the given [`LogArea`] of the SDK.
+    /// By default, each area logs everything that the global `log` max level allows.
+    ///
+    /// The area levels can only further restrict the global `log` max level, e.g. the evaluation log
+    /// (logged at `Info` level) can be turned off while the fetch diagnostics are still logged at `Info` level.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, LogArea};
+    /// use log::LevelFilter;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .log_level(LogArea::Evaluation, LevelFilter::Warn)
+    ///     .log_level(LogArea::Fetch, LevelFilter::Info);
+    /// ```
+    pub fn log_level(mut self, area: LogArea, level: LevelFilter) -> Self {
+        self.log_levels.set(area, level);
+        self
+    }
+
+    /// Sets how the methods evaluating all feature flags (e.g. [`Client::get_all_value_details`]) share the executor
+    /// with other tasks. Default values are `1000` and `100`.
+    ///
+    /// When the config JSON holds more than `threshold` feature flags and settings, the evaluation yields to the
+    /// executor (with [`tokio::task::yield_now`]) after every `interval` evaluated feature flags, so that the long
+    /// synchronous evaluation loop doesn't delay the other tasks running on the same worker thread.
+    /// Passing [`usize::MAX`] as `threshold` turns the yielding off.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .evaluation_yield(500, 50);
+    /// ```
+    pub fn evaluation_yield(mut self, threshold: usize, interval: usize) -> Self {
+        self.evaluation_yield = Some((threshold, interval.max(1)));
+        self
+    }
+
+    /// Sets whether the evaluation log (the `Info` level breakdown of each evaluation) is built and logged.
+    /// Default value is `true`.
+    ///
+    /// When disabled, the evaluation log is skipped regardless of the logger's level and of the level set
+    /// with [`ClientBuilder::log_level`] for [`LogArea::Evaluation`], while the evaluation warnings and errors
+    /// are still logged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .evaluation_logging(false);
+    /// ```
+    pub fn evaluation_logging(mut self, enabled: bool) -> Self {
+        self.evaluation_logging = enabled;
+        self
+    }
+
+    /// Sets a hook that is called each time the [`ClientCacheState`] of the [`Client`] changes.
+    ///
+    /// The hook is called while the SDK holds the lock of its config cache, so it should return quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, ClientCacheState};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .on_cache_state_changed(|state| {
+    ///         if state == ClientCacheState::HasCachedFlagDataOnly {
+    ///             println!("serving cached feature flag data");
+    ///         }
+    ///     });
+    /// ```
+    pub fn on_cache_state_changed(
+        mut self,
+        hook: impl Fn(ClientCacheState) + Send + Sync + 'static,
+    ) -> Self {
+        self.cache_state_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets a hook that is called each time the [`DataGovernanceStatus`] of the [`Client`] changes,
+    /// e.g. when the preferences on the ConfigCat Dashboard reveal that the `.data_governance()` parameter is not in sync.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, DataGovernanceStatus};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .on_data_governance_status_changed(|status| {
+    ///         if let DataGovernanceStatus::Mismatch { url } = status {
+    ///             println!("data governance mismatch, redirected to {url}");
+    ///         }
+    ///     });
+    /// ```
+    pub fn on_data_governance_status_changed(
+        mut self,
+        hook: impl Fn(&DataGovernanceStatus) + Send + Sync + 'static,
+    ) -> Self {
+        self.data_governance_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets a hook that is called each time the flag overrides are merged into a config JSON downloaded
+    /// from the ConfigCat CDN. The [`OverrideReport`] lists the keys whose values come from the flag overrides.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, MapDataSource, OverrideBehavior, Value};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .overrides(
+    ///         Box::new(MapDataSource::from([("flag", Value::Bool(true))])),
+    ///         OverrideBehavior::LocalOverRemote,
+    ///     )
+    ///     .on_overrides_applied(|report| {
+    ///         if report.has_overridden_keys() {
+    ///             println!("overridden settings: {:?}", report.overridden);
+    ///         }
+    ///     });
+    /// ```
+    pub fn on_overrides_applied(
+        mut self,
+        hook: impl Fn(&OverrideReport) + Send + Sync + 'static,
+    ) -> Self {
+        self.override_report_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets a hook that is called each time a download of the config JSON completes (successfully or not),
+    /// with the updated [`FetchCounters`] of the [`Client`].
+    ///
+    /// The hook is called while the SDK holds the lock of its config cache, so it should return quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .on_fetch_completed(|counters| {
+    ///         println!("config revision: {}, fetches: {} new, {} not modified, {} failed",
+    ///             counters.revision, counters.fetched, counters.not_modified, counters.failed);
+    ///     });
+    /// ```
+    pub fn on_fetch_completed(
+        mut self,
+        hook: impl Fn(&FetchCounters) + Send + Sync + 'static,
+    ) -> Self {
+        self.fetch_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets a hook that is called with the required minimum SDK version each time a config JSON is applied whose
+    /// preferences require a newer version of this SDK. An error is logged in this case regardless of the hook.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .on_sdk_upgrade_required(|required| {
+    ///         eprintln!("the feature flags require ConfigCat SDK {required} or newer");
+    ///     });
+    /// ```
+    pub fn on_sdk_upgrade_required(mut self, hook: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.sdk_upgrade_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets a hook that is called each time the [`Client`] is switched to online or offline mode
+    /// with [`Client::online`] / [`Client::offline`]. See also [`Client::events`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{Client, ClientMode};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .on_mode_changed(|mode| {
+    ///         if mode == ClientMode::Offline {
+    ///             println!("feature flags are served from the cache");
+    ///         }
+    ///     });
+    /// ```
+    pub fn on_mode_changed(mut self, hook: impl Fn(ClientMode) + Send + Sync + 'static) -> Self {
+        self.mode_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets a hook that is called with the result of each refresh initiated by [`Client::refresh`] or
+    /// [`Client::refresh_if_older`]. Refreshes rejected up front (by a client built in offline mode or with
+    /// [`OverrideBehavior::LocalOnly`] overrides) are not reported.
+    /// See also [`Client::events`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .on_refresh_completed(|result| {
+    ///         if let Err(err) = result {
+    ///             eprintln!("refresh failed: {err}");
+    ///         }
+    ///     });
+    /// ```
+    pub fn on_refresh_completed(
+        mut self,
+        hook: impl Fn(&Result<RefreshOutcome, ClientError>) + Send + Sync + 'static,
+    ) -> Self {
+        self.refresh_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets a hook that is called each time the circuit breaker set with [`ClientBuilder::circuit_breaker`]
+    /// changes its state. See also [`Client::events`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use configcat::{CircuitState, Client};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .circuit_breaker(5, Duration::from_secs(300))
+    ///     .on_circuit_state_changed(|state| {
+    ///         if state == CircuitState::Open {
+    ///             eprintln!("the ConfigCat CDN is unreachable, downloads are paused");
+    ///         }
+    ///     });
+    /// ```
+    pub fn on_circuit_state_changed(
+        mut self,
+        hook: impl Fn(CircuitState) + Send + Sync + 'static,
+    ) -> Self {
+        self.circuit_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Indicates whether the [`Client`] should track which feature flags and settings get evaluated,
+    /// so [`Client::usage_report`] can list the ones that are never evaluated. Default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .track_usage(true);
+    /// ```
+    pub fn track_usage(mut self, enabled: bool) -> Self {
+        self.track_usage = enabled;
+        self
+    }
+
+    /// Sets a hook that is called every `period` with the [`UsageReport`] of the period, listing the feature flags
+    /// and settings of the config JSON that weren't evaluated. Setting the hook turns on the usage tracking
+    /// (see [`ClientBuilder::track_usage`]).
+    ///
+    /// Each call starts a new period. The report of the last, partial period is passed to the hook when the [`Client`] is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .on_usage_report(Duration::from_secs(24 * 60 * 60), |report| {
+    ///         println!("not evaluated since {}: {:?}", report.since, report.unused);
+    ///     });
+    /// ```
+    pub fn on_usage_report(
+        mut self,
+        period: Duration,
+        hook: impl Fn(&UsageReport) + Send + Sync + 'static,
+    ) -> Self {
+        self.usage_report_hook = Some((period, Arc::new(hook)));
+        self
+    }
+
+    /// Sets a hook that is called with the [`ExposureEvent`] of each successful evaluation of a single feature flag
+    /// or setting, e.g. to send experiment exposures to an analytics service.
+    ///
+    /// The hook is called on the evaluating task, so it should return quickly. See [`ClientBuilder::exposure_dedup`]
+    /// for reporting each exposure only once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .on_flag_evaluated(|exposure| {
+    ///         println!("'{}' served {:?} to '{}'", exposure.key, exposure.variation_id, exposure.user_id());
+    ///     });
+    /// ```
+    pub fn on_flag_evaluated(
+        mut self,
+        hook: impl Fn(&ExposureEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.flag_evaluated_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Deduplicates the exposures passed to the hook set with [`ClientBuilder::on_flag_evaluated`]: each combination
+    /// of user identifier, feature flag key and variation ID is reported once within `ttl`.
+    ///
+    /// At most `capacity` exposures are remembered, when it's exceeded, the least recently seen exposure is forgotten
+    /// (and reported again when it recurs). By default, every evaluation is reported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use configcat::Client;
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .exposure_dedup(100_000, Duration::from_secs(30 * 60));
+    /// ```
+    pub fn exposure_dedup(mut self, capacity: usize, ttl: Duration) -> Self {
+        self.exposure_dedup = Some((capacity, ttl));
+        self
+    }
+
+    /// Sets the [`DefaultValueProvider`] consulted when the evaluation of a feature flag or setting fails,
+    /// before falling back to the default value passed to the evaluation methods.
+    ///
+    /// [`crate::EvaluationDetails::value_source`] tells which one produced the value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use configcat::{Client, Value};
+    ///
+    /// let defaults = HashMap::from([("isFeatureEnabled".to_owned(), Value::Bool(false))]);
+    /// let builder = Client::builder("sdk-key").default_value_provider(defaults);
+    /// ```
+    pub fn default_value_provider(mut self, provider: impl DefaultValueProvider + 'static) -> Self {
+        self.default_value_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Sets feature flag and setting overrides for the SDK.
+    ///
+    /// With overrides, you can overwrite feature flag and setting values
+    /// downloaded from the ConfigCat CDN with local values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use std::time::Duration;
+    /// use configcat::{Client, MapDataSource, OverrideBehavior, PollingMode, Value};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .overrides(Box::new(MapDataSource::from([
+    ///         ("flag", Value::Bool(true))
+    ///     ])), OverrideBehavior::LocalOnly);
+    /// ```
+    pub fn overrides(
+        mut self,
+        source: Box<dyn OverrideDataSource>,
+        behavior: OverrideBehavior,
+    ) -> Self {
+        self.overrides = Some(FlagOverrides::new(source, behavior));
+        self
+    }
+
+    /// Sets feature flag and setting overrides for the SDK loaded from an [`AsyncOverrideDataSource`].
+    ///
+    /// The SDK loads the overrides from the source every `refresh_interval` and re-applies them
+    /// on the currently used config. With [`OverrideBehavior::LocalOnly`], the [`Client`] is considered
+    /// initialized once the first load attempt has completed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use std::time::Duration;
+    /// use configcat::{AsyncOverrideDataSource, Client, OverrideBehavior, OverrideLoadFuture};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .async_overrides(Box::new(RemoteSource{}), OverrideBehavior::LocalOverRemote, Duration::from_secs(30));
+    ///
+    /// struct RemoteSource {}
+    ///
+    /// impl AsyncOverrideDataSource for RemoteSource {
+    ///     fn load(&self) -> OverrideLoadFuture<'_> {
+    ///         Box::pin(async { Ok(HashMap::new()) })
+    ///     }
+    /// }
+    /// ```
+    pub fn async_overrides(
+        mut self,
+        source: Box<dyn AsyncOverrideDataSource>,
+        behavior: OverrideBehavior,
+        refresh_interval: Duration,
+    ) -> Self {
+        self.overrides = Some(FlagOverrides::new_async(source, behavior, refresh_interval));
+        self
+    }
+
+    /// Creates a [`Client`] from the configuration made on the builder.
+    ///
+    /// # Errors
+    ///
+    /// This method fails in the following cases:
+    /// - The given SDK key is empty or has an invalid format.
+    /// - The initialization of the internal [`reqwest::Client`] failed.
+    /// - The SDK was built without the `network` feature and no local-only flag overrides were set.
+    /// - The client needs to run background tasks (e.g. with [`PollingMode::AutoPoll`]) but it's created outside
+    ///   of a tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat::{DataGovernance, Client, PollingMode};
+    ///
+    /// let builder = Client::builder("sdk-key")
+    ///     .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)))
+    ///     .data_governance(DataGovernance::EU);
+    ///
+    /// let client = builder.build().unwrap();
+    /// ```
+    pub fn build(self) -> Result<Client, ClientError> {
+        self.validate()?;
+        Client::with_options(self.build_options())
+    }
+
+    /// Creates a [`ClientPool`] of `size` clients from the configuration made on the builder.
+    ///
+    /// Only one client of the pool downloads the config JSON, the others read it from a cache
+    /// shared between the clients.
+    ///
+    /// # Errors
+    ///
+    /// This method fails in the following cases:
+    /// - The given SDK key is empty or has an invalid format.
+    /// - The initialization of the internal [`reqwest::Client`] failed.
+    /// - The SDK was built without the `network` feature and no local-only flag overrides were set.
+    /// - The clients need to run background tasks (e.g. with [`PollingMode::AutoPoll`]) but they're created outside
+    ///   of a tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// let pool = Client::builder("sdk-key")
+    ///     .build_pool(4)
+    ///     .unwrap();
+    /// ```
+    pub fn build_pool(self, size: usize) -> Result<ClientPool, ClientError> {
+        self.validate()?;
+        ClientPool::with_options(self.build_options(), size)
+    }
+
+    fn validate(&self) -> Result<(), ClientError> {
+        if self.sdk_key.is_empty() {
+            return Err(ClientError::new(
+                ErrorKind::InvalidSdkKey,
+                "SDK Key cannot be empty".to_owned(),
+            ));
+        }
         if !self.overrides.is_local()
             && !is_sdk_key_valid(self.sdk_key.as_str(), self.base_url.is_some())
         {
@@ -300,26 +1726,138 @@ impl ClientBuilder {
                 format!("SDK Key '{}' is invalid.", self.sdk_key),
             ));
         }
-        Client::with_options(self.build_options())
+        for url in self.base_url.iter().chain(self.failover_urls.iter()) {
+            normalize_base_url(url)?;
+        }
+        #[cfg(all(not(feature = "network"), feature = "test-util"))]
+        let fetcher_mocked = self.mock_fetcher.is_some();
+        #[cfg(all(not(feature = "network"), not(feature = "test-util")))]
+        let fetcher_mocked = false;
+        #[cfg(not(feature = "network"))]
+        if !self.overrides.is_local() && !fetcher_mocked {
+            return Err(ClientError::new(
+                ErrorKind::NetworkUnavailable,
+                "The SDK was built without the `network` feature, only clients with local-only flag overrides can be created.".to_owned(),
+            ));
+        }
+        Ok(())
     }
 
-    pub(crate) fn build_options(self) -> Options {
+    pub(crate) fn build_options(mut self) -> Options {
+        if !self.evaluation_logging {
+            let level = self.log_levels.get(LogArea::Evaluation);
+            self.log_levels
+                .set(LogArea::Evaluation, level.min(LevelFilter::Warn));
+        }
+        #[cfg_attr(not(feature = "brotli"), allow(unused_mut))]
+        let mut cache = self.cache.unwrap_or(Box::new(EmptyConfigCache::new()));
+        #[cfg(feature = "brotli")]
+        if let Some(compression) = self.cache_compression {
+            cache = Box::new(CompressedConfigCache::new(
+                cache,
+                compression,
+                self.log_levels.get(LogArea::Cache),
+            ));
+        }
         Options {
             sdk_key: self.sdk_key,
             offline: self.offline,
-            cache: self.cache.unwrap_or(Box::new(EmptyConfigCache::new())),
-            polling_mode: self
-                .polling_mode
-                .unwrap_or(PollingMode::AutoPoll(Duration::from_secs(60))),
-            base_url: self.base_url,
+            cache,
+            memory_mode: self.memory_mode.unwrap_or_default(),
+            polling_mode: self.polling_mode.unwrap_or_else(default_polling_mode),
+            init_behavior: self.init_behavior,
+            not_ready_behavior: self.not_ready_behavior.unwrap_or_default(),
+            early_refresh_beta: self.early_refresh_beta,
+            base_url: self
+                .base_url
+                .map(|url| normalize_base_url(&url).unwrap_or(url)),
             data_governance: self.data_governance.unwrap_or(DataGovernance::Global),
             http_timeout: self.http_timeout.unwrap_or(Duration::from_secs(30)),
-            overrides: self.overrides,
+            startup_http_timeout: self.startup_http_timeout,
+            proxy: self.proxy,
+            overrides: self.overrides.map(Arc::new),
             default_user: self.default_user,
+            default_user_provider: self.default_user_provider,
+            prefixed_default_users: self.prefixed_default_users,
+            default_user_resolver: self.default_user_resolver,
+            key_lookup_mode: self.key_lookup_mode.unwrap_or_default(),
+            text_normalization: self.text_normalization.unwrap_or_default(),
+            whitespace_trimming: self.whitespace_trimming.unwrap_or_default(),
+            bucketing_strategy: self.bucketing_strategy.unwrap_or_default(),
+            semver_parsing: self.semver_parsing.unwrap_or_default(),
+            key_prefix: self.key_prefix,
+            max_config_age: self.max_config_age,
+            include_config_in_details: self.include_config_in_details,
+            freeze_schedule: self.freeze_schedule,
+            log_levels: self.log_levels,
+            failover_urls: self
+                .failover_urls
+                .into_iter()
+                .map(|url| normalize_base_url(&url).unwrap_or(url))
+                .collect(),
+            failover_cooldown: self.failover_cooldown.unwrap_or(Duration::from_secs(300)),
+            circuit_breaker: self.circuit_breaker,
+            evaluation_yield: self.evaluation_yield.unwrap_or((1000, 100)),
+            cache_state_hook: self.cache_state_hook,
+            data_governance_hook: self.data_governance_hook,
+            override_report_hook: self.override_report_hook,
+            fetch_hook: self.fetch_hook,
+            sdk_upgrade_hook: self.sdk_upgrade_hook,
+            mode_hook: self.mode_hook,
+            refresh_hook: self.refresh_hook,
+            circuit_hook: self.circuit_hook,
+            track_usage: self.track_usage || self.usage_report_hook.is_some(),
+            usage_report_hook: self.usage_report_hook,
+            flag_evaluated_hook: self.flag_evaluated_hook,
+            exposure_dedup: self.exposure_dedup,
+            default_value_provider: self.default_value_provider,
+            preloaded_entry: self.preloaded_entry,
+            pooled: false,
+            #[cfg(feature = "test-util")]
+            mock_fetcher: self.mock_fetcher,
         }
     }
 }
 
+#[cfg(feature = "network")]
+fn default_polling_mode() -> PollingMode {
+    PollingMode::AutoPoll(Duration::from_secs(60))
+}
+
+#[cfg(not(feature = "network"))]
+fn default_polling_mode() -> PollingMode {
+    PollingMode::Manual
+}
+
+fn normalize_base_url(url: &str) -> Result<String, ClientError> {
+    let invalid = |reason: &str| {
+        ClientError::new(
+            ErrorKind::InvalidBaseUrl,
+            format!("The base URL '{url}' is invalid, {reason}."),
+        )
+    };
+    let trimmed = url.trim().trim_end_matches('/');
+    let lowercase = trimmed.to_ascii_lowercase();
+    let Some(rest) = ["https://", "http://"]
+        .iter()
+        .find_map(|scheme| lowercase.strip_prefix(scheme))
+    else {
+        return Err(invalid("it must start with 'http://' or 'https://'"));
+    };
+    if rest.is_empty() || rest.starts_with('/') {
+        return Err(invalid("the host is missing"));
+    }
+    if rest.contains(['?', '#']) {
+        return Err(invalid("it must not contain a query string or fragment"));
+    }
+    if rest.contains("/configuration-files/") || rest.ends_with("/configuration-files") {
+        return Err(invalid(
+            "it must not contain the path of the config JSON, which is appended by the SDK",
+        ));
+    }
+    Ok(trimmed.to_owned())
+}
+
 fn is_sdk_key_valid(sdk_key: &str, is_custom_url: bool) -> bool {
     if is_custom_url
         && sdk_key.len() > SDK_KEY_PROXY_PREFIX.len()
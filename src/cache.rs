@@ -1,10 +1,56 @@
+#[cfg(feature = "brotli")]
+use crate::constants::CACHE_DECOMPRESSION_FAILED_EVENT_ID;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "brotli")]
+use log::{Level, LevelFilter};
+use serde::{Deserialize, Serialize};
+
 /// A cache API used to make custom cache implementations.
+///
+/// The methods are called synchronously on the async runtime's worker threads, so they should return quickly.
+/// [`ConfigCache::read`] is called on each evaluation that needs the config JSON; it doesn't block the other
+/// evaluations of the [`crate::Client`], but the calling one waits for it. Implementations backed by a remote
+/// store (e.g. Redis) should use short timeouts and return `None` when the store is slow or unavailable.
 pub trait ConfigCache: Sync + Send {
     /// Gets the actual value from the cache identified by the given `key`.
     fn read(&self, key: &str) -> Option<String>;
 
     /// Writes the given `value` to the cache by the given `key`.
     fn write(&self, key: &str, value: &str);
+
+    /// Called after an entry stored with a previous serialization format under `legacy_key`
+    /// was migrated and written to the cache by the given `key`.
+    ///
+    /// The default implementation does nothing, custom caches can use it to remove the legacy entry.
+    fn migrated(&self, legacy_key: &str, key: &str) {
+        _ = (legacy_key, key);
+    }
+}
+
+/// A config JSON with its HTTP ETag and fetch time, exported by [`crate::Client::export_cache_entry`] from a
+/// warm client and preloaded into a new one with [`crate::ClientBuilder::preload_cache_entry`].
+///
+/// The entry is serializable, so it can be handed over e.g. between the processes of a blue/green deployment.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::CacheEntry;
+///
+/// let entry: CacheEntry = serde_json::from_str(r#"{
+///     "config_json": "{\"f\":{}}",
+///     "etag": "\"etag\"",
+///     "fetch_time": "2024-01-01T12:00:00Z"
+/// }"#).unwrap();
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CacheEntry {
+    /// The config JSON as downloaded from the ConfigCat CDN (without the flag overrides).
+    pub config_json: String,
+    /// The HTTP ETag of the config JSON.
+    pub etag: String,
+    /// The time when the config JSON was downloaded.
+    pub fetch_time: DateTime<Utc>,
 }
 
 pub struct EmptyConfigCache {}
@@ -21,3 +67,178 @@ impl ConfigCache for EmptyConfigCache {
     }
     fn write(&self, _: &str, _: &str) {}
 }
+
+/// Describes how the config JSON is compressed before it's written to the [`ConfigCache`].
+///
+/// Compressed entries are tagged with a header and Base64-encoded, so custom [`ConfigCache`] implementations
+/// keep receiving opaque strings. Entries are decompressed on read based on their header, so clients with
+/// and without compression can share the same cache during a rollout.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{CacheCompression, Client};
+///
+/// let builder = Client::builder("sdk-key")
+///     .cache_compression(CacheCompression::Brotli);
+/// ```
+#[cfg(feature = "brotli")]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CacheCompression {
+    /// The config JSON is written to the cache as it is.
+    #[default]
+    None,
+    /// The config JSON is compressed with Brotli.
+    Brotli,
+}
+
+#[cfg(feature = "brotli")]
+const BROTLI_HEADER: &str = "br1:";
+
+/// The entries are compressed on the task writing the cache, so a mid-range quality is used to keep that fast.
+#[cfg(feature = "brotli")]
+const BROTLI_QUALITY: u32 = 5;
+
+/// Compresses the entries written to and decompresses the entries read from the wrapped cache.
+#[cfg(feature = "brotli")]
+pub(crate) struct CompressedConfigCache {
+    inner: Box<dyn ConfigCache>,
+    compression: CacheCompression,
+    log_level: LevelFilter,
+}
+
+#[cfg(feature = "brotli")]
+impl CompressedConfigCache {
+    pub(crate) fn new(
+        inner: Box<dyn ConfigCache>,
+        compression: CacheCompression,
+        log_level: LevelFilter,
+    ) -> Self {
+        Self {
+            inner,
+            compression,
+            log_level,
+        }
+    }
+}
+
+#[cfg(feature = "brotli")]
+impl ConfigCache for CompressedConfigCache {
+    fn read(&self, key: &str) -> Option<String> {
+        let value = self.inner.read(key)?;
+        let Some(compressed) = value.strip_prefix(BROTLI_HEADER) else {
+            return Some(value);
+        };
+        match brotli_decompress(compressed) {
+            Ok(decompressed) => Some(decompressed),
+            Err(err) => {
+                filtered_log!(self.log_level, Level::Warn, event_id = CACHE_DECOMPRESSION_FAILED_EVENT_ID; "Error occurred while decompressing the cache entry. ({err})");
+                None
+            }
+        }
+    }
+
+    fn write(&self, key: &str, value: &str) {
+        match self.compression {
+            CacheCompression::None => self.inner.write(key, value),
+            CacheCompression::Brotli => {
+                self.inner.write(key, brotli_compress(value).as_str());
+            }
+        }
+    }
+
+    fn migrated(&self, legacy_key: &str, key: &str) {
+        self.inner.migrated(legacy_key, key);
+    }
+}
+
+#[cfg(feature = "brotli")]
+fn brotli_compress(value: &str) -> String {
+    use base64::Engine;
+    use std::io::Write;
+
+    let mut compressed = Vec::with_capacity(value.len() / 4);
+    {
+        let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, BROTLI_QUALITY, 22);
+        // writing to a `Vec` can't fail
+        _ = writer.write_all(value.as_bytes());
+    }
+    let encoded = base64::engine::general_purpose::STANDARD.encode(compressed);
+    BROTLI_HEADER.to_owned() + encoded.as_str()
+}
+
+#[cfg(feature = "brotli")]
+fn brotli_decompress(value: &str) -> Result<String, String> {
+    use base64::Engine;
+    use std::io::Read;
+
+    let compressed = base64::engine::general_purpose::STANDARD
+        .decode(value)
+        .map_err(|err| err.to_string())?;
+    let mut decompressed = String::new();
+    brotli::Decompressor::new(compressed.as_slice(), 4096)
+        .read_to_string(&mut decompressed)
+        .map_err(|err| err.to_string())?;
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+#[cfg(feature = "brotli")]
+mod cache_tests {
+    use crate::cache::{CompressedConfigCache, BROTLI_HEADER};
+    use crate::{CacheCompression, ConfigCache};
+    use log::LevelFilter;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct MapCache(Mutex<HashMap<String, String>>);
+
+    impl ConfigCache for Arc<MapCache> {
+        fn read(&self, key: &str) -> Option<String> {
+            self.0.lock().unwrap().get(key).cloned()
+        }
+
+        fn write(&self, key: &str, value: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(key.to_owned(), value.to_owned());
+        }
+    }
+
+    #[test]
+    fn brotli_round_trip() {
+        let inner = Arc::new(MapCache::default());
+        let cache = CompressedConfigCache::new(
+            Box::new(Arc::clone(&inner)),
+            CacheCompression::Brotli,
+            LevelFilter::Warn,
+        );
+        let entry = format!(
+            "1700000000000\n\"etag\"\n{{\"f\":{{{}}}}}",
+            (0..100)
+                .map(|i| format!("\"flag{i}\":{{\"t\":0,\"v\":{{\"b\":true}}}}"))
+                .collect::<Vec<String>>()
+                .join(",")
+        );
+
+        cache.write("key", entry.as_str());
+        let stored = inner.read("key").unwrap();
+        assert!(stored.starts_with(BROTLI_HEADER));
+        assert!(stored.len() < entry.len() / 4);
+        assert_eq!(cache.read("key").unwrap(), entry);
+
+        let plain = CompressedConfigCache::new(
+            Box::new(Arc::clone(&inner)),
+            CacheCompression::None,
+            LevelFilter::Warn,
+        );
+        assert_eq!(plain.read("key").unwrap(), entry);
+        plain.write("key", "uncompressed");
+        assert_eq!(cache.read("key").unwrap(), "uncompressed");
+
+        inner.write("key", "br1:not base64");
+        assert!(cache.read("key").is_none());
+    }
+}
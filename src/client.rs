@@ -1,17 +1,38 @@
 use crate::builder::{ClientBuilder, Options};
+use crate::constants::{
+    BATCH_CHUNK_SIZE, MISSING_KEY_LOG_CAPACITY, MISSING_KEY_LOG_INTERVAL, PKG_VERSION,
+};
+use crate::diagnostics::{redact_url, DiagnosticsReport};
 use crate::errors::ErrorKind;
-use crate::eval::details::EvaluationDetails;
-use crate::eval::evaluator::{eval, EvalResult};
-use crate::fetch::service::ConfigService;
+use crate::eval::details::{
+    warning_error, AllValuesResult, BatchEvaluation, EvalOptions, EvaluationDetails,
+};
+use crate::eval::evaluator::{eval, eval_log, eval_prepared, EvalResult, Prepared};
+use crate::exposure::{user_id, ExposureDeduper};
+use crate::fetch::fetcher::DataGovernanceStatus;
+use crate::fetch::service::{ConfigResult, ConfigService, ReadyReport, RefreshOutcome};
+use crate::modes::{KeyLookupMode, NotReadyBehavior, PollingMode, StaleBehavior};
 use crate::r#override::OptionalOverrides;
+use crate::utils::{is_offline_by_env, mask_sdk_key, spawn_named};
 use crate::value::{OptionalValueDisplay, Value, ValuePrimitive};
-use crate::{ClientCacheState, ClientError, Setting, User};
-use log::{error, warn};
-use std::any::type_name;
-use std::collections::HashMap;
-use std::fmt::{Debug, Formatter};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use crate::{
+    CacheEntry, ClientCacheState, ClientError, ClientEvent, Config, EvaluationSession,
+    ExposureEvent, FetchCounters, OverrideReport, Setting, SettingsSnapshot, UsageReport, User,
+    ValueSource,
+};
+use chrono::{DateTime, Utc};
+use log::{error, log_enabled, warn, Level};
+use serde::de::DeserializeOwned;
+use std::any::{type_name, Any, TypeId};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display, Formatter};
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, watch, Semaphore};
 use tokio::time::timeout;
 
 /// The main component for evaluating feature flags and settings.
@@ -36,19 +57,44 @@ use tokio::time::timeout;
 pub struct Client {
     options: Arc<Options>,
     service: ConfigService,
-    default_user: Arc<Mutex<Option<User>>>,
+    default_user: Arc<Mutex<Option<Arc<User>>>>,
+    json_cache: RwLock<JsonValueCache>,
+    unknown_variants: Mutex<UnknownVariantLog>,
+    missing_key_logs: Mutex<KeyLogLimiter>,
+    exposures: Option<Arc<ExposureDeduper>>,
 }
 
 impl Client {
     pub(crate) fn with_options(options: Options) -> Result<Self, ClientError> {
         let opts = Arc::new(options);
-        match ConfigService::new(Arc::clone(&opts)) {
-            Ok(service) => Ok(Self {
-                options: Arc::clone(&opts),
-                service,
-                default_user: Arc::new(Mutex::new(opts.default_user().cloned())),
-            }),
-            Err(err) => Err(err),
+        let exposures = opts
+            .exposure_dedup()
+            .map(|(capacity, ttl)| Arc::new(ExposureDeduper::new(capacity, ttl)));
+        let service = ConfigService::new(Arc::clone(&opts))?;
+        Ok(Self::with_service(opts, service, exposures))
+    }
+
+    /// Creates a client of a [`crate::ClientPool`] that shares the exposure deduplication and the usage tracking
+    /// of the pool's `primary` client.
+    pub(crate) fn pooled(options: Options, primary: &Client) -> Result<Self, ClientError> {
+        let opts = Arc::new(options);
+        let service = ConfigService::pooled(Arc::clone(&opts), &primary.service)?;
+        Ok(Self::with_service(opts, service, primary.exposures.clone()))
+    }
+
+    fn with_service(
+        opts: Arc<Options>,
+        service: ConfigService,
+        exposures: Option<Arc<ExposureDeduper>>,
+    ) -> Self {
+        Self {
+            default_user: Arc::new(Mutex::new(opts.default_user().cloned().map(Arc::new))),
+            options: opts,
+            service,
+            json_cache: RwLock::new(JsonValueCache::default()),
+            unknown_variants: Mutex::new(UnknownVariantLog::default()),
+            missing_key_logs: Mutex::new(KeyLogLimiter::default()),
+            exposures,
         }
     }
 
@@ -78,7 +124,9 @@ impl Client {
     ///
     /// # Errors
     ///
-    /// This method fails if the given SDK key is empty or has an invalid format.
+    /// This method fails in the following cases:
+    /// - The given SDK key is empty or has an invalid format.
+    /// - The initialization of the internal [`reqwest::Client`] failed.
     ///
     /// # Examples
     ///
@@ -93,10 +141,15 @@ impl Client {
 
     /// Initiates a force refresh on the cached config JSON data.
     ///
+    /// Concurrent calls are collapsed into one download: the calls made while a refresh is in progress wait for it
+    /// and return [`RefreshOutcome::Coalesced`] (or its error). The returned [`RefreshOutcome`] reports whether
+    /// a download was made; it's [`RefreshOutcome::SkippedOffline`] when the client is switched to offline mode
+    /// with [`Client::offline`].
+    ///
     /// # Errors
     ///
     /// This method fails in the following cases:
-    /// - The SDK is in offline mode.
+    /// - The SDK is in offline mode, or its network activity is switched off by the `CONFIGCAT_SDK_OFFLINE` environment variable.
     /// - The SDK has a [`crate::OverrideBehavior::LocalOnly`] override set.
     /// - The HTTP request that supposed to download the new config JSON fails.
     ///
@@ -109,29 +162,48 @@ impl Client {
     /// async fn main() {
     ///     let client = Client::new("sdk-key").unwrap();
     ///
-    ///     let result = client.refresh().await.unwrap();
+    ///     let outcome = client.refresh().await.unwrap();
     /// }
     /// ```
-    pub async fn refresh(&self) -> Result<(), ClientError> {
-        if self.options.offline() {
-            let err = ClientError::new(
-                ErrorKind::OfflineClient,
-                "Client is in offline mode, it cannot initiate HTTP calls.".to_owned(),
-            );
-            warn!(event_id = err.kind.as_u8(); "{}", err);
-            return Err(err);
-        }
-        if self.options.overrides().is_local() {
-            let err = ClientError::new(
-                ErrorKind::LocalOnlyClient,
-                "Client has local-only overrides, it cannot initiate HTTP calls.".to_owned(),
-            );
-            warn!(event_id = err.kind.as_u8(); "{}", err);
-            return Err(err);
-        }
+    pub async fn refresh(&self) -> Result<RefreshOutcome, ClientError> {
+        self.check_can_refresh()?;
         self.service.refresh().await
     }
 
+    /// Initiates a force refresh on the cached config JSON data when it's older than `max_age`.
+    ///
+    /// The config JSON is read from the configured [`crate::ConfigCache`] first, so a refresh made by
+    /// another instance sharing the cache is picked up without a download. The returned [`RefreshOutcome`]
+    /// reports whether a download was made.
+    ///
+    /// # Errors
+    ///
+    /// This method fails in the following cases:
+    /// - The SDK is in offline mode, or its network activity is switched off by the `CONFIGCAT_SDK_OFFLINE` environment variable.
+    /// - The SDK has a [`crate::OverrideBehavior::LocalOnly`] override set.
+    /// - The HTTP request that supposed to download the new config JSON fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat::{Client, RefreshOutcome};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let outcome = client.refresh_if_older(Duration::from_secs(60)).await.unwrap();
+    ///     if outcome == RefreshOutcome::Fetched {
+    ///         println!("config JSON downloaded");
+    ///     }
+    /// }
+    /// ```
+    pub async fn refresh_if_older(&self, max_age: Duration) -> Result<RefreshOutcome, ClientError> {
+        self.check_can_refresh()?;
+        self.service.refresh_if_older(max_age).await
+    }
+
     /// Evaluates a feature flag or setting identified by the given `key`.
     ///
     /// Returns `default` if the flag doesn't exist, or there was an error during the evaluation.
@@ -155,11 +227,12 @@ impl Client {
         default: T,
         user: Option<User>,
     ) -> T {
-        self.get_value_details(key, default, user).await.value
+        let result = self.service.config().await;
+        self.eval_value_details(&result, key, default, user.as_ref())
+            .value
     }
 
-    /// The same as [`Client::get_value`] but returns an [`EvaluationDetails`] that
-    /// contains additional information about the result of the evaluation process.
+    /// The same as [`Client::get_value`] but takes the user by reference, so it doesn't have to be cloned for each evaluation.
     ///
     /// # Examples
     ///
@@ -171,51 +244,52 @@ impl Client {
     ///     let client = Client::new("sdk-key").unwrap();
     ///
     ///     let user = User::new("user-id");
-    ///     let details = client.get_value_details("flag-key", String::default(), Some(user)).await;
+    ///     let value = client.get_value_ref("flag-key", false, Some(&user)).await;
     /// }
     /// ```
-    pub async fn get_value_details<T: ValuePrimitive + Clone + Default>(
+    pub async fn get_value_ref<T: ValuePrimitive + Clone + Default>(
         &self,
         key: &str,
         default: T,
-        user: Option<User>,
-    ) -> EvaluationDetails<T> {
+        user: Option<&User>,
+    ) -> T {
         let result = self.service.config().await;
-        let mut eval_user = user;
-        if eval_user.is_none() {
-            eval_user = self.read_def_user();
-        }
-        match eval_flag(
-            &result.config().settings,
-            key,
-            eval_user.as_ref(),
-            Some(&default.clone().into()),
-        ) {
-            Ok(eval_result) => {
-                if let Some(val) = T::from_value(&eval_result.value) {
-                    EvaluationDetails {
-                        value: val,
-                        key: key.to_owned(),
-                        user: eval_user,
-                        fetch_time: Some(*result.fetch_time()),
-                        ..eval_result.into()
-                    }
-                } else {
-                    let err = ClientError::new(ErrorKind::SettingValueTypeMismatch, format!("The type of a setting must match the requested type. Setting's type was '{}' but the requested type was '{}'. Learn more: https://configcat.com/docs/sdk-reference/rust/#setting-type-mapping", eval_result.setting_type, type_name::<T>()));
-                    error!(event_id = err.kind.as_u8(); "{}", err);
-                    EvaluationDetails::from_err(default, key, eval_user, err)
-                }
-            }
-            Err(err) => {
-                error!(event_id = err.kind.as_u8(); "{}", err);
-                EvaluationDetails::from_err(default, key, eval_user, err)
-            }
-        }
+        self.eval_value_details(&result, key, default, user).value
     }
 
-    /// Evaluates a feature flag identified by the given `key`.
+    /// Evaluates a feature flag or setting identified by the given `key` synchronously, for contexts where
+    /// awaiting is impossible (e.g. `Drop` implementations or panic hooks).
     ///
-    /// Returns an [`EvaluationDetails`] that contains the evaluated feature flag's value in a [`Value`] variant.
+    /// The evaluation uses the config published by the latest evaluation, refresh or poll of the [`Client`],
+    /// so it never waits for a download or the lock of the cached config. **The result may be stale**:
+    /// a newer config JSON in the [`crate::ConfigCache`] is not picked up, and `default` is returned until
+    /// the first config JSON is loaded. The default user (if any) is used for the evaluation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use configcat::Client;
+    ///
+    /// struct Connection {
+    ///     client: Arc<Client>,
+    /// }
+    ///
+    /// impl Drop for Connection {
+    ///     fn drop(&mut self) {
+    ///         if self.client.cached_value("graceful-shutdown", false) {
+    ///             // close the connection gracefully
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn cached_value<T: ValuePrimitive + Clone + Default>(&self, key: &str, default: T) -> T {
+        let result = self.service.snapshot();
+        self.eval_value_details(&result, key, default, None).value
+    }
+
+    /// The same as [`Client::get_value`] but returns an [`EvaluationDetails`] that
+    /// contains additional information about the result of the evaluation process.
     ///
     /// # Examples
     ///
@@ -227,41 +301,23 @@ impl Client {
     ///     let client = Client::new("sdk-key").unwrap();
     ///
     ///     let user = User::new("user-id");
-    ///     let details = client.get_flag_details("flag-key", Some(user)).await;
+    ///     let details = client.get_value_details("flag-key", String::default(), Some(user)).await;
     /// }
     /// ```
-    pub async fn get_flag_details(
+    pub async fn get_value_details<T: ValuePrimitive + Clone + Default>(
         &self,
         key: &str,
+        default: T,
         user: Option<User>,
-    ) -> EvaluationDetails<Option<Value>> {
+    ) -> EvaluationDetails<T> {
         let result = self.service.config().await;
-        let mut eval_user = user;
-        if eval_user.is_none() {
-            eval_user = self.read_def_user();
-        }
-        match eval_flag(&result.config().settings, key, eval_user.as_ref(), None) {
-            Ok(eval_result) => EvaluationDetails {
-                value: Some(eval_result.value),
-                key: key.to_owned(),
-                user: eval_user,
-                fetch_time: Some(*result.fetch_time()),
-                is_default_value: false,
-                variation_id: eval_result.variation_id,
-                matched_targeting_rule: eval_result.rule,
-                matched_percentage_option: eval_result.option,
-                error: None,
-            },
-            Err(err) => {
-                error!(event_id = err.kind.as_u8(); "{}", err);
-                EvaluationDetails::from_err(None, key, eval_user, err)
-            }
-        }
+        self.eval_value_details_with_user(&result, key, default, user, &EvalOptions::default())
     }
 
-    /// Evaluates all feature flags and settings.
+    /// The same as [`Client::get_value_details`] but takes the user by reference.
     ///
-    /// Returns a [`HashMap`] of [`String`] keys and evaluated [`Value`]s.
+    /// The user is cloned into the returned [`EvaluationDetails`] only when `include_user` is `true`,
+    /// otherwise [`EvaluationDetails::user`] is `None`.
     ///
     /// # Examples
     ///
@@ -273,160 +329,328 @@ impl Client {
     ///     let client = Client::new("sdk-key").unwrap();
     ///
     ///     let user = User::new("user-id");
-    ///     let values = client.get_all_values(Some(user)).await;
+    ///     let details = client.get_value_details_ref("flag-key", String::default(), Some(&user), false).await;
     /// }
     /// ```
-    pub async fn get_all_values(&self, user: Option<User>) -> HashMap<String, Value> {
-        let details = self.get_all_value_details(user).await;
-        let mut result = HashMap::<String, Value>::with_capacity(details.len());
-        for detail in details {
-            if let Some(val) = detail.value {
-                result.insert(detail.key, val);
-            }
+    pub async fn get_value_details_ref<T: ValuePrimitive + Clone + Default>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<&User>,
+        include_user: bool,
+    ) -> EvaluationDetails<T> {
+        let result = self.service.config().await;
+        if include_user {
+            return self.eval_value_details_with_user(
+                &result,
+                key,
+                default,
+                user.cloned(),
+                &EvalOptions::default(),
+            );
         }
-        result
+        self.eval_value_details(&result, key, default, user)
     }
 
-    /// The same as [`Client::get_all_values`] but returns a [`Vec`] of [`EvaluationDetails`] that
-    /// contains additional information about each evaluation process and the evaluated
-    /// feature flag values in [`Value`] variants.
+    /// The same as [`Client::get_value_details`] but evaluates with the given [`EvalOptions`].
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use configcat::{Client, User};
+    /// use chrono::{TimeZone, Utc};
+    /// use configcat::{Client, EvalOptions, User};
     ///
     /// #[tokio::main]
     /// async fn main() {
     ///     let client = Client::new("sdk-key").unwrap();
     ///
     ///     let user = User::new("user-id");
-    ///     let all_details = client.get_all_value_details(Some(user)).await;
+    ///     let options = EvalOptions { now: Some(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap()), ..EvalOptions::default() };
+    ///     let details = client.get_value_details_with_options("flag-key", false, Some(user), &options).await;
     /// }
     /// ```
-    pub async fn get_all_value_details(
+    pub async fn get_value_details_with_options<T: ValuePrimitive + Clone + Default>(
         &self,
+        key: &str,
+        default: T,
         user: Option<User>,
-    ) -> Vec<EvaluationDetails<Option<Value>>> {
-        let config_result = self.service.config().await;
-        let mut eval_user = user;
-        if eval_user.is_none() {
-            eval_user = self.read_def_user();
-        }
-        let settings = &config_result.config().settings;
-        let mut result = Vec::<EvaluationDetails<Option<Value>>>::with_capacity(settings.len());
-        for k in settings.keys() {
-            let usr_clone = eval_user.clone();
-            let details = match eval_flag(settings, k, usr_clone.as_ref(), None) {
-                Ok(eval_result) => EvaluationDetails {
-                    value: Some(eval_result.value),
-                    key: k.to_owned(),
-                    user: usr_clone,
-                    fetch_time: Some(*config_result.fetch_time()),
-                    variation_id: eval_result.variation_id,
-                    matched_targeting_rule: eval_result.rule,
-                    matched_percentage_option: eval_result.option,
-                    ..EvaluationDetails::default()
-                },
-                Err(err) => {
-                    error!(event_id = err.kind.as_u8(); "{}", err);
-                    EvaluationDetails::from_err(None, k, usr_clone, err)
-                }
-            };
-            result.push(details);
-        }
-        result
+        options: &EvalOptions,
+    ) -> EvaluationDetails<T> {
+        let result = self.service.config().await;
+        self.eval_value_details_with_user(&result, key, default, user, options)
     }
 
-    /// Returns the keys of all feature flags and settings.
+    /// Evaluates a text setting identified by the given `key` and deserializes its value from JSON into `T`.
     ///
-    /// If there's no config JSON to work on, this method returns an empty [`Vec`].
+    /// Returns `default` if the setting doesn't exist, it's not a text setting, its value is not a valid JSON
+    /// representation of `T`, or there was an error during the evaluation.
+    ///
+    /// Parsed values are cached until a new config JSON is downloaded, so repeated calls
+    /// with the same evaluated text don't deserialize it again.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use configcat::{Client, User};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Clone, Default)]
+    /// struct Banner {
+    ///     title: String,
+    ///     visible: bool,
+    /// }
     ///
     /// #[tokio::main]
     /// async fn main() {
     ///     let client = Client::new("sdk-key").unwrap();
     ///
     ///     let user = User::new("user-id");
-    ///     let keys = client.get_all_keys().await;
+    ///     let banner = client.get_json_value("banner", Banner::default(), Some(user)).await;
     /// }
     /// ```
-    pub async fn get_all_keys(&self) -> Vec<String> {
-        let config_result = self.service.config().await;
-        let settings = &config_result.config().settings;
-        if !settings.is_empty() {
-            return settings.keys().cloned().collect();
+    pub async fn get_json_value<T: DeserializeOwned + Clone + Send + Sync + 'static>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<User>,
+    ) -> T {
+        let result = self.service.config().await;
+        self.eval_json_value(&result, key, default, user.as_ref())
+    }
+
+    pub(crate) fn eval_json_value<T: DeserializeOwned + Clone + Send + Sync + 'static>(
+        &self,
+        result: &ConfigResult,
+        key: &str,
+        default: T,
+        user: Option<&User>,
+    ) -> T {
+        let details = self.eval_value_details(result, key, String::default(), user);
+        if details.error.is_some() {
+            return default;
+        }
+        match self.parse_json::<T>(result.etag(), &details.value) {
+            Ok(value) => value,
+            Err(err) => {
+                let err = ClientError::new(
+                    ErrorKind::SettingValueParseFailure,
+                    format!("Failed to deserialize the value of setting '{key}' from JSON to '{}' ({err}). Returning the `defaultValue` parameter that you specified in your application.", type_name::<T>()),
+                );
+                error!(event_id = err.kind.as_u8(); "{err}");
+                default
+            }
         }
-        error!(event_id = 1000; "Config JSON is not present. Returning empty vector.");
-        vec![]
     }
 
-    /// Puts the [`Client`] into offline mode.
+    /// Evaluates a text setting identified by the given `key` and parses its value into `E` with [`FromStr`].
     ///
-    /// In this mode the SDK is not allowed to initiate HTTP request and works only from the configured cache.
+    /// Returns `E::default()` if the setting doesn't exist, it's not a text setting, its value is not a known
+    /// variant of `E`, or there was an error during the evaluation. Each unknown variant is logged only once per key.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use configcat::Client;
+    /// use std::str::FromStr;
+    /// use configcat::{Client, User};
+    ///
+    /// #[derive(Default)]
+    /// enum CheckoutFlow {
+    ///     #[default]
+    ///     V1,
+    ///     V2,
+    /// }
+    ///
+    /// impl FromStr for CheckoutFlow {
+    ///     type Err = ();
+    ///
+    ///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ///         match s {
+    ///             "v1" => Ok(CheckoutFlow::V1),
+    ///             "v2" => Ok(CheckoutFlow::V2),
+    ///             _ => Err(()),
+    ///         }
+    ///     }
+    /// }
     ///
     /// #[tokio::main]
     /// async fn main() {
     ///     let client = Client::new("sdk-key").unwrap();
     ///
-    ///     client.offline();
+    ///     let user = User::new("user-id");
+    ///     let flow: CheckoutFlow = client.get_enum_value("checkout_flow", Some(user)).await;
     /// }
     /// ```
-    pub fn offline(&self) {
-        self.service.set_mode(true);
+    pub async fn get_enum_value<E: FromStr + Default>(&self, key: &str, user: Option<User>) -> E {
+        let result = self.service.config().await;
+        self.eval_enum_value(&result, key, user.as_ref())
     }
 
-    /// Puts the [`Client`] into online mode.
+    pub(crate) fn eval_enum_value<E: FromStr + Default>(
+        &self,
+        result: &ConfigResult,
+        key: &str,
+        user: Option<&User>,
+    ) -> E {
+        let details = self.eval_value_details(result, key, String::default(), user);
+        if details.error.is_some() {
+            return E::default();
+        }
+        if let Ok(value) = details.value.parse::<E>() {
+            return value;
+        }
+        if self.first_unknown_variant(result.etag(), key, &details.value) {
+            let err = ClientError::new(
+                ErrorKind::SettingValueParseFailure,
+                format!("Failed to parse the value '{}' of setting '{key}' to '{}' (unknown variant). Returning the default variant.", details.value, type_name::<E>()),
+            );
+            error!(event_id = err.kind.as_u8(); "{err}");
+        }
+        E::default()
+    }
+
+    /// Evaluates a feature flag or setting identified by the given `key` and converts its value into `T`
+    /// with [`TryFrom<Value>`].
     ///
-    /// In this mode the SDK initiates HTTP requests to fetch the latest config JSON data.
+    /// Returns `default` if the setting doesn't exist, the conversion fails, or there was an error during the evaluation.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use configcat::Client;
+    /// use configcat::{Client, User, Value};
+    ///
+    /// enum RolloutStage {
+    ///     Internal,
+    ///     Public,
+    /// }
+    ///
+    /// impl TryFrom<Value> for RolloutStage {
+    ///     type Error = String;
+    ///
+    ///     fn try_from(value: Value) -> Result<Self, Self::Error> {
+    ///         match value.as_str().as_deref() {
+    ///             Some("internal") => Ok(RolloutStage::Internal),
+    ///             Some("public") => Ok(RolloutStage::Public),
+    ///             _ => Err(format!("'{value}' is not a rollout stage")),
+    ///         }
+    ///     }
+    /// }
     ///
     /// #[tokio::main]
     /// async fn main() {
     ///     let client = Client::new("sdk-key").unwrap();
     ///
-    ///     client.online();
+    ///     let user = User::new("user-id");
+    ///     let stage = client.get_custom_value("rollout_stage", RolloutStage::Internal, Some(user)).await;
     /// }
     /// ```
-    pub fn online(&self) {
-        self.service.set_mode(false);
+    pub async fn get_custom_value<T>(&self, key: &str, default: T, user: Option<User>) -> T
+    where
+        T: TryFrom<Value>,
+        T::Error: Display,
+    {
+        self.get_custom_value_details(key, default, user)
+            .await
+            .value
     }
 
-    /// Returns `true` when the SDK is configured not to initiate HTTP requests, otherwise `false`.
+    /// The same as [`Client::get_custom_value`] but returns an [`EvaluationDetails`] that
+    /// contains additional information about the result of the evaluation process.
+    ///
+    /// When the conversion fails, [`EvaluationDetails::error`] contains a [`ErrorKind::SettingValueParseFailure`]
+    /// error with the message of the conversion error.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use configcat::Client;
+    /// use configcat::{Client, User, Value};
+    ///
+    /// struct Percent(u8);
+    ///
+    /// impl TryFrom<Value> for Percent {
+    ///     type Error = String;
+    ///
+    ///     fn try_from(value: Value) -> Result<Self, Self::Error> {
+    ///         match value.as_int() {
+    ///             Some(val @ 0..=100) => Ok(Percent(val as u8)),
+    ///             _ => Err(format!("'{value}' is not a percentage")),
+    ///         }
+    ///     }
+    /// }
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::builder("sdk-key")
-    ///         .offline(true)
-    ///         .build()
-    ///         .unwrap();
+    ///     let client = Client::new("sdk-key").unwrap();
     ///
-    ///     let offline = client.is_offline();
+    ///     let user = User::new("user-id");
+    ///     let details = client.get_custom_value_details("discount", Percent(0), Some(user)).await;
+    ///     if let Some(err) = details.error {
+    ///         println!("{err}");
+    ///     }
     /// }
     /// ```
-    pub fn is_offline(&self) -> bool {
-        self.service.is_offline()
+    pub async fn get_custom_value_details<T>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<User>,
+    ) -> EvaluationDetails<T>
+    where
+        T: TryFrom<Value>,
+        T::Error: Display,
+    {
+        let result = self.service.config().await;
+        self.eval_custom_value_details(&result, key, default, user)
     }
 
-    /// Sets the default user.
+    pub(crate) fn eval_custom_value_details<T>(
+        &self,
+        result: &ConfigResult,
+        key: &str,
+        default: T,
+        user: Option<User>,
+    ) -> EvaluationDetails<T>
+    where
+        T: TryFrom<Value>,
+        T::Error: Display,
+    {
+        let mut details = self.eval_flag_details(result, key, user);
+        let Some(value) = details.value.take() else {
+            return details.with_value(default);
+        };
+        if details.value_source == ValueSource::DefaultValueProvider {
+            // the evaluation failed already, so its error is kept when the provided value can't be converted either
+            if let Ok(value) = T::try_from(value) {
+                return details.with_value(value);
+            }
+            details.value_source = ValueSource::DefaultParameter;
+            return details.with_value(default);
+        }
+        match T::try_from(value) {
+            Ok(value) => details.with_value(value),
+            Err(err) => {
+                let err = ClientError::new(
+                    ErrorKind::SettingValueParseFailure,
+                    format!("Failed to convert the value of setting '{key}' to '{}' ({err}). Returning the `defaultValue` parameter that you specified in your application.", type_name::<T>()),
+                );
+                error!(event_id = err.kind.as_u8(); "{err}");
+                details.is_default_value = true;
+                details.error = Some(err);
+                if let Some(value) = self
+                    .provided_default(key)
+                    .and_then(|value| T::try_from(value).ok())
+                {
+                    details.value_source = ValueSource::DefaultValueProvider;
+                    return details.with_value(value);
+                }
+                details.value_source = ValueSource::DefaultParameter;
+                details.with_value(default)
+            }
+        }
+    }
+
+    /// Evaluates a feature flag identified by the given `key`.
+    ///
+    /// Returns an [`EvaluationDetails`] that contains the evaluated feature flag's value in a [`Value`] variant.
     ///
     /// # Examples
     ///
@@ -435,116 +659,1649 @@ impl Client {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let mut client = Client::new("sdk-key").unwrap();
+    ///     let client = Client::new("sdk-key").unwrap();
     ///
-    ///     client.set_default_user(User::new("user-id"));
+    ///     let user = User::new("user-id");
+    ///     let details = client.get_flag_details("flag-key", Some(user)).await;
     /// }
     /// ```
-    pub fn set_default_user(&mut self, user: User) {
-        self.set_def_user(Some(user));
-    }
+    pub async fn get_flag_details(
+        &self,
+        key: &str,
+        user: Option<User>,
+    ) -> EvaluationDetails<Option<Value>> {
+        let result = self.service.config().await;
+        self.eval_flag_details(&result, key, user)
+    }
+
+    pub(crate) fn eval_flag_details(
+        &self,
+        result: &ConfigResult,
+        key: &str,
+        user: Option<User>,
+    ) -> EvaluationDetails<Option<Value>> {
+        self.service.record_usage(key);
+        let mut eval_user = user;
+        if eval_user.is_none() {
+            eval_user = self.read_def_user(key);
+        }
+        if let Some(behavior) = self.stale_behavior(result) {
+            return self
+                .with_provided_flag_default(stale_details(result, key, None, eval_user, behavior));
+        }
+        let setting_key = self.prefixed_key(key);
+        match eval_flag(
+            &result.config().settings,
+            &setting_key,
+            eval_user.as_ref(),
+            None,
+            self.options.key_lookup_mode(),
+            &self.options.eval_options(&EvalOptions::default()),
+        ) {
+            Ok(eval_result) => {
+                self.report_exposure(
+                    key,
+                    &eval_result.value,
+                    eval_result.variation_id.as_ref(),
+                    eval_user.as_ref(),
+                );
+                EvaluationDetails {
+                    value: Some(eval_result.value),
+                    key: key.to_owned(),
+                    user: eval_user,
+                    fetch_time: Some(*result.fetch_time()),
+                    config_age: result.config_age(),
+                    last_fetch: result.last_fetch(),
+                    is_default_value: false,
+                    value_source: ValueSource::Evaluation,
+                    variation_id: eval_result.variation_id,
+                    setting_type: Some(eval_result.setting_type),
+                    matched_targeting_rule: eval_result.rule,
+                    matched_percentage_option: eval_result.option,
+                    matched_rule_index: eval_result.rule_index,
+                    rule_outcomes: eval_result.rule_outcomes,
+                    prerequisites: eval_result.prerequisites,
+                    condition_timings: eval_result.condition_timings,
+                    config: self.details_config(result),
+                    error: eval_result.warning.map(warning_error),
+                }
+            }
+            Err(err) => {
+                let err = self.check_not_ready(err, key);
+                self.log_eval_error(&err, &setting_key, &result.config().settings);
+                self.with_provided_flag_default(EvaluationDetails::from_err(
+                    None, key, eval_user, err,
+                ))
+            }
+        }
+    }
+
+    /// Evaluates all feature flags and settings.
+    ///
+    /// Returns a [`HashMap`] of [`String`] keys and evaluated [`Value`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let user = User::new("user-id");
+    ///     let values = client.get_all_values(Some(user)).await;
+    /// }
+    /// ```
+    pub async fn get_all_values(&self, user: Option<User>) -> HashMap<String, Value> {
+        let details = self.get_all_value_details(user).await;
+        values_of(details)
+    }
+
+    /// The same as [`Client::get_all_values`] but also returns the errors of the evaluations that failed,
+    /// so callers can tell apart the feature flags that failed to evaluate from the missing ones.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let result = client.get_all_values_strict(Some(User::new("user-id"))).await;
+    ///     for (key, err) in &result.errors {
+    ///         println!("'{key}' failed to evaluate: {err}");
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_all_values_strict(&self, user: Option<User>) -> AllValuesResult {
+        let details = self.get_all_value_details(user).await;
+        AllValuesResult::from_details(details)
+    }
+
+    /// The same as [`Client::get_all_values`] but returns a [`Vec`] of [`EvaluationDetails`] that
+    /// contains additional information about each evaluation process and the evaluated
+    /// feature flag values in [`Value`] variants.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let user = User::new("user-id");
+    ///     let all_details = client.get_all_value_details(Some(user)).await;
+    /// }
+    /// ```
+    pub async fn get_all_value_details(
+        &self,
+        user: Option<User>,
+    ) -> Vec<EvaluationDetails<Option<Value>>> {
+        let config_result = self.service.config().await;
+        let settings = &config_result.config().settings;
+        let mut result = Vec::<EvaluationDetails<Option<Value>>>::with_capacity(settings.len());
+        for (i, (k, key)) in settings
+            .keys()
+            .filter_map(|k| self.unprefixed_key(k).map(|key| (k, key)))
+            .enumerate()
+        {
+            self.eval_yield_point(i, settings.len()).await;
+            result.push(self.eval_setting_details(&config_result, k, key, user.as_ref()));
+        }
+        result
+    }
+
+    pub(crate) fn eval_all_value_details(
+        &self,
+        config_result: &ConfigResult,
+        user: Option<&User>,
+    ) -> Vec<EvaluationDetails<Option<Value>>> {
+        config_result
+            .config()
+            .settings
+            .keys()
+            .filter_map(|k| self.unprefixed_key(k).map(|key| (k, key)))
+            .map(|(k, key)| self.eval_setting_details(config_result, k, key, user))
+            .collect()
+    }
+
+    fn eval_setting_details(
+        &self,
+        config_result: &ConfigResult,
+        setting_key: &str,
+        key: &str,
+        user: Option<&User>,
+    ) -> EvaluationDetails<Option<Value>> {
+        let settings = &config_result.config().settings;
+        let usr_clone = user.cloned().or_else(|| self.read_def_user(key));
+        if let Some(behavior) = self.stale_behavior(config_result) {
+            return stale_details(config_result, key, None, usr_clone, behavior);
+        }
+        match eval_flag(
+            settings,
+            setting_key,
+            usr_clone.as_ref(),
+            None,
+            KeyLookupMode::Exact,
+            &self.options.eval_options(&EvalOptions::default()),
+        ) {
+            Ok(eval_result) => EvaluationDetails {
+                value: Some(eval_result.value),
+                key: key.to_owned(),
+                user: usr_clone,
+                fetch_time: Some(*config_result.fetch_time()),
+                config_age: config_result.config_age(),
+                last_fetch: config_result.last_fetch(),
+                variation_id: eval_result.variation_id,
+                setting_type: Some(eval_result.setting_type),
+                matched_targeting_rule: eval_result.rule,
+                matched_percentage_option: eval_result.option,
+                matched_rule_index: eval_result.rule_index,
+                rule_outcomes: eval_result.rule_outcomes,
+                prerequisites: eval_result.prerequisites,
+                condition_timings: eval_result.condition_timings,
+                config: self.details_config(config_result),
+                error: eval_result.warning.map(warning_error),
+                ..EvaluationDetails::default()
+            },
+            Err(err) => {
+                self.log_eval_error(&err, setting_key, settings);
+                EvaluationDetails::from_err(None, key, usr_clone, err)
+            }
+        }
+    }
+
+    /// Yields to the executor after every configured number of evaluations when the config is large,
+    /// see [`ClientBuilder::evaluation_yield`].
+    async fn eval_yield_point(&self, evaluated: usize, total: usize) {
+        let (threshold, interval) = self.options.evaluation_yield();
+        if total > threshold && evaluated > 0 && evaluated.is_multiple_of(interval) {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    /// Returns the variation ID of the value that the feature flag or setting identified by `key` evaluates to,
+    /// e.g. for analytics that only need the variation IDs.
+    ///
+    /// Unlike [`Client::get_flag_details`], this method doesn't build an [`EvaluationDetails`].
+    /// Returns `None` when the evaluation fails or the evaluated value has no variation ID.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let user = User::new("user-id");
+    ///     let variation_id = client.get_variation_id("flag-key", Some(user)).await;
+    /// }
+    /// ```
+    pub async fn get_variation_id(&self, key: &str, user: Option<User>) -> Option<String> {
+        self.service.record_usage(key);
+        let result = self.service.config().await;
+        let setting_key = self.prefixed_key(key);
+        self.eval_variation_id(
+            &result,
+            key,
+            &setting_key,
+            self.options.key_lookup_mode(),
+            user.as_ref(),
+        )
+    }
+
+    /// Returns the variation IDs of the values that all feature flags and settings evaluate to, keyed by
+    /// the feature flag or setting keys. Feature flags whose evaluated value has no variation ID are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let user = User::new("user-id");
+    ///     for (key, variation_id) in client.get_all_variation_ids(Some(user)).await {
+    ///         println!("{key}: {variation_id}");
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_all_variation_ids(&self, user: Option<User>) -> HashMap<String, String> {
+        let result = self.service.config().await;
+        let settings = &result.config().settings;
+        let mut variation_ids = HashMap::with_capacity(settings.len());
+        for (i, (k, key)) in settings
+            .keys()
+            .filter_map(|k| self.unprefixed_key(k).map(|key| (k, key)))
+            .enumerate()
+        {
+            self.eval_yield_point(i, settings.len()).await;
+            if let Some(variation_id) =
+                self.eval_variation_id(&result, key, k, KeyLookupMode::Exact, user.as_ref())
+            {
+                variation_ids.insert(key.to_owned(), variation_id);
+            }
+        }
+        variation_ids
+    }
+
+    /// Returns the keys of all feature flags and settings.
+    ///
+    /// If there's no config JSON to work on, this method returns an empty [`Vec`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let user = User::new("user-id");
+    ///     let keys = client.get_all_keys().await;
+    /// }
+    /// ```
+    pub async fn get_all_keys(&self) -> Vec<String> {
+        let config_result = self.service.config().await;
+        self.all_keys(&config_result)
+    }
+
+    /// Evaluates a feature flag or setting identified by the given `key` for each user of `users`.
+    ///
+    /// The config JSON is resolved once for the whole batch, along with the per-condition structures of
+    /// the setting and its prerequisite flags (hash sets of the comparison values, parsed semantic versions).
+    /// The users are evaluated in chunks in parallel on tokio's blocking thread pool.
+    /// The results are delivered through the returned [`mpsc::Receiver`] as they become available, so their order may differ from the order of `users`.
+    /// Dropping the receiver stops the evaluation of the remaining users.
+    /// When the config JSON is older than the age set with [`ClientBuilder::max_config_age`], each user gets a `None` value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let users = vec![User::new("user-1"), User::new("user-2")];
+    ///     let mut results = client.evaluate_batch("flag-key", users).await;
+    ///     while let Some(result) = results.recv().await {
+    ///         println!("{}: {:?}", result.identifier, result.value);
+    ///     }
+    /// }
+    /// ```
+    pub async fn evaluate_batch<I>(&self, key: &str, users: I) -> mpsc::Receiver<BatchEvaluation>
+    where
+        I: IntoIterator<Item = User>,
+        I::IntoIter: Send + 'static,
+    {
+        self.service.record_usage(key);
+        let result = self.service.config().await;
+        let config = Arc::clone(result.config());
+        let setting_key = if let Some(behavior) = self.stale_behavior(&result) {
+            log_stale(
+                &result,
+                key,
+                behavior,
+                "Returning `None` for each user of the batch.",
+            );
+            None
+        } else {
+            let setting_key = if config.settings.is_empty() {
+                Err(ClientError::new(ErrorKind::ConfigJsonNotAvailable, format!("Config JSON is not present when evaluating setting '{key}'. Returning `None` for each user of the batch.")))
+            } else {
+                let key = self.prefixed_key(key);
+                find_setting(&config.settings, &key, self.options.key_lookup_mode(), None)
+                    .map(|(setting_key, _)| setting_key.to_owned())
+            };
+            match setting_key {
+                Ok(setting_key) => Some(Arc::new(setting_key)),
+                Err(err) => {
+                    let err = self.check_not_ready(err, key);
+                    self.log_eval_error(&err, key, &config.settings);
+                    None
+                }
+            }
+        };
+
+        let options = self.options.eval_options(&EvalOptions::default());
+        let prepared = Arc::new(setting_key.as_ref().map_or_else(Prepared::default, |key| {
+            Prepared::new(
+                &config.settings[key.as_str()],
+                key,
+                &config.settings,
+                &options,
+            )
+        }));
+        let mut users = users.into_iter();
+        let (tx, rx) = mpsc::channel(BATCH_CHUNK_SIZE);
+        spawn_named("configcat-batch-eval", async move {
+            let workers = Arc::new(Semaphore::new(
+                std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            ));
+            let failure_logged = Arc::new(AtomicBool::new(false));
+            while !tx.is_closed() {
+                let chunk = users.by_ref().take(BATCH_CHUNK_SIZE).collect::<Vec<User>>();
+                if chunk.is_empty() {
+                    break;
+                }
+                let Ok(permit) = Arc::clone(&workers).acquire_owned().await else {
+                    break;
+                };
+                let config = Arc::clone(&config);
+                let setting_key = setting_key.clone();
+                let prepared = Arc::clone(&prepared);
+                let failure_logged = Arc::clone(&failure_logged);
+                let tx = tx.clone();
+                tokio::task::spawn_blocking(move || {
+                    let _permit = permit;
+                    for user in chunk {
+                        let result = eval_batch_user(
+                            &config.settings,
+                            setting_key.as_deref().map(String::as_str),
+                            &user,
+                            &options,
+                            &prepared,
+                            &failure_logged,
+                        );
+                        if tx.blocking_send(result).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        rx
+    }
+
+    /// Returns the config the [`Client`] currently evaluates feature flags and settings on.
+    ///
+    /// Combined with [`Config::patched`] and [`crate::shadow_diff`], the snapshot can be used
+    /// to compare the current config with a proposed one before rolling it out.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let config = client.config_snapshot().await;
+    ///     let keys = config.settings.keys().collect::<Vec<_>>();
+    /// }
+    /// ```
+    pub async fn config_snapshot(&self) -> Arc<Config> {
+        let result = self.service.config().await;
+        Arc::clone(result.config())
+    }
+
+    /// Returns the [`Setting`] of the feature flag or setting identified by the given `key` in the current config,
+    /// with its targeting rules, percentage options and variation IDs, or `None` when the key is not found.
+    ///
+    /// The key is looked up like in the evaluations, so the key prefix and the [`crate::KeyLookupMode`] of the
+    /// client apply. The returned setting is a copy, it's not affected by later config JSON downloads.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     if let Some(setting) = client.get_setting("flag-key").await {
+    ///         println!("{} targeting rules", setting.targeting_rules.as_ref().map_or(0, Vec::len));
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_setting(&self, key: &str) -> Option<Arc<Setting>> {
+        let result = self.service.config().await;
+        let key = self.prefixed_key(key);
+        find_setting(
+            &result.config().settings,
+            &key,
+            self.options.key_lookup_mode(),
+            None,
+        )
+        .ok()
+        .map(|(_, setting)| Arc::new(setting.clone()))
+    }
+
+    /// Returns the settings of the current config, without copying them.
+    ///
+    /// The settings are keyed by their keys in the config JSON (including the key prefix of the client).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let settings = client.settings_snapshot().await;
+    ///     for (key, setting) in settings.iter() {
+    ///         println!("{key}: {:?}", setting.setting_type);
+    ///     }
+    /// }
+    /// ```
+    pub async fn settings_snapshot(&self) -> SettingsSnapshot {
+        SettingsSnapshot::new(self.config_snapshot().await)
+    }
+
+    /// Exports the config JSON currently held by the [`Client`] with its ETag and fetch time, so it can be
+    /// preloaded into another client with [`ClientBuilder::preload_cache_entry`].
+    ///
+    /// Returns `None` when the client has no config JSON yet, or uses [`crate::OverrideBehavior::LocalOnly`] overrides.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     if let Some(entry) = client.export_cache_entry().await {
+    ///         let exported = serde_json::to_string(&entry).unwrap();
+    ///         // hand `exported` over to the next deployment
+    ///     }
+    /// }
+    /// ```
+    pub async fn export_cache_entry(&self) -> Option<CacheEntry> {
+        self.service.cache_entry().await
+    }
+
+    /// Begins an [`EvaluationSession`] that pins the current config JSON for its lifetime,
+    /// so the evaluations made through it are consistent even when the config JSON gets refreshed meanwhile.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let session = client.begin_session().await;
+    ///     let value = session.get_value("flag-key", false, Some(User::new("user-id")));
+    /// }
+    /// ```
+    pub async fn begin_session(&self) -> EvaluationSession<'_> {
+        let result = self.service.config().await;
+        EvaluationSession::new(self, result)
+    }
+
+    /// Puts the [`Client`] into offline mode.
+    ///
+    /// In this mode the SDK is not allowed to initiate HTTP request and works only from the configured cache.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     client.offline();
+    /// }
+    /// ```
+    pub fn offline(&self) {
+        self.service.set_mode(true);
+    }
+
+    /// Puts the [`Client`] into online mode.
+    ///
+    /// In this mode the SDK initiates HTTP requests to fetch the latest config JSON data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     client.online();
+    /// }
+    /// ```
+    pub fn online(&self) {
+        self.service.set_mode(false);
+    }
+
+    /// Returns `true` when the SDK is configured not to initiate HTTP requests, otherwise `false`.
+    ///
+    /// The SDK is also considered offline while the `CONFIGCAT_SDK_OFFLINE` environment variable is set to `1` or `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::builder("sdk-key")
+    ///         .offline(true)
+    ///         .build()
+    ///         .unwrap();
+    ///
+    ///     let offline = client.is_offline();
+    /// }
+    /// ```
+    pub fn is_offline(&self) -> bool {
+        self.service.is_offline()
+    }
+
+    /// Stops the background tasks of the client (the polling, the async flag override refresh and the usage
+    /// reports) and completes when they have terminated, including the config JSON download in progress (if any).
+    ///
+    /// Dropping the client stops the background tasks too, but doesn't wait for them. After the shutdown,
+    /// the client keeps serving the config JSON it has, but it no longer polls.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     client.shutdown().await;
+    ///     // no SDK tasks are running from now on
+    /// }
+    /// ```
+    pub async fn shutdown(&self) {
+        self.service.shutdown().await;
+    }
+
+    /// Changes the poll interval of [`PollingMode::AutoPoll`] without rebuilding the [`Client`].
+    ///
+    /// The running polling loop restarts with the new interval, and it checks the age of the cached
+    /// config JSON right away, so a shorter interval takes effect immediately. Has no effect
+    /// (and logs a warning) in other polling modes.
+    ///
+    /// # Errors
+    ///
+    /// This method fails with [`ErrorKind::InvalidConfiguration`] when the given interval is zero,
+    /// the running polling loop keeps its interval.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     // poll more often during a deployment
+    ///     client.set_poll_interval(Duration::from_secs(30)).unwrap();
+    /// }
+    /// ```
+    pub fn set_poll_interval(&self, interval: Duration) -> Result<(), ClientError> {
+        if interval.is_zero() {
+            return Err(ClientError::new(
+                ErrorKind::InvalidConfiguration,
+                "The poll interval must be greater than zero.".to_owned(),
+            ));
+        }
+        if !self.service.set_poll_interval(interval) {
+            warn!(
+                "The poll interval can only be changed in AutoPoll mode, the call has no effect."
+            );
+        }
+        Ok(())
+    }
+
+    /// Enables or disables the bypass of the [`FreezeSchedule`](crate::FreezeSchedule) set on the builder.
+    ///
+    /// While bypassed, new config JSON versions are applied even during freeze windows, including the one
+    /// already downloaded and deferred in the current window (on the next evaluation or refresh).
+    /// Meant for emergency rollouts.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     client.bypass_freeze(true);
+    ///     _ = client.refresh().await;
+    ///     client.bypass_freeze(false);
+    /// }
+    /// ```
+    pub fn bypass_freeze(&self, bypass: bool) {
+        self.service.set_freeze_bypass(bypass);
+    }
+
+    /// Pins the config to the given ETag, adding it to the allowed ones when the config is already pinned.
+    ///
+    /// While pinned, the SDK keeps polling, but a downloaded (or cached) config JSON whose ETag isn't allowed
+    /// doesn't replace the current one, and each withheld download is reported as
+    /// [`ClientEvent::UpdateWithheld`] to the receivers of [`Client::events`].
+    /// A client without any config JSON applies the first one regardless of the pinned ETags.
+    ///
+    /// The ETag of the current config JSON is available through [`Client::export_cache_entry`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     if let Some(entry) = client.export_cache_entry().await {
+    ///         client.pin_config(&entry.etag);
+    ///     }
+    ///     // after the incident
+    ///     client.unpin_config();
+    /// }
+    /// ```
+    pub fn pin_config(&self, etag: &str) {
+        self.service.pin_config(etag);
+    }
+
+    /// Removes the ETags set with [`Client::pin_config`], the latest config JSON is applied on the next
+    /// refresh or poll.
+    pub fn unpin_config(&self) {
+        self.service.unpin_config();
+    }
+
+    /// Sets the default user.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut client = Client::new("sdk-key").unwrap();
+    ///
+    ///     client.set_default_user(User::new("user-id"));
+    /// }
+    /// ```
+    pub fn set_default_user(&mut self, user: User) {
+        self.set_def_user(Some(user));
+    }
+
+    /// Clears the default user.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut client = Client::builder("sdk-key")
+    ///         .default_user(User::new("user-id"))
+    ///         .build()
+    ///         .unwrap();
+    ///
+    ///     client.clear_default_user();
+    /// }
+    /// ```
+    pub fn clear_default_user(&mut self) {
+        self.set_def_user(None);
+    }
+
+    /// Asynchronously waits for the initialization of the [`Client`] for a maximum duration specified in `wait_timeout`.
+    ///
+    /// Returns a [`ReadyReport`] with the [`ClientCacheState`], the fetch time and the source of the config JSON,
+    /// and the time the initialization took (e.g. for recording startup SLOs).
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the initialization takes more time than the specified `wait_timeout`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, ClientCacheState, ConfigSource};
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///     let report = client.wait_for_ready(Duration::from_secs(5)).await.unwrap();
+    ///
+    ///     assert!(matches!(report.state, ClientCacheState::HasUpToDateFlagData));
+    ///     assert_eq!(report.source, ConfigSource::Http);
+    ///     println!("initialized in {}ms", report.init_duration.as_millis());
+    /// }
+    /// ```
+    pub async fn wait_for_ready(&self, wait_timeout: Duration) -> Result<ReadyReport, ClientError> {
+        let init = timeout(wait_timeout, self.service.wait_for_init()).await;
+        if let Ok(report) = init {
+            Ok(report)
+        } else {
+            let err = ClientError::new(
+                ErrorKind::ClientInitTimedOut,
+                format!(
+                    "Client initialization timed out after {}s.",
+                    wait_timeout.as_secs()
+                ),
+            );
+            warn!(event_id = err.kind.as_u8(); "{err}");
+            Err(err)
+        }
+    }
+
+    /// The same as [`Client::wait_for_ready`] but returns only the [`ClientCacheState`].
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the initialization takes more time than the specified `wait_timeout`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, ClientCacheState};
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///     let state = client.wait_for_ready_state(Duration::from_secs(5)).await.unwrap();
+    ///
+    ///     assert!(matches!(state, ClientCacheState::HasUpToDateFlagData));
+    /// }
+    /// ```
+    pub async fn wait_for_ready_state(
+        &self,
+        wait_timeout: Duration,
+    ) -> Result<ClientCacheState, ClientError> {
+        self.wait_for_ready(wait_timeout)
+            .await
+            .map(|report| report.state)
+    }
+
+    /// Returns a [`watch::Receiver`] that observes the number of completed [`PollingMode::AutoPoll`] iterations.
+    ///
+    /// The counter is incremented after each scheduled fetch attempt (regardless of its outcome), so tests and
+    /// diagnostics can wait for poll iterations instead of sleeping. It stays `0` in other polling modes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///     let mut ticks = client.poll_tick_watch();
+    ///
+    ///     // waits for the next poll iteration
+    ///     _ = ticks.changed().await;
+    ///     println!("poll iterations: {}", *ticks.borrow());
+    /// }
+    /// ```
+    pub fn poll_tick_watch(&self) -> watch::Receiver<u64> {
+        self.service.watch_poll_ticks()
+    }
+
+    /// Returns a [`watch::Receiver`] that observes the current [`ClientCacheState`] of the [`Client`].
+    ///
+    /// The state is updated each time the SDK reads its cache or fetches the config JSON,
+    /// so it reflects when the client degrades to serving cached feature flag data only.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, ClientCacheState};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///     let mut state = client.state_watch();
+    ///
+    ///     while state.changed().await.is_ok() {
+    ///         if *state.borrow() == ClientCacheState::HasCachedFlagDataOnly {
+    ///             println!("serving cached feature flag data");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn state_watch(&self) -> watch::Receiver<ClientCacheState> {
+        self.service.watch_cache_state()
+    }
+
+    /// Returns a receiver of the [`ClientEvent`]s reported from now on: the switches between online and offline
+    /// mode, the results of the refreshes initiated by [`Client::refresh`] or [`Client::refresh_if_older`],
+    /// the state changes of the circuit breaker set with [`crate::ClientBuilder::circuit_breaker`],
+    /// and the downloads withheld by [`Client::pin_config`].
+    ///
+    /// Each receiver buffers a limited number of events, a receiver that falls behind misses the oldest ones
+    /// (see [`broadcast::Receiver::recv`]).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, ClientEvent};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///     let mut events = client.events();
+    ///
+    ///     while let Ok(event) = events.recv().await {
+    ///         if let ClientEvent::RefreshCompleted(Ok(_)) = event {
+    ///             println!("feature flags refreshed, resuming the dependent jobs");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn events(&self) -> broadcast::Receiver<ClientEvent> {
+        self.service.subscribe_events()
+    }
 
-    /// Clears the default user.
+    /// Returns the [`FetchCounters`] of the [`Client`]: the number of downloads that returned a new config JSON,
+    /// were answered with not modified or failed, and the local revision of the config JSON.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use configcat::{Client, User};
+    /// use configcat::Client;
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let mut client = Client::builder("sdk-key")
-    ///         .default_user(User::new("user-id"))
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     _ = client.refresh().await;
+    ///     let counters = client.fetch_counters();
+    ///     println!("config revision: {}", counters.revision);
+    /// }
+    /// ```
+    pub fn fetch_counters(&self) -> FetchCounters {
+        self.service.fetch_counters()
+    }
+
+    /// Returns whether the `.data_governance()` parameter of the [`Client`] is in sync with the preferences
+    /// on the ConfigCat Dashboard, as detected from the downloaded config JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, DataGovernanceStatus};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     if let DataGovernanceStatus::Mismatch { url } = client.data_governance_status() {
+    ///         println!("data governance mismatch, redirected to {url}");
+    ///     }
+    /// }
+    /// ```
+    pub fn data_governance_status(&self) -> DataGovernanceStatus {
+        self.service.data_governance_status()
+    }
+
+    /// Returns how the flag overrides were merged into the config JSON in use, or `None` when there are
+    /// no flag overrides to merge (no overrides, [`crate::OverrideBehavior::LocalOnly`], or no config JSON yet).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, FileDataSource, OverrideBehavior};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::builder("sdk-key")
+    ///         .overrides(
+    ///             Box::new(FileDataSource::new("path/to/file.json").unwrap()),
+    ///             OverrideBehavior::LocalOverRemote,
+    ///         )
     ///         .build()
     ///         .unwrap();
     ///
-    ///     client.clear_default_user();
+    ///     _ = client.refresh().await;
+    ///     if let Some(report) = client.override_report() {
+    ///         println!("overridden settings: {:?}", report.overridden);
+    ///     }
     /// }
     /// ```
-    pub fn clear_default_user(&mut self) {
-        self.set_def_user(None);
+    pub fn override_report(&self) -> Option<Arc<OverrideReport>> {
+        self.service.override_report()
     }
 
-    /// Asynchronously waits for the initialization of the [`Client`] for a maximum duration specified in `wait_timeout`.
-    ///
-    /// # Errors
+    /// Returns the [`UsageReport`] of the current period, listing the feature flags and settings of the config JSON
+    /// that weren't evaluated since the [`Client`] was created or since the last [`Client::flush_usage_report`] call.
     ///
-    /// This method fails if the initialization takes more time than the specified `wait_timeout`.
+    /// Returns `None` when the usage tracking is off (see [`ClientBuilder::track_usage`]).
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use configcat::{Client, ClientCacheState};
-    /// use std::time::Duration;
+    /// use configcat::Client;
     ///
     /// #[tokio::main]
     /// async fn main() {
+    ///     let client = Client::builder("sdk-key").track_usage(true).build().unwrap();
+    ///
+    ///     _ = client.get_value("flag-key", false, None).await;
+    ///     if let Some(report) = client.usage_report() {
+    ///         println!("never evaluated: {:?}", report.unused);
+    ///     }
+    /// }
+    /// ```
+    pub fn usage_report(&self) -> Option<UsageReport> {
+        self.service.usage_report(false)
+    }
+
+    /// The same as [`Client::usage_report`] but also starts a new period.
+    pub fn flush_usage_report(&self) -> Option<UsageReport> {
+        self.service.usage_report(true)
+    }
+
+    /// Assembles a [`DiagnosticsReport`] about the configuration and state of the [`Client`],
+    /// which can be attached to support tickets.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
     ///
+    /// #[tokio::main]
+    /// async fn main() {
     ///     let client = Client::new("sdk-key").unwrap();
-    ///     let state = client.wait_for_ready(Duration::from_secs(5)).await.unwrap();
     ///
-    ///     assert!(matches!(state, ClientCacheState::HasUpToDateFlagData));
+    ///     let report = client.diagnostics_report().await;
+    ///     println!("{}", report.to_json());
     /// }
     /// ```
-    pub async fn wait_for_ready(
+    pub async fn diagnostics_report(&self) -> DiagnosticsReport {
+        let result = self.service.config().await;
+        let settings = &result.config().settings;
+        let example_key = settings.keys().min();
+        let last_fetch = result.last_fetch();
+        DiagnosticsReport {
+            sdk_version: PKG_VERSION.to_owned(),
+            sdk_key: mask_sdk_key(self.options.sdk_key()),
+            polling_mode: format!("{:?}", self.options.polling_mode()),
+            base_url: self.options.base_url().map(|url| redact_url(url)),
+            offline: self.is_offline(),
+            cache_state: format!("{:?}", *self.state_watch().borrow()),
+            data_governance_status: format!("{:?}", self.data_governance_status()),
+            setting_count: settings.len(),
+            fetch_time: (*result.fetch_time() != DateTime::<Utc>::MIN_UTC)
+                .then(|| result.fetch_time().to_rfc3339()),
+            config_age_ms: result.config_age().map(|age| age.as_millis()),
+            last_fetch_status: last_fetch.and_then(|stats| stats.status),
+            last_fetch_latency_ms: last_fetch.map(|stats| stats.latency.as_millis()),
+            fetch_counters: self.fetch_counters(),
+            required_sdk_version: result
+                .config()
+                .required_sdk_upgrade()
+                .map(ToString::to_string),
+            example_eval_log: example_key.map(|key| eval_log(&settings[key], key, None, settings)),
+        }
+    }
+
+    /// Evaluates the setting without cloning the user into the returned [`EvaluationDetails`].
+    pub(crate) fn eval_value_details<T: ValuePrimitive + Clone + Default>(
         &self,
-        wait_timeout: Duration,
-    ) -> Result<ClientCacheState, ClientError> {
-        let init = timeout(wait_timeout, self.service.wait_for_init()).await;
-        if let Ok(state) = init {
-            Ok(state)
+        result: &ConfigResult,
+        key: &str,
+        default: T,
+        user: Option<&User>,
+    ) -> EvaluationDetails<T> {
+        self.eval_value_details_with(result, key, default, user, &EvalOptions::default())
+    }
+
+    /// Evaluates like [`Client::eval_value_details`] and puts the evaluated user into the details,
+    /// which is the default user when `user` is `None`.
+    pub(crate) fn eval_value_details_with_user<T: ValuePrimitive + Clone + Default>(
+        &self,
+        result: &ConfigResult,
+        key: &str,
+        default: T,
+        user: Option<User>,
+        options: &EvalOptions,
+    ) -> EvaluationDetails<T> {
+        let def_user = if user.is_none() {
+            self.def_user(key)
+        } else {
+            None
+        };
+        let mut details = self.eval_value_details_with(
+            result,
+            key,
+            default,
+            user.as_ref().or(def_user.as_deref()),
+            options,
+        );
+        details.user = user.or_else(|| def_user.map(|user| (*user).clone()));
+        details
+    }
+
+    fn eval_value_details_with<T: ValuePrimitive + Clone + Default>(
+        &self,
+        result: &ConfigResult,
+        key: &str,
+        default: T,
+        user: Option<&User>,
+        options: &EvalOptions,
+    ) -> EvaluationDetails<T> {
+        self.service.record_usage(key);
+        if let Some(behavior) = self.stale_behavior(result) {
+            return self.with_provided_default(stale_details(result, key, default, None, behavior));
+        }
+        let def_user = if user.is_none() {
+            self.def_user(key)
+        } else {
+            None
+        };
+        let setting_key = self.prefixed_key(key);
+        match eval_flag(
+            &result.config().settings,
+            &setting_key,
+            user.or(def_user.as_deref()),
+            default.clone().try_into().ok().as_ref(),
+            self.options.key_lookup_mode(),
+            &self.options.eval_options(options),
+        ) {
+            Ok(eval_result) => {
+                if let Some(val) = T::from_value(&eval_result.value) {
+                    self.report_exposure(
+                        key,
+                        &eval_result.value,
+                        eval_result.variation_id.as_ref(),
+                        user.or(def_user.as_deref()),
+                    );
+                    EvaluationDetails {
+                        value: val,
+                        key: key.to_owned(),
+                        fetch_time: Some(*result.fetch_time()),
+                        config_age: result.config_age(),
+                        last_fetch: result.last_fetch(),
+                        config: self.details_config(result),
+                        ..eval_result.into()
+                    }
+                } else {
+                    let err = ClientError::new(ErrorKind::SettingValueTypeMismatch, format!("The type of a setting must match the requested type. Setting's type was '{}' but the requested type was '{}'. Learn more: https://configcat.com/docs/sdk-reference/rust/#setting-type-mapping", eval_result.setting_type, type_name::<T>()));
+                    error!(event_id = err.kind.as_u8(); "{err}");
+                    self.with_provided_default(EvaluationDetails::from_err(default, key, None, err))
+                }
+            }
+            Err(err) => {
+                let err = self.check_not_ready(err, key);
+                self.log_eval_error(&err, &setting_key, &result.config().settings);
+                self.with_provided_default(EvaluationDetails::from_err(default, key, None, err))
+            }
+        }
+    }
+
+    /// Replaces the default value of a failed evaluation with the one of the [`crate::DefaultValueProvider`]
+    /// set with [`ClientBuilder::default_value_provider`], when it provides one of the requested type.
+    fn with_provided_default<T: ValuePrimitive>(
+        &self,
+        mut details: EvaluationDetails<T>,
+    ) -> EvaluationDetails<T> {
+        if let Some(value) = self
+            .provided_default(&details.key)
+            .and_then(|value| T::from_value(&value))
+        {
+            details.value = value;
+            details.value_source = ValueSource::DefaultValueProvider;
+        }
+        details
+    }
+
+    fn with_provided_flag_default(
+        &self,
+        mut details: EvaluationDetails<Option<Value>>,
+    ) -> EvaluationDetails<Option<Value>> {
+        if let Some(value) = self.provided_default(&details.key) {
+            details.value = Some(value);
+            details.value_source = ValueSource::DefaultValueProvider;
+        }
+        details
+    }
+
+    fn provided_default(&self, key: &str) -> Option<Value> {
+        self.options
+            .default_value_provider()
+            .and_then(|provider| provider.default_value(key))
+    }
+
+    fn report_exposure(
+        &self,
+        key: &str,
+        value: &Value,
+        variation_id: Option<&String>,
+        user: Option<&User>,
+    ) {
+        let Some(hook) = self.options.flag_evaluated_hook() else {
+            return;
+        };
+        if let Some(exposures) = self.exposures.as_ref() {
+            let variation = variation_id
+                .filter(|id| !id.is_empty())
+                .map_or_else(|| value.to_string(), Clone::clone);
+            if !exposures.first_exposure(user_id(user), key, variation) {
+                return;
+            }
+        }
+        hook(&ExposureEvent {
+            key: key.to_owned(),
+            value: value.clone(),
+            variation_id: variation_id.cloned(),
+            user: user.cloned(),
+        });
+    }
+
+    fn eval_variation_id(
+        &self,
+        result: &ConfigResult,
+        key: &str,
+        setting_key: &str,
+        lookup_mode: KeyLookupMode,
+        user: Option<&User>,
+    ) -> Option<String> {
+        if self.stale_behavior(result).is_some() {
+            return None;
+        }
+        let def_user = if user.is_none() {
+            self.def_user(key)
         } else {
+            None
+        };
+        match eval_flag(
+            &result.config().settings,
+            setting_key,
+            user.or(def_user.as_deref()),
+            None,
+            lookup_mode,
+            &self.options.eval_options(&EvalOptions::default()),
+        ) {
+            Ok(eval_result) => eval_result.variation_id,
+            Err(err) => {
+                let err = self.check_not_ready(err, key);
+                self.log_eval_error(&err, setting_key, &result.config().settings);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn all_keys(&self, config_result: &ConfigResult) -> Vec<String> {
+        let settings = &config_result.config().settings;
+        if !settings.is_empty() {
+            return settings
+                .keys()
+                .filter_map(|k| self.unprefixed_key(k))
+                .map(ToOwned::to_owned)
+                .collect();
+        }
+        error!(event_id = 1000; "Config JSON is not present. Returning empty vector.");
+        vec![]
+    }
+
+    /// Returns the key of the setting in the config JSON that belongs to the given `key` used by the application.
+    fn prefixed_key<'k>(&self, key: &'k str) -> Cow<'k, str> {
+        match self.options.key_prefix() {
+            Some(prefix) => Cow::Owned(format!("{prefix}{key}")),
+            None => Cow::Borrowed(key),
+        }
+    }
+
+    /// Returns the key used by the application for the given setting key, or `None` when the
+    /// setting doesn't belong to the configured key prefix.
+    fn unprefixed_key<'k>(&self, key: &'k str) -> Option<&'k str> {
+        match self.options.key_prefix() {
+            Some(prefix) => key.strip_prefix(prefix),
+            None => Some(key),
+        }
+    }
+
+    fn check_can_refresh(&self) -> Result<(), ClientError> {
+        if self.options.offline() || is_offline_by_env() {
             let err = ClientError::new(
-                ErrorKind::ClientInitTimedOut,
-                format!(
-                    "Client initialization timed out after {}s.",
-                    wait_timeout.as_secs()
-                ),
+                ErrorKind::OfflineClient,
+                "Client is in offline mode, it cannot initiate HTTP calls.".to_owned(),
             );
-            warn!(event_id = err.kind.as_u8(); "{}", err);
-            Err(err)
+            warn!(event_id = err.kind.as_u8(); "{err}");
+            return Err(err);
+        }
+        if self.options.overrides().is_local() {
+            let err = ClientError::new(
+                ErrorKind::LocalOnlyClient,
+                "Client has local-only overrides, it cannot initiate HTTP calls.".to_owned(),
+            );
+            warn!(event_id = err.kind.as_u8(); "{err}");
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn details_config(&self, result: &ConfigResult) -> Option<Arc<Config>> {
+        self.options
+            .include_config_in_details()
+            .then(|| Arc::clone(result.config()))
+    }
+
+    /// Returns the configured [`StaleBehavior`] when the config JSON is older than the configured maximum age.
+    fn stale_behavior(&self, result: &ConfigResult) -> Option<StaleBehavior> {
+        let (max_age, behavior) = self.options.max_config_age()?;
+        if self.options.overrides().is_local() || result.config().settings.is_empty() {
+            return None;
+        }
+        result
+            .config_age()
+            .is_some_and(|age| age > max_age)
+            .then_some(behavior)
+    }
+
+    fn check_not_ready(&self, err: ClientError, key: &str) -> ClientError {
+        if err.kind == ErrorKind::ConfigJsonNotAvailable
+            && matches!(self.options.polling_mode(), PollingMode::Manual)
+            && self.options.not_ready_behavior() == NotReadyBehavior::Error
+        {
+            return ClientError::new(ErrorKind::NotReady, format!("Config JSON is not available yet when evaluating setting '{key}'. Call `refresh()` before evaluating feature flags in manual polling mode."));
+        }
+        err
+    }
+
+    fn log_eval_error(&self, err: &ClientError, key: &str, settings: &HashMap<String, Setting>) {
+        if err.kind != ErrorKind::SettingKeyMissing {
+            error!(event_id = err.kind.as_u8(); "{err}");
+            return;
+        }
+        if !log_enabled!(Level::Error) || !self.allow_missing_key_log(key) {
+            return;
+        }
+        let keys = settings
+            .keys()
+            .map(|k| format!("'{k}'"))
+            .collect::<Vec<String>>()
+            .join(", ");
+        error!(event_id = err.kind.as_u8(); "{err} Available keys: [{keys}].");
+    }
+
+    fn allow_missing_key_log(&self, key: &str) -> bool {
+        let mut limiter = self.missing_key_logs.lock().unwrap();
+        limiter.allow(key)
+    }
+
+    fn parse_json<T: DeserializeOwned + Clone + Send + Sync + 'static>(
+        &self,
+        etag: &str,
+        json: &str,
+    ) -> Result<T, serde_json::Error> {
+        if let Some(value) = self.json_cache.read().unwrap().get::<T>(etag, json) {
+            return Ok(value);
         }
+        // parsed without holding the lock, so concurrent readers of the cached values aren't blocked
+        let parsed = serde_json::from_str::<T>(json)?;
+        let mut cache = self.json_cache.write().unwrap();
+        cache.insert(etag, json, parsed.clone());
+        Ok(parsed)
     }
 
-    fn read_def_user(&self) -> Option<User> {
+    fn first_unknown_variant(&self, etag: &str, key: &str, value: &str) -> bool {
+        let mut unknown = self.unknown_variants.lock().unwrap();
+        unknown.first(etag, key, value)
+    }
+
+    /// Returns the default user of the feature flag or setting identified by `key`.
+    fn def_user(&self, key: &str) -> Option<Arc<User>> {
+        if let Some(user) = self
+            .options
+            .default_user_resolver()
+            .and_then(|resolver| resolver(key))
+        {
+            return Some(Arc::new(user));
+        }
+        if let Some(user) = self.options.prefixed_default_user(key) {
+            return Some(Arc::clone(user));
+        }
+        if let Some(user) = self
+            .options
+            .default_user_provider()
+            .and_then(|provider| provider())
+        {
+            return Some(Arc::new(user));
+        }
         let user = self.default_user.lock().unwrap();
         user.clone()
     }
 
+    fn read_def_user(&self, key: &str) -> Option<User> {
+        self.def_user(key).map(|user| (*user).clone())
+    }
+
     fn set_def_user(&self, user: Option<User>) {
         let mut def_user = self.default_user.lock().unwrap();
-        *def_user = user;
+        *def_user = user.map(Arc::new);
+    }
+}
+
+pub(crate) fn values_of(details: Vec<EvaluationDetails<Option<Value>>>) -> HashMap<String, Value> {
+    let mut result = HashMap::<String, Value>::with_capacity(details.len());
+    for detail in details {
+        if let Some(val) = detail.value {
+            result.insert(detail.key, val);
+        }
     }
+    result
 }
 
-fn eval_flag(
+pub(crate) fn eval_flag(
     settings: &HashMap<String, Setting>,
     key: &str,
     user: Option<&User>,
     default: Option<&Value>,
+    lookup_mode: KeyLookupMode,
+    options: &EvalOptions,
 ) -> Result<EvalResult, ClientError> {
     if settings.is_empty() {
         return Err(ClientError::new(ErrorKind::ConfigJsonNotAvailable, format!("Config JSON is not present when evaluating setting '{key}'. Returning the `defaultValue` parameter that you specified in your application: '{}'.", default.to_str())));
     }
-    match settings.get(key) {
-        None => {
-            let keys = settings
-                .keys()
-                .map(|k| format!("'{k}'"))
-                .collect::<Vec<String>>()
-                .join(", ");
-            Err(ClientError::new(ErrorKind::SettingKeyMissing, format!("Failed to evaluate setting '{key}' (the key was not found in config JSON). Returning the `defaultValue` parameter that you specified in your application: '{}'. Available keys: [{keys}].", default.to_str())))
-        }
-        Some(setting) => {
-            let eval_result = eval(setting, key, user, settings, default);
-            match eval_result {
-                Ok(result) => Ok(result),
-                Err(err) => Err(ClientError::new(
+    let (setting_key, setting) = find_setting(settings, key, lookup_mode, default)?;
+    match eval(setting, setting_key, user, settings, default, options) {
+        Ok(result) => Ok(result),
+        Err(err) => Err(ClientError::new(
+            ErrorKind::EvaluationFailure,
+            format!("Failed to evaluate setting '{key}' ({err})"),
+        )),
+    }
+}
+
+fn eval_batch_user(
+    settings: &HashMap<String, Setting>,
+    setting_key: Option<&str>,
+    user: &User,
+    options: &EvalOptions,
+    prepared: &Prepared,
+    failure_logged: &AtomicBool,
+) -> BatchEvaluation {
+    let mut result = BatchEvaluation {
+        identifier: user
+            .get(User::IDENTIFIER)
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        value: None,
+        variation_id: None,
+    };
+    let Some(setting_key) = setting_key else {
+        return result;
+    };
+    match eval_prepared(
+        &settings[setting_key],
+        setting_key,
+        user,
+        settings,
+        options,
+        prepared,
+    ) {
+        Ok(eval_result) => {
+            result.value = Some(eval_result.value);
+            result.variation_id = eval_result.variation_id;
+        }
+        Err(err) => {
+            // one log entry is enough to signal that the batch has failed evaluations
+            if !failure_logged.swap(true, Ordering::SeqCst) {
+                let err = ClientError::new(
                     ErrorKind::EvaluationFailure,
-                    format!("Failed to evaluate setting '{key}' ({err})"),
-                )),
+                    format!("Failed to evaluate setting '{setting_key}' ({err})"),
+                );
+                error!(event_id = err.kind.as_u8(); "{err}");
+            }
+        }
+    }
+    result
+}
+
+/// Builds the result of an evaluation refused because the config JSON is older than the configured maximum age.
+fn stale_details<T>(
+    result: &ConfigResult,
+    key: &str,
+    default: T,
+    user: Option<User>,
+    behavior: StaleBehavior,
+) -> EvaluationDetails<T> {
+    let error = log_stale(
+        result,
+        key,
+        behavior,
+        "Returning the `defaultValue` parameter that you specified in your application.",
+    );
+    EvaluationDetails::<()> {
+        key: key.to_owned(),
+        is_default_value: true,
+        value_source: ValueSource::DefaultParameter,
+        user,
+        error,
+        fetch_time: Some(*result.fetch_time()),
+        config_age: result.config_age(),
+        last_fetch: result.last_fetch(),
+        ..EvaluationDetails::default()
+    }
+    .with_value(default)
+}
+
+/// Logs that the config JSON is older than the configured maximum age, and returns the error to report
+/// when the [`StaleBehavior`] requires one.
+fn log_stale(
+    result: &ConfigResult,
+    key: &str,
+    behavior: StaleBehavior,
+    fallback: &str,
+) -> Option<ClientError> {
+    let err = ClientError::new(ErrorKind::ConfigJsonTooOld, format!("The config JSON is older than the maximum age set with `max_config_age()` when evaluating setting '{key}' (fetched at {}). {fallback}", result.fetch_time()));
+    match behavior {
+        StaleBehavior::ServeDefaults => {
+            warn!(event_id = err.kind.as_u8(); "{err}");
+            None
+        }
+        StaleBehavior::Error => {
+            error!(event_id = err.kind.as_u8(); "{err}");
+            Some(err)
+        }
+    }
+}
+
+fn find_setting<'a>(
+    settings: &'a HashMap<String, Setting>,
+    key: &str,
+    lookup_mode: KeyLookupMode,
+    default: Option<&Value>,
+) -> Result<(&'a str, &'a Setting), ClientError> {
+    if let Some((k, setting)) = settings.get_key_value(key) {
+        return Ok((k.as_str(), setting));
+    }
+    if lookup_mode == KeyLookupMode::CaseInsensitive {
+        let mut candidates = settings
+            .iter()
+            .filter(|(k, _)| k.to_lowercase() == key.to_lowercase())
+            .collect::<Vec<_>>();
+        if candidates.len() == 1 {
+            let (k, setting) = candidates.remove(0);
+            return Ok((k.as_str(), setting));
+        }
+        if candidates.len() > 1 {
+            let mut keys = candidates
+                .iter()
+                .map(|(k, _)| format!("'{k}'"))
+                .collect::<Vec<String>>();
+            keys.sort();
+            let keys = keys.join(", ");
+            return Err(ClientError::new(ErrorKind::SettingKeyMissing, format!("Failed to evaluate setting '{key}' (the key matches more than one key in config JSON when ignoring case: [{keys}]). Returning the `defaultValue` parameter that you specified in your application: '{}'.", default.to_str())));
+        }
+    }
+    Err(ClientError::new(ErrorKind::SettingKeyMissing, format!("Failed to evaluate setting '{key}' (the key was not found in config JSON). Returning the `defaultValue` parameter that you specified in your application: '{}'.", default.to_str())))
+}
+
+/// Limits how often the missing key error is logged for the same key.
+#[derive(Default)]
+struct KeyLogLimiter {
+    last_logged: HashMap<String, Instant>,
+}
+
+impl KeyLogLimiter {
+    fn allow(&mut self, key: &str) -> bool {
+        let now = Instant::now();
+        if let Some(last) = self.last_logged.get(key) {
+            if now.duration_since(*last) < MISSING_KEY_LOG_INTERVAL {
+                return false;
+            }
+        }
+        if self.last_logged.len() >= MISSING_KEY_LOG_CAPACITY {
+            self.last_logged
+                .retain(|_, last| now.duration_since(*last) < MISSING_KEY_LOG_INTERVAL);
+        }
+        if self.last_logged.len() >= MISSING_KEY_LOG_CAPACITY {
+            // all tracked keys were logged recently, the least recently logged one makes room
+            if let Some(oldest) = self
+                .last_logged
+                .iter()
+                .min_by_key(|(_, last)| **last)
+                .map(|(oldest, _)| oldest.clone())
+            {
+                self.last_logged.remove(&oldest);
             }
         }
+        self.last_logged.insert(key.to_owned(), now);
+        true
+    }
+}
+
+// the parsed values of the JSON text settings, reset when the config JSON changes
+#[derive(Default)]
+struct JsonValueCache {
+    etag: String,
+    values: HashMap<TypeId, HashMap<String, Box<dyn Any + Send + Sync>>>,
+}
+
+impl JsonValueCache {
+    fn get<T: Clone + 'static>(&self, etag: &str, json: &str) -> Option<T> {
+        if self.etag != etag {
+            return None;
+        }
+        self.values
+            .get(&TypeId::of::<T>())?
+            .get(json)?
+            .downcast_ref::<T>()
+            .cloned()
+    }
+
+    fn insert<T: Send + Sync + 'static>(&mut self, etag: &str, json: &str, value: T) {
+        if self.etag != etag {
+            self.values.clear();
+            etag.clone_into(&mut self.etag);
+        }
+        self.values
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .insert(json.to_owned(), Box::new(value));
+    }
+}
+
+// the unknown enum variants already logged, reset when the config JSON changes
+// as they can only come from the setting values of the current one
+#[derive(Default)]
+struct UnknownVariantLog {
+    etag: String,
+    logged: HashSet<(String, String)>,
+}
+
+impl UnknownVariantLog {
+    fn first(&mut self, etag: &str, key: &str, value: &str) -> bool {
+        if self.etag != etag {
+            self.logged.clear();
+            etag.clone_into(&mut self.etag);
+        }
+        self.logged.insert((key.to_owned(), value.to_owned()))
     }
 }
 
@@ -556,3 +2313,52 @@ impl Debug for Client {
             .finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod client_tests {
+    use crate::client::{JsonValueCache, KeyLogLimiter, UnknownVariantLog};
+    use crate::constants::MISSING_KEY_LOG_CAPACITY;
+
+    #[test]
+    fn missing_key_log_limited_per_key() {
+        let mut limiter = KeyLogLimiter::default();
+        assert!(limiter.allow("key1"));
+        assert!(!limiter.allow("key1"));
+        assert!(limiter.allow("key2"));
+        assert!(!limiter.allow("key2"));
+    }
+
+    #[test]
+    fn missing_key_log_limited_at_capacity() {
+        let mut limiter = KeyLogLimiter::default();
+        for i in 0..MISSING_KEY_LOG_CAPACITY {
+            assert!(limiter.allow(format!("key{i}").as_str()));
+        }
+        assert!(limiter.allow("new-key"));
+        assert!(!limiter.allow("new-key"));
+        assert_eq!(limiter.last_logged.len(), MISSING_KEY_LOG_CAPACITY);
+    }
+
+    #[test]
+    fn json_value_cache_keyed_by_etag_and_type() {
+        let mut cache = JsonValueCache::default();
+        cache.insert("etag1", "[1,2]", vec![1, 2]);
+        assert_eq!(cache.get::<Vec<i32>>("etag1", "[1,2]"), Some(vec![1, 2]));
+        assert_eq!(cache.get::<Vec<i64>>("etag1", "[1,2]"), None);
+        assert_eq!(cache.get::<Vec<i32>>("etag2", "[1,2]"), None);
+
+        cache.insert("etag2", "[3]", vec![3]);
+        assert_eq!(cache.get::<Vec<i32>>("etag2", "[3]"), Some(vec![3]));
+        assert_eq!(cache.get::<Vec<i32>>("etag2", "[1,2]"), None);
+    }
+
+    #[test]
+    fn unknown_variant_log_reset_on_config_change() {
+        let mut log = UnknownVariantLog::default();
+        assert!(log.first("etag1", "key", "v4"));
+        assert!(!log.first("etag1", "key", "v4"));
+        assert!(log.first("etag1", "key", "v5"));
+        assert!(log.first("etag2", "key", "v4"));
+        assert_eq!(log.logged.len(), 1);
+    }
+}
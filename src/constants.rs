@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// The ConfigCat Rust SDK's version.
 pub const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -5,9 +7,57 @@ pub const SDK_KEY_PROXY_PREFIX: &str = "configcat-proxy/";
 pub const SDK_KEY_SECTION_LENGTH: usize = 22;
 pub const SDK_KEY_PREFIX: &str = "configcat-sdk-1";
 pub const CONFIG_FILE_NAME: &str = "config_v6.json";
+/// The format version of the config JSON downloaded from [`CONFIG_FILE_NAME`].
+pub const CONFIG_FORMAT_VERSION: u8 = 6;
 pub const SERIALIZATION_FORMAT_VERSION: &str = "v2";
+/// Previous serialization format versions whose cache entries are migrated on read, newest first.
+pub const LEGACY_SERIALIZATION_FORMAT_VERSIONS: &[&str] = &["v1"];
+/// The number of consecutive transient fetch failures after which the next failover URL is used.
+pub const FAILOVER_THRESHOLD: u32 = 3;
+/// The minimum time between two missing key error logs of the same key.
+pub const MISSING_KEY_LOG_INTERVAL: Duration = Duration::from_secs(60);
+/// The maximum number of keys tracked for missing key error log de-duplication.
+pub const MISSING_KEY_LOG_CAPACITY: usize = 1024;
+/// Config JSON bodies at least this large are parsed on tokio's blocking thread pool.
+#[cfg(feature = "network")]
+pub const BACKGROUND_PARSE_THRESHOLD: usize = 256 * 1024;
+/// The number of users evaluated together on one blocking thread by [`crate::Client::evaluate_batch`].
+pub const BATCH_CHUNK_SIZE: usize = 1024;
+/// The number of [`crate::ClientEvent`]s buffered for each receiver of [`crate::Client::events`].
+pub const EVENT_CHANNEL_CAPACITY: usize = 64;
+/// The fetch duration assumed by the early refresh until the first fetch's latency is known.
+pub const EARLY_REFRESH_DEFAULT_DELTA: Duration = Duration::from_secs(1);
+/// When this environment variable is set to `1` or `true`, the SDK doesn't initiate HTTP requests.
+pub const OFFLINE_ENV_VAR: &str = "CONFIGCAT_SDK_OFFLINE";
+// the event IDs of the log messages specific to this SDK, the ones shared with the other ConfigCat SDKs are inlined
+/// The % options of a setting have out of range percentages or sum to more than 100, its evaluations fail.
+pub const INVALID_PERCENTAGE_OPTIONS_EVENT_ID: u32 = 3008;
+/// The percentages of the % options of a setting sum to less than 100, detected when parsing the config JSON.
+pub const INCOMPLETE_PERCENTAGE_OPTIONS_EVENT_ID: u32 = 3009;
+/// Fetching the config JSON failed [`FAILOVER_THRESHOLD`] times in a row, the next failover URL is used.
+pub const FAILOVER_SWITCH_EVENT_ID: u32 = 1110;
+/// Re-applying the changed flag overrides to the current config JSON failed.
+pub const OVERRIDES_REAPPLY_FAILED_EVENT_ID: u32 = 1303;
+/// The changed flag overrides can't be re-applied before the next config JSON download, as neither the memory
+/// nor the cache holds the config JSON in [`crate::MemoryMode::Bounded`].
+pub const OVERRIDES_REAPPLY_DEFERRED_EVENT_ID: u32 = 1304;
+/// A cache entry written with a previous serialization format couldn't be migrated, it's ignored.
+pub const LEGACY_CACHE_MIGRATION_FAILED_EVENT_ID: u32 = 2202;
+/// The cache entry passed to [`crate::ClientBuilder::preload_cache_entry`] couldn't be read, it's ignored.
+pub const PRELOADED_CACHE_ENTRY_INVALID_EVENT_ID: u32 = 2203;
+/// A Brotli-compressed cache entry couldn't be decompressed, it's treated as missing.
+#[cfg(feature = "brotli")]
+pub const CACHE_DECOMPRESSION_FAILED_EVENT_ID: u32 = 2204;
+// the environment variables read by `ClientBuilder::from_env`
+pub const SDK_KEY_ENV_VAR: &str = "CONFIGCAT_SDK_KEY";
+pub const BASE_URL_ENV_VAR: &str = "CONFIGCAT_BASE_URL";
+pub const POLLING_MODE_ENV_VAR: &str = "CONFIGCAT_POLLING_MODE";
+pub const POLL_INTERVAL_ENV_VAR: &str = "CONFIGCAT_POLL_INTERVAL_SECS";
+pub const HTTP_TIMEOUT_ENV_VAR: &str = "CONFIGCAT_HTTP_TIMEOUT_SECS";
+pub const DATA_GOVERNANCE_ENV_VAR: &str = "CONFIGCAT_DATA_GOVERNANCE";
 
 #[cfg(test)]
+#[cfg(feature = "network")]
 pub mod test_constants {
     pub const MOCK_PATH: &str = "/configuration-files/key/config_v6.json";
     pub const MOCK_KEY: &str = "key";
@@ -0,0 +1,39 @@
+use crate::Value;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+/// Provides the fallback values of feature flags and settings, set with [`crate::ClientBuilder::default_value_provider`].
+///
+/// The provider is consulted when the evaluation of a feature flag or setting fails (e.g. the key is missing
+/// from the config JSON, or the config JSON isn't available yet), before falling back to the default value
+/// passed to the evaluation methods. A provided value is used only when its type matches the requested type.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{Client, DefaultValueProvider, Value};
+///
+/// struct CodeDefaults {}
+///
+/// impl DefaultValueProvider for CodeDefaults {
+///     fn default_value(&self, key: &str) -> Option<Value> {
+///         match key {
+///             "isFeatureEnabled" => Some(Value::Bool(false)),
+///             "maxItems" => Some(Value::Int(10)),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let builder = Client::builder("sdk-key").default_value_provider(CodeDefaults {});
+/// ```
+pub trait DefaultValueProvider: Sync + Send {
+    /// Returns the fallback value of the feature flag or setting identified by `key`, or `None` when there's none.
+    fn default_value(&self, key: &str) -> Option<Value>;
+}
+
+impl<S: BuildHasher + Sync + Send> DefaultValueProvider for HashMap<String, Value, S> {
+    fn default_value(&self, key: &str) -> Option<Value> {
+        self.get(key).cloned()
+    }
+}
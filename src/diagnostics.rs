@@ -0,0 +1,89 @@
+use crate::FetchCounters;
+use serde::Serialize;
+
+/// A snapshot of the [`crate::Client`]'s configuration and state, meant to be attached to support tickets.
+///
+/// The report doesn't contain personal data: the SDK Key is masked, credentials are removed from the base URL,
+/// and the example evaluation log is produced without a User Object.
+///
+/// Created with [`crate::Client::diagnostics_report`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat::Client;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = Client::new("sdk-key").unwrap();
+///
+///     let report = client.diagnostics_report().await;
+///     println!("{}", report.to_json());
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsReport {
+    /// The version of the SDK.
+    pub sdk_version: String,
+    /// The SDK Key with all but its last 6 characters masked.
+    pub sdk_key: String,
+    /// The configured polling mode.
+    pub polling_mode: String,
+    /// The custom base URL without credentials (if any).
+    pub base_url: Option<String>,
+    /// Indicates whether the client is in offline mode.
+    pub offline: bool,
+    /// The current [`crate::ClientCacheState`] of the client.
+    pub cache_state: String,
+    /// The current [`crate::DataGovernanceStatus`] of the client.
+    pub data_governance_status: String,
+    /// The number of feature flags and settings in the config JSON.
+    pub setting_count: usize,
+    /// Time of the last successful config download in RFC 3339 format (if any).
+    pub fetch_time: Option<String>,
+    /// Age of the config JSON in milliseconds (if any).
+    pub config_age_ms: Option<u128>,
+    /// The HTTP status of the last config download attempt (if any).
+    pub last_fetch_status: Option<u16>,
+    /// The latency of the last config download attempt in milliseconds (if any).
+    pub last_fetch_latency_ms: Option<u128>,
+    /// The outcomes of the config downloads so far and the local revision of the config JSON.
+    pub fetch_counters: FetchCounters,
+    /// The minimum SDK version required by the preferences of the config JSON, only set when it's newer than
+    /// `sdk_version` (the SDK should be upgraded).
+    pub required_sdk_version: Option<String>,
+    /// The evaluation log of the first feature flag or setting (by key), evaluated without a User Object.
+    pub example_eval_log: Option<String>,
+}
+
+impl DiagnosticsReport {
+    /// Serializes the report to pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Removes the credentials, query and fragment from `url`.
+#[cfg(feature = "network")]
+pub(crate) fn redact_url(url: &str) -> String {
+    let Ok(mut parsed) = reqwest::Url::parse(url) else {
+        return "<invalid URL>".to_owned();
+    };
+    _ = parsed.set_username("");
+    _ = parsed.set_password(None);
+    parsed.set_query(None);
+    parsed.set_fragment(None);
+    parsed.to_string()
+}
+
+/// Removes the credentials, query and fragment from `url`.
+#[cfg(not(feature = "network"))]
+pub(crate) fn redact_url(url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return "<invalid URL>".to_owned();
+    };
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    format!("{scheme}://{host}{path}")
+}
@@ -1,5 +1,6 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::io;
 
 /// Error kind that represents failures reported by the [`crate::Client`].
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -30,12 +31,34 @@ pub enum ErrorKind {
     InvalidHttpResponseWhenLocalCacheIsEmpty = 1106,
     /// The evaluation failed because of a type mismatch between the evaluated setting value and the specified default value.
     SettingValueTypeMismatch = 2002,
+    /// The evaluation failed because the setting's value couldn't be deserialized from JSON or converted to the requested type.
+    SettingValueParseFailure = 2003,
     /// The client is in offline mode, it cannot initiate HTTP requests.
     OfflineClient = 3200,
     /// The refresh operation failed because the client is configured to use the [`crate::OverrideBehavior::LocalOnly`] override behavior,
     LocalOnlyClient = 3202,
     /// Initialization of the [`crate::Client`] timed out.
     ClientInitTimedOut = 4200,
+    /// The evaluation failed because the config JSON was not available yet in [`crate::PollingMode::Manual`]
+    /// (reported instead of [`ErrorKind::ConfigJsonNotAvailable`] when [`crate::NotReadyBehavior::Error`] is set).
+    NotReady = 4201,
+    /// The evaluation returned the default value because the config JSON was older than the maximum age
+    /// set with [`crate::ClientBuilder::max_config_age`] and [`crate::StaleBehavior::Error`].
+    ConfigJsonTooOld = 4202,
+    /// The SDK was built without the `network` feature, so the client cannot download the config JSON
+    /// and can only be used with the [`crate::OverrideBehavior::LocalOnly`] override behavior.
+    NetworkUnavailable = 4203,
+    /// The options passed to [`crate::ClientBuilder::from_config`] or the environment variables read by
+    /// [`crate::ClientBuilder::from_env`] are missing or invalid.
+    InvalidConfiguration = 4204,
+    /// The base URL set with [`crate::ClientBuilder::base_url`] or [`crate::ClientBuilder::failover_urls`] is invalid.
+    InvalidBaseUrl = 4205,
+    /// The config JSON download was skipped because the circuit breaker set with [`crate::ClientBuilder::circuit_breaker`]
+    /// is open after too many consecutive failures.
+    CircuitOpen = 4206,
+    /// The [`crate::Client`] was created outside of a tokio runtime, but it needs one to run its background tasks
+    /// (e.g. the polling of [`crate::PollingMode::AutoPoll`]).
+    NoAsyncRuntime = 4207,
 }
 
 impl ErrorKind {
@@ -43,10 +66,41 @@ impl ErrorKind {
     pub(crate) fn as_u8(self) -> u8 {
         self as u8
     }
+
+    fn io_kind(self) -> io::ErrorKind {
+        match self {
+            ErrorKind::HttpRequestTimeout | ErrorKind::ClientInitTimedOut => {
+                io::ErrorKind::TimedOut
+            }
+            ErrorKind::InvalidSdkKey => io::ErrorKind::PermissionDenied,
+            ErrorKind::ConfigJsonNotAvailable | ErrorKind::SettingKeyMissing => {
+                io::ErrorKind::NotFound
+            }
+            ErrorKind::UnexpectedHttpResponse
+            | ErrorKind::InvalidHttpResponseContent
+            | ErrorKind::InvalidHttpResponseWhenLocalCacheIsEmpty
+            | ErrorKind::SettingValueTypeMismatch
+            | ErrorKind::SettingValueParseFailure => io::ErrorKind::InvalidData,
+            ErrorKind::InvalidConfiguration | ErrorKind::InvalidBaseUrl => {
+                io::ErrorKind::InvalidInput
+            }
+            ErrorKind::OfflineClient
+            | ErrorKind::LocalOnlyClient
+            | ErrorKind::NetworkUnavailable
+            | ErrorKind::NoAsyncRuntime => io::ErrorKind::Unsupported,
+            ErrorKind::NotReady | ErrorKind::CircuitOpen => io::ErrorKind::WouldBlock,
+            ErrorKind::NoError
+            | ErrorKind::HttpClientInitFailure
+            | ErrorKind::EvaluationFailure
+            | ErrorKind::HttpRequestFailure
+            | ErrorKind::RedirectLoop
+            | ErrorKind::ConfigJsonTooOld => io::ErrorKind::Other,
+        }
+    }
 }
 
 /// Error struct that holds the [`ErrorKind`] and message of the reported failure.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ClientError {
     /// Error kind that represents failures reported by the [`crate::Client`].
     pub kind: ErrorKind,
@@ -58,6 +112,30 @@ impl ClientError {
     pub(crate) fn new(kind: ErrorKind, message: String) -> Self {
         Self { kind, message }
     }
+
+    /// Prepends `context` to the message of the error, keeping its [`ErrorKind`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     if let Err(err) = client.refresh().await {
+    ///         let err = err.context("refreshing the feature flags of the checkout service");
+    ///         eprintln!("{err}");
+    ///     }
+    /// }
+    /// ```
+    pub fn context(self, context: impl Display) -> Self {
+        Self {
+            kind: self.kind,
+            message: format!("{context}: {}", self.message),
+        }
+    }
 }
 
 impl Display for ClientError {
@@ -67,3 +145,56 @@ impl Display for ClientError {
 }
 
 impl Error for ClientError {}
+
+impl From<ClientError> for io::Error {
+    /// Converts the [`ClientError`] to an [`io::Error`] of the closest [`io::ErrorKind`], e.g.
+    /// [`ErrorKind::HttpRequestTimeout`] becomes [`io::ErrorKind::TimedOut`].
+    /// The original error is kept as the inner error.
+    fn from(err: ClientError) -> Self {
+        io::Error::new(err.kind.io_kind(), err)
+    }
+}
+
+// keeps `ClientError` usable with error reporting crates like `anyhow` and `eyre`
+const _: fn() = || {
+    fn assert_send_sync<T: Error + Send + Sync + 'static>() {}
+    assert_send_sync::<ClientError>();
+};
+
+#[cfg(test)]
+mod errors_tests {
+    use crate::{ClientError, ErrorKind};
+    use std::io;
+
+    #[test]
+    fn context() {
+        let err = ClientError::new(
+            ErrorKind::HttpRequestTimeout,
+            "Request timed out.".to_owned(),
+        )
+        .context("refreshing the config");
+        assert_eq!(err.kind, ErrorKind::HttpRequestTimeout);
+        assert_eq!(err.message, "refreshing the config: Request timed out.");
+    }
+
+    #[test]
+    fn into_io_error() {
+        let err: io::Error = ClientError::new(
+            ErrorKind::HttpRequestTimeout,
+            "Request timed out.".to_owned(),
+        )
+        .into();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(err.to_string(), "Request timed out.");
+        let inner = err
+            .get_ref()
+            .unwrap()
+            .downcast_ref::<ClientError>()
+            .unwrap();
+        assert_eq!(inner.kind, ErrorKind::HttpRequestTimeout);
+
+        let err: io::Error =
+            ClientError::new(ErrorKind::InvalidBaseUrl, "Invalid base URL.".to_owned()).into();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -1,7 +1,15 @@
 use crate::eval::evaluator::EvalResult;
-use crate::{ClientError, PercentageOption, TargetingRule, User};
+use crate::fetch::fetcher::FetchStats;
+use crate::{
+    BucketingStrategy, ClientError, Config, ErrorKind, PercentageOption, SemverParsing,
+    SettingType, TargetingRule, TextNormalization, User, Value, WhitespaceTrimming,
+};
 use chrono::{DateTime, Utc};
+use log::LevelFilter;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Details of the flag evaluation's result.
 ///
@@ -27,20 +35,141 @@ pub struct EvaluationDetails<T> {
     pub value: T,
     /// Key of the feature flag or setting.
     pub key: String,
-    /// Indicates whether the default value passed to the setting evaluation methods is used as the result of the evaluation.
+    /// Indicates whether the default value passed to the setting evaluation methods (or the one provided by the
+    /// [`crate::DefaultValueProvider`]) is used as the result of the evaluation, see [`EvaluationDetails::value_source`].
     pub is_default_value: bool,
+    /// The source of [`EvaluationDetails::value`].
+    pub value_source: ValueSource,
     /// Variation ID of the feature flag or setting (if available).
     pub variation_id: Option<String>,
+    /// The type of the evaluated feature flag or setting as defined in the config JSON
+    /// (not available when the evaluation failed).
+    pub setting_type: Option<SettingType>,
     /// The User Object used for the evaluation (if available).
     pub user: Option<User>,
-    /// Error in case evaluation failed.
+    /// Error in case evaluation failed. Also set with a served `value` when an invalid part of the setting
+    /// was skipped, e.g. % options whose percentages sum to less than 100.
     pub error: Option<ClientError>,
     /// Time of last successful config download on which the evaluation was based.
     pub fetch_time: Option<DateTime<Utc>>,
+    /// Age of the config at the time of the evaluation, the time elapsed since [`EvaluationDetails::fetch_time`].
+    pub config_age: Option<Duration>,
+    /// The HTTP status and latency of the last config download attempt (if any).
+    pub last_fetch: Option<FetchStats>,
     /// The targeting rule (if any) that matched during the evaluation and was used to return the evaluated value.
     pub matched_targeting_rule: Option<Arc<TargetingRule>>,
     /// The percentage option (if any) that was used to select the evaluated value.
     pub matched_percentage_option: Option<Arc<PercentageOption>>,
+    /// The index of the targeting rule (if any) that matched during the evaluation.
+    pub matched_rule_index: Option<usize>,
+    /// The outcomes of the targeting rules in evaluation order, up to and including the matching one.
+    pub rule_outcomes: Vec<RuleOutcome>,
+    /// The keys and evaluated values of the prerequisite flags (including the nested ones) in evaluation order.
+    /// Each prerequisite flag is listed once, empty when the evaluation didn't involve prerequisite flags.
+    pub prerequisites: Vec<(String, Value)>,
+    /// The wall time spent on each evaluated targeting rule condition, in evaluation order.
+    ///
+    /// Only collected when [`EvalOptions::profile`] is set, empty otherwise.
+    pub condition_timings: Vec<ConditionTiming>,
+    /// The config the evaluation was based on, e.g. to resolve the segments referenced by [`EvaluationDetails::matched_targeting_rule`].
+    ///
+    /// Only set when it's enabled with [`crate::ClientBuilder::include_config_in_details`].
+    pub config: Option<Arc<Config>>,
+}
+
+/// The source of the value in [`EvaluationDetails`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ValueSource {
+    /// The value is the result of evaluating the feature flag or setting.
+    #[default]
+    Evaluation,
+    /// The evaluation failed, the value was provided by the [`crate::DefaultValueProvider`] set with
+    /// [`crate::ClientBuilder::default_value_provider`].
+    DefaultValueProvider,
+    /// The evaluation failed, the value is the default value passed to the setting evaluation method.
+    DefaultParameter,
+}
+
+/// Describes the outcome of a targeting rule visited during the flag evaluation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleOutcome {
+    /// The targeting rule matched and its value was used as the result of the evaluation.
+    Matched,
+    /// The conditions of the targeting rule didn't match.
+    NotMatched,
+    /// The targeting rule was skipped because the User Object was missing.
+    UserMissing,
+    /// The targeting rule was skipped because the given User attribute was missing.
+    AttributeMissing(String),
+    /// The targeting rule was skipped because the given User attribute was invalid.
+    AttributeInvalid(String),
+    /// The targeting rule matched, but its % options were skipped because their percentages sum to less than 100.
+    PercentageOptionsIncomplete,
+}
+
+/// The time spent on evaluating a single targeting rule condition, collected when [`EvalOptions::profile`] is set.
+///
+/// The conditions of prerequisite flags are listed too (with the key of the prerequisite flag), before the
+/// prerequisite flag condition that triggered their evaluation, whose duration includes theirs.
+/// The conditions skipped after a non-matching AND condition aren't listed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionTiming {
+    /// Key of the feature flag or setting that owns the condition.
+    pub key: String,
+    /// The index of the targeting rule that owns the condition.
+    pub rule_index: usize,
+    /// The index of the condition within the targeting rule's conditions.
+    pub condition_index: usize,
+    /// The comparator of the condition as it appears in the evaluation log, e.g. `IS ONE OF (hashed)`
+    /// or `IS IN SEGMENT`.
+    pub comparator: String,
+    /// The wall time spent on evaluating the condition.
+    pub duration: Duration,
+}
+
+/// The result of a feature flag or setting evaluated for one user of a batch by [`crate::Client::evaluate_batch`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchEvaluation {
+    /// Identifier of the user.
+    pub identifier: String,
+    /// Value of the feature flag or setting, or `None` when the evaluation failed.
+    pub value: Option<Value>,
+    /// Variation ID of the feature flag or setting (if available).
+    pub variation_id: Option<String>,
+}
+
+/// The values of all feature flags and settings evaluated by [`crate::Client::get_all_values_strict`],
+/// with the errors of the evaluations that failed.
+#[derive(Debug, Default, PartialEq)]
+pub struct AllValuesResult {
+    /// The values of the feature flags and settings that were evaluated successfully (or with a non-fatal error).
+    pub values: HashMap<String, Value>,
+    /// The keys of the feature flags and settings whose evaluation failed, with the reported errors.
+    pub errors: Vec<(String, ClientError)>,
+}
+
+impl AllValuesResult {
+    /// Indicates whether the evaluation of any feature flag or setting failed.
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    pub(crate) fn from_details(details: Vec<EvaluationDetails<Option<Value>>>) -> Self {
+        let mut result = Self {
+            values: HashMap::with_capacity(details.len()),
+            errors: Vec::new(),
+        };
+        for detail in details {
+            // a non-fatal error (e.g. skipped % options) comes with the value
+            if let Some(val) = detail.value {
+                result.values.insert(detail.key.clone(), val);
+            }
+            if let Some(err) = detail.error {
+                result.errors.push((detail.key, err));
+            }
+        }
+        result
+    }
 }
 
 impl<T: Default> EvaluationDetails<T> {
@@ -49,6 +178,7 @@ impl<T: Default> EvaluationDetails<T> {
             value: val,
             key: key.to_owned(),
             is_default_value: true,
+            value_source: ValueSource::DefaultParameter,
             user,
             error: Some(err),
             ..EvaluationDetails::default()
@@ -56,13 +186,144 @@ impl<T: Default> EvaluationDetails<T> {
     }
 }
 
+impl<T> EvaluationDetails<T> {
+    pub(crate) fn with_value<U>(self, value: U) -> EvaluationDetails<U> {
+        EvaluationDetails {
+            value,
+            key: self.key,
+            is_default_value: self.is_default_value,
+            value_source: self.value_source,
+            variation_id: self.variation_id,
+            setting_type: self.setting_type,
+            user: self.user,
+            error: self.error,
+            fetch_time: self.fetch_time,
+            config_age: self.config_age,
+            last_fetch: self.last_fetch,
+            matched_targeting_rule: self.matched_targeting_rule,
+            matched_percentage_option: self.matched_percentage_option,
+            matched_rule_index: self.matched_rule_index,
+            rule_outcomes: self.rule_outcomes,
+            prerequisites: self.prerequisites,
+            condition_timings: self.condition_timings,
+            config: self.config,
+        }
+    }
+}
+
+impl<T: Debug> EvaluationDetails<T> {
+    /// Returns the single-line summary of the evaluation, the same as the [`Display`] output.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("sdk-key").unwrap();
+    ///
+    ///     let details = client.get_value_details("flag-key", false, Some(User::new("user-id"))).await;
+    ///     // key=flag-key value=true default=false variation="a0e56eda" rule=0 age=1234ms
+    ///     println!("{}", details.summary());
+    /// }
+    /// ```
+    pub fn summary(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Writes a compact, single-line summary of the evaluation in a stable `name=value` format:
+/// the key, the value (in its [`Debug`] format), whether the default value was used, the variation ID,
+/// the index of the matched targeting rule, the age of the config in milliseconds, and the error (if any).
+/// Missing (and empty) fields are written as `-`.
+impl<T: Debug> Display for EvaluationDetails<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "key={} value={:?} default={}",
+            self.key, self.value, self.is_default_value
+        )?;
+        match self.variation_id.as_ref().filter(|id| !id.is_empty()) {
+            Some(variation_id) => write!(f, " variation={variation_id:?}")?,
+            None => f.write_str(" variation=-")?,
+        }
+        match self.matched_rule_index {
+            Some(index) => write!(f, " rule={index}")?,
+            None => f.write_str(" rule=-")?,
+        }
+        match self.config_age {
+            Some(age) => write!(f, " age={}ms", age.as_millis())?,
+            None => f.write_str(" age=-")?,
+        }
+        if let Some(err) = self.error.as_ref() {
+            write!(f, " error={:?}", err.message)?;
+        }
+        Ok(())
+    }
+}
+
 impl<T: Default> From<EvalResult> for EvaluationDetails<T> {
     fn from(value: EvalResult) -> Self {
         EvaluationDetails {
             variation_id: value.variation_id,
+            setting_type: Some(value.setting_type),
             matched_targeting_rule: value.rule,
             matched_percentage_option: value.option,
+            matched_rule_index: value.rule_index,
+            rule_outcomes: value.rule_outcomes,
+            prerequisites: value.prerequisites,
+            condition_timings: value.condition_timings,
+            error: value.warning.map(warning_error),
             ..EvaluationDetails::default()
         }
     }
 }
+
+pub(crate) fn warning_error(warning: String) -> ClientError {
+    ClientError::new(ErrorKind::EvaluationFailure, warning)
+}
+
+/// Options that adjust a single feature flag evaluation.
+///
+/// # Examples
+///
+/// ```no_run
+/// use chrono::{TimeZone, Utc};
+/// use configcat::{Client, EvalOptions, User};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = Client::new("sdk-key").unwrap();
+///
+///     let options = EvalOptions { now: Some(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap()), ..EvalOptions::default() };
+///     let details = client.get_value_details_with_options("flag-key", false, Some(User::new("user-id")), &options).await;
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EvalOptions {
+    /// The timestamp that the `BEFORE` / `AFTER` date comparators compare with instead of the evaluated user attribute,
+    /// so date-windowed feature flags can be tested ahead of time. When `None`, the user attribute is used.
+    pub now: Option<DateTime<Utc>>,
+    /// Enables the profiling of the evaluation, the time spent on each targeting rule condition is collected
+    /// into [`EvaluationDetails::condition_timings`]. Adds some overhead, meant for offline analysis of slow feature flags.
+    pub profile: bool,
+    /// The normalization applied before text comparisons. When `None`, the one set with
+    /// [`crate::ClientBuilder::text_normalization`] is used.
+    pub text_normalization: Option<TextNormalization>,
+    /// The trimming applied before text comparisons. When `None`, the one set with
+    /// [`crate::ClientBuilder::whitespace_trimming`] is used.
+    pub whitespace_trimming: Option<WhitespaceTrimming>,
+    /// The assignment of users to the buckets of % options. When `None`, the one set with
+    /// [`crate::ClientBuilder::bucketing_strategy`] is used.
+    pub bucketing_strategy: Option<BucketingStrategy>,
+    /// The leniency of the semantic version parsing. When `None`, the one set with
+    /// [`crate::ClientBuilder::semver_parsing`] is used.
+    pub semver_parsing: Option<SemverParsing>,
+    /// The maximum level of the evaluation related log messages. When `None`, the level of
+    /// [`crate::LogArea::Evaluation`] set with [`crate::ClientBuilder::log_level`] is used.
+    pub log_level: Option<LevelFilter>,
+    /// A seed mixed into the hash input of the % options, see [`crate::Simulation`]. When `None`, the users
+    /// are bucketed like in the other ConfigCat SDKs.
+    pub hash_seed: Option<u64>,
+}
@@ -1,7 +1,10 @@
+use crate::eval::details::{ConditionTiming, EvalOptions, RuleOutcome};
 use crate::eval::evaluator::ConditionResult::{
     AttrInvalid, AttrMissing, CompValInvalid, Fatal, NoUser, Success,
 };
+use crate::eval::hashing::{HashScheme, Salt};
 use crate::eval::log_builder::EvalLogBuilder;
+use crate::user::UserValue;
 use crate::value::{OptionalValueDisplay, Value};
 use crate::UserComparator::{
     AfterDateTime, ArrayContainsAnyOf, ArrayContainsAnyOfHashed, ArrayNotContainsAnyOf,
@@ -13,22 +16,20 @@ use crate::UserComparator::{
     StartsWithAnyOfHashed,
 };
 use crate::{
-    utils, Condition, PercentageOption, PrerequisiteFlagComparator, PrerequisiteFlagCondition,
+    utils, BucketingStrategy, Condition, PercentageOption, PrerequisiteFlagComparator,
+    PrerequisiteFlagCondition,
     SegmentComparator::{IsIn, IsNotIn},
-    SegmentCondition, ServedValue, Setting, SettingType, SettingValue, TargetingRule, User,
-    UserComparator, UserCondition,
+    SegmentCondition, SemverParsing, ServedValue, Setting, SettingType, SettingValue,
+    TargetingRule, TextNormalization, User, UserComparator, UserCondition, WhitespaceTrimming,
 };
-use log::{info, log_enabled, warn};
-use semver::Version;
-use std::collections::HashMap;
+use log::{info, log_enabled, Level, LevelFilter};
+use semver::{BuildMetadata, Version};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::sync::Arc;
-
-macro_rules! eval_log_enabled {
-    () => {
-        log_enabled!(log::Level::Info)
-    };
-}
+use std::time::Instant;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
 
 const RULE_IGNORED_MSG: &str =
     "The current targeting rule is ignored and the evaluation continues with the next rule.";
@@ -37,18 +38,163 @@ const COMP_VAL_INVALID_MSG: &str = "Comparison value is missing or invalid";
 const SETTING_VAL_INVALID_MSG: &str = "Setting value is missing or invalid";
 const IDENTIFIER_ATTR: &str = "Identifier";
 
+// the maximum level of the evaluation related log messages
+fn max_log_level(options: &EvalOptions) -> LevelFilter {
+    options.log_level.unwrap_or(LevelFilter::Trace)
+}
+
 pub struct EvalResult {
     pub value: Value,
     pub variation_id: Option<String>,
     pub rule: Option<Arc<TargetingRule>>,
     pub option: Option<Arc<PercentageOption>>,
     pub setting_type: SettingType,
+    pub rule_index: Option<usize>,
+    pub rule_outcomes: Vec<RuleOutcome>,
+    // a non-fatal issue of the setting that was skipped during the evaluation
+    pub warning: Option<String>,
+    // the prerequisite flags evaluated along the way, in evaluation order
+    pub prerequisites: Vec<(String, Value)>,
+    // the timings of the evaluated conditions, only collected when profiling
+    pub condition_timings: Vec<ConditionTiming>,
+}
+
+impl EvalResult {
+    fn with_rule_outcomes(
+        mut self,
+        index: Option<usize>,
+        outcomes: Vec<RuleOutcome>,
+        warning: Option<String>,
+    ) -> Self {
+        self.rule_index = index;
+        self.rule_outcomes = outcomes;
+        self.warning = warning;
+        self
+    }
+}
+
+// the prerequisite flags of a single top-level evaluation
+#[derive(Default)]
+struct PrerequisiteTracker {
+    // the keys of the flags being evaluated, for detecting circular dependencies
+    path: Vec<String>,
+    // the results of the prerequisite flags evaluated so far
+    results: HashMap<String, Value>,
+    // the keys and results of the prerequisite flags in evaluation order
+    evaluated: Vec<(String, Value)>,
+    // the timings of the evaluated conditions when profiling, see `EvalOptions::profile`
+    timings: Vec<ConditionTiming>,
+}
+
+/// The per-condition structures of a setting and its prerequisite flags, computed once and shared by
+/// the evaluations of a batch. It must be used together with the same `settings` it was built from,
+/// as the conditions are identified by their addresses.
+#[derive(Default)]
+pub struct Prepared {
+    conditions: HashMap<usize, PreparedCondition>,
+}
+
+enum PreparedCondition {
+    // the normalized comparison values of an IS (NOT) ONE OF condition
+    OneOf(HashSet<String>),
+    // the parsed comparison values of a semver IS (NOT) ONE OF condition, `None` when one of them is invalid
+    SemverOneOf(Option<Vec<Version>>),
+    // the parsed comparison value of a semver comparison, `None` when it's invalid
+    Semver(Option<Version>),
+}
+
+impl Prepared {
+    pub fn new(
+        setting: &Setting,
+        key: &str,
+        settings: &HashMap<String, Setting>,
+        options: &EvalOptions,
+    ) -> Self {
+        let mut prepared = Self::default();
+        prepared.add_setting(setting, key, settings, options, &mut HashSet::new());
+        prepared
+    }
+
+    fn add_setting<'a>(
+        &mut self,
+        setting: &'a Setting,
+        key: &'a str,
+        settings: &'a HashMap<String, Setting>,
+        options: &EvalOptions,
+        visited: &mut HashSet<&'a str>,
+    ) {
+        if !visited.insert(key) {
+            return;
+        }
+        let conditions = setting
+            .targeting_rules
+            .iter()
+            .flatten()
+            .filter_map(|rule| rule.conditions.as_ref())
+            .flatten();
+        for condition in conditions {
+            if let Some(user_condition) = condition.user_condition.as_ref() {
+                self.add_condition(user_condition, key, options);
+            } else if let Some(segment) = condition
+                .segment_condition
+                .as_ref()
+                .and_then(|cond| cond.segment.as_ref())
+            {
+                for user_condition in &segment.conditions {
+                    self.add_condition(user_condition, key, options);
+                }
+            } else if let Some(cond) = condition.prerequisite_flag_condition.as_ref() {
+                if let Some((flag_key, prerequisite)) = settings.get_key_value(&cond.flag_key) {
+                    self.add_setting(prerequisite, flag_key, settings, options, visited);
+                }
+            }
+        }
+    }
+
+    fn add_condition(&mut self, cond: &UserCondition, key: &str, options: &EvalOptions) {
+        let prepared = match cond.comparator {
+            OneOf | NotOneOf | OneOfHashed | NotOneOfHashed => {
+                let Some(comp_val) = cond.string_vec_val.as_ref() else {
+                    return;
+                };
+                PreparedCondition::OneOf(
+                    comp_val
+                        .iter()
+                        .map(|val| normalized_str(val, options).into_owned())
+                        .collect(),
+                )
+            }
+            OneOfSemver | NotOneOfSemver => {
+                let Some(comp_val) = cond.string_vec_val.as_ref() else {
+                    return;
+                };
+                PreparedCondition::SemverOneOf(parse_semvers(comp_val, cond, key, options))
+            }
+            GreaterSemver | GreaterEqSemver | LessSemver | LessEqSemver => {
+                let Some(comp_val) = cond.string_val.as_ref() else {
+                    return;
+                };
+                PreparedCondition::Semver(parse_semver(comp_val, cond, key, options))
+            }
+            _ => return,
+        };
+        self.conditions.insert(condition_id(cond), prepared);
+    }
+
+    fn get(&self, cond: &UserCondition) -> Option<&PreparedCondition> {
+        self.conditions.get(&condition_id(cond))
+    }
+}
+
+fn condition_id(cond: &UserCondition) -> usize {
+    std::ptr::from_ref(cond) as usize
 }
 
 pub enum PercentageResult {
     Success(Arc<PercentageOption>),
     UserAttrMissing(String),
-    Fatal(String),
+    // the percentages sum to less than 100 and the user's bucket fell outside of them
+    SumUnder100,
 }
 
 pub enum ConditionResult {
@@ -106,10 +252,94 @@ pub fn eval(
     user: Option<&User>,
     settings: &HashMap<String, Setting>,
     default: Option<&Value>,
+    options: &EvalOptions,
 ) -> Result<EvalResult, String> {
-    let mut eval_log = EvalLogBuilder::default();
-    let mut cycle_tracker = Vec::<String>::default();
-    if eval_log_enabled!() {
+    eval_logged(
+        setting,
+        key,
+        user,
+        settings,
+        default,
+        options,
+        &Prepared::default(),
+    )
+}
+
+/// Evaluates the setting like [`eval`], using the per-condition structures computed by [`Prepared::new`].
+pub fn eval_prepared(
+    setting: &Setting,
+    key: &str,
+    user: &User,
+    settings: &HashMap<String, Setting>,
+    options: &EvalOptions,
+    prepared: &Prepared,
+) -> Result<EvalResult, String> {
+    eval_logged(setting, key, Some(user), settings, None, options, prepared)
+}
+
+fn eval_logged(
+    setting: &Setting,
+    key: &str,
+    user: Option<&User>,
+    settings: &HashMap<String, Setting>,
+    default: Option<&Value>,
+    options: &EvalOptions,
+    prepared: &Prepared,
+) -> Result<EvalResult, String> {
+    let mut eval_log =
+        EvalLogBuilder::new(log_enabled!(Level::Info) && Level::Info <= max_log_level(options));
+    let result = eval_with_log(
+        setting,
+        key,
+        user,
+        settings,
+        default,
+        options,
+        prepared,
+        &mut eval_log,
+    );
+    if eval_log.is_enabled() {
+        info!(event_id = 5000; "{}", eval_log.content());
+    }
+    result
+}
+
+/// Evaluates the setting like [`eval`], but returns the evaluation log instead of logging it.
+pub fn eval_log(
+    setting: &Setting,
+    key: &str,
+    user: Option<&User>,
+    settings: &HashMap<String, Setting>,
+) -> String {
+    let mut eval_log = EvalLogBuilder::new(true);
+    _ = eval_with_log(
+        setting,
+        key,
+        user,
+        settings,
+        None,
+        &EvalOptions::default(),
+        &Prepared::default(),
+        &mut eval_log,
+    );
+    eval_log.content().to_owned()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn eval_with_log(
+    setting: &Setting,
+    key: &str,
+    user: Option<&User>,
+    settings: &HashMap<String, Setting>,
+    default: Option<&Value>,
+    options: &EvalOptions,
+    prepared: &Prepared,
+    eval_log: &mut EvalLogBuilder,
+) -> Result<EvalResult, String> {
+    let user = user.map(User::for_evaluation);
+    let user = user.as_deref();
+    let mut prerequisites = PrerequisiteTracker::default();
+    if eval_log.is_enabled() {
         eval_log.append(format!("Evaluating '{key}'").as_str());
         if let Some(user) = user {
             eval_log.append(format!(" for User '{user}'").as_str());
@@ -121,10 +351,17 @@ pub fn eval(
         key,
         user,
         settings,
-        &mut eval_log,
-        &mut cycle_tracker,
-    );
-    if eval_log_enabled!() {
+        eval_log,
+        &mut prerequisites,
+        options,
+        prepared,
+    )
+    .map(|res| EvalResult {
+        prerequisites: prerequisites.evaluated,
+        condition_timings: prerequisites.timings,
+        ..res
+    });
+    if eval_log.is_enabled() {
         if let Ok(res) = &result {
             eval_log.new_ln(Some(format!("Returning '{}'.", res.value).as_str()));
         } else {
@@ -134,55 +371,68 @@ pub fn eval(
                 .new_ln(Some(format!("Returning '{}'.", default.to_str()).as_str()));
         }
         eval_log.dec_indent();
-        info!(event_id = 5000; "{}", eval_log.content());
     }
     result
 }
 
-#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 fn eval_setting(
     setting: &Setting,
     key: &str,
     user: Option<&User>,
     settings: &HashMap<String, Setting>,
     log: &mut EvalLogBuilder,
-    cycle_tracker: &mut Vec<String>,
+    prerequisites: &mut PrerequisiteTracker,
+    options: &EvalOptions,
+    prepared: &Prepared,
 ) -> Result<EvalResult, String> {
+    if let Some(issue) = setting.percentage_options_issue.as_ref() {
+        return Err(format!("% options are invalid, {issue}"));
+    }
     let mut user_missing_logged = false;
+    let mut rule_outcomes = Vec::<RuleOutcome>::new();
+    let mut warning = None;
     if let Some(targeting_rules) = setting.targeting_rules.as_ref() {
-        if eval_log_enabled!() {
+        if log.is_enabled() {
             log.new_ln(Some(
                 "Evaluating targeting rules and applying the first match if any:",
             ));
         }
-        for rule in targeting_rules {
+        for (index, rule) in targeting_rules.iter().enumerate() {
             if let Some(conditions) = rule.conditions.as_ref() {
                 let result = eval_conditions(
                     conditions,
                     rule.served_value.as_ref(),
+                    index,
                     key,
                     user,
-                    setting.salt.as_ref(),
+                    Salt::of(setting),
                     key,
                     log,
                     settings,
-                    cycle_tracker,
+                    prerequisites,
+                    options,
+                    prepared,
                 );
-                if eval_log_enabled!() && !result.is_success() {
+                if log.is_enabled() && !result.is_success() {
                     log.inc_indent().new_ln(Some(RULE_IGNORED_MSG)).dec_indent();
                 }
                 match result {
                     Success(true) => {
                         if let Some(served_val) = rule.served_value.as_ref() {
+                            rule_outcomes.push(RuleOutcome::Matched);
                             return produce_result(
                                 &served_val.value,
-                                &setting.setting_type,
+                                setting.setting_type,
                                 served_val.variation_id.as_ref(),
                                 Some(rule.clone()),
                                 None,
-                            );
+                            )
+                            .map(|res| {
+                                res.with_rule_outcomes(Some(index), rule_outcomes, warning)
+                            });
                         }
-                        if eval_log_enabled!() {
+                        if log.is_enabled() {
                             log.inc_indent();
                         }
                         match rule.percentage_options.as_ref() {
@@ -193,32 +443,53 @@ fn eval_setting(
                                         u,
                                         key,
                                         setting.percentage_attribute.as_ref(),
+                                        setting.hash_scheme,
                                         log,
+                                        options,
                                     );
                                     match percentage_result {
                                         PercentageResult::Success(opt) => {
-                                            if eval_log_enabled!() {
+                                            if log.is_enabled() {
                                                 log.dec_indent();
                                             }
+                                            rule_outcomes.push(RuleOutcome::Matched);
                                             return produce_result(
                                                 &opt.served_value,
-                                                &setting.setting_type,
+                                                setting.setting_type,
                                                 opt.variation_id.as_ref(),
                                                 Some(rule.clone()),
                                                 Some(opt.clone()),
-                                            );
+                                            )
+                                            .map(|res| {
+                                                res.with_rule_outcomes(
+                                                    Some(index),
+                                                    rule_outcomes,
+                                                    warning,
+                                                )
+                                            });
                                         }
                                         PercentageResult::UserAttrMissing(attr) => {
-                                            log_attr_missing_percentage(key, attr.as_str());
+                                            log_attr_missing_percentage(
+                                                key,
+                                                attr.as_str(),
+                                                options,
+                                            );
+                                            rule_outcomes.push(RuleOutcome::AttributeMissing(attr));
+                                        }
+                                        PercentageResult::SumUnder100 => {
+                                            warning =
+                                                Some(log_percentage_sum_under_100(key, options));
+                                            rule_outcomes
+                                                .push(RuleOutcome::PercentageOptionsIncomplete);
                                         }
-                                        PercentageResult::Fatal(err) => return Err(err),
                                     }
                                 } else {
                                     if !user_missing_logged {
                                         user_missing_logged = true;
-                                        log_user_missing(key);
+                                        log_user_missing(key, options);
                                     }
-                                    if eval_log_enabled!() {
+                                    rule_outcomes.push(RuleOutcome::UserMissing);
+                                    if log.is_enabled() {
                                         log.new_ln(Some("Skipping % options because the User Object is missing."));
                                     }
                                 }
@@ -229,26 +500,32 @@ fn eval_setting(
                                 )
                             }
                         }
-                        if eval_log_enabled!() {
+                        if log.is_enabled() {
                             log.new_ln(Some(RULE_IGNORED_MSG)).dec_indent();
                         }
                     }
-                    Success(false) => continue,
+                    Success(false) => rule_outcomes.push(RuleOutcome::NotMatched),
                     Fatal(err) => return Err(err),
                     NoUser => {
                         if !user_missing_logged {
                             user_missing_logged = true;
-                            log_user_missing(key);
+                            log_user_missing(key, options);
                         }
-                        continue;
+                        rule_outcomes.push(RuleOutcome::UserMissing);
                     }
                     AttrMissing(attr, cond_str) => {
-                        log_attr_missing(key, attr.as_str(), cond_str.as_str());
-                        continue;
+                        log_attr_missing(key, attr.as_str(), cond_str.as_str(), options);
+                        rule_outcomes.push(RuleOutcome::AttributeMissing(attr));
                     }
                     AttrInvalid(reason, attr, cond_str) => {
-                        log_attr_invalid(key, attr.as_str(), reason.as_str(), cond_str.as_str());
-                        continue;
+                        log_attr_invalid(
+                            key,
+                            attr.as_str(),
+                            reason.as_str(),
+                            cond_str.as_str(),
+                            options,
+                        );
+                        rule_outcomes.push(RuleOutcome::AttributeInvalid(attr));
                     }
                     CompValInvalid(error) => {
                         return match error {
@@ -257,6 +534,8 @@ fn eval_setting(
                         }
                     }
                 }
+            } else {
+                rule_outcomes.push(RuleOutcome::NotMatched);
             }
         }
     }
@@ -268,28 +547,33 @@ fn eval_setting(
                 u,
                 key,
                 setting.percentage_attribute.as_ref(),
+                setting.hash_scheme,
                 log,
+                options,
             );
             match percentage_result {
                 PercentageResult::Success(opt) => {
                     return produce_result(
                         &opt.served_value,
-                        &setting.setting_type,
+                        setting.setting_type,
                         opt.variation_id.as_ref(),
                         None,
                         Some(opt.clone()),
-                    );
+                    )
+                    .map(|res| res.with_rule_outcomes(None, rule_outcomes, warning));
                 }
                 PercentageResult::UserAttrMissing(attr) => {
-                    log_attr_missing_percentage(key, attr.as_str());
+                    log_attr_missing_percentage(key, attr.as_str(), options);
+                }
+                PercentageResult::SumUnder100 => {
+                    warning = Some(log_percentage_sum_under_100(key, options));
                 }
-                PercentageResult::Fatal(err) => return Err(err),
             }
         } else {
             if !user_missing_logged {
-                log_user_missing(key);
+                log_user_missing(key, options);
             }
-            if eval_log_enabled!() {
+            if log.is_enabled() {
                 log.new_ln(Some(
                     "Skipping % options because the User Object is missing.",
                 ));
@@ -298,16 +582,17 @@ fn eval_setting(
     }
     produce_result(
         &setting.value,
-        &setting.setting_type,
+        setting.setting_type,
         setting.variation_id.as_ref(),
         None,
         None,
     )
+    .map(|res| res.with_rule_outcomes(None, rule_outcomes, warning))
 }
 
 fn produce_result(
     sv: &SettingValue,
-    setting_type: &SettingType,
+    setting_type: SettingType,
     variation: Option<&String>,
     rule: Option<Arc<TargetingRule>>,
     option: Option<Arc<PercentageOption>>,
@@ -318,7 +603,12 @@ fn produce_result(
             rule,
             option,
             variation_id: Some(variation.unwrap_or(&String::default()).to_owned()),
-            setting_type: setting_type.clone(),
+            setting_type,
+            rule_index: None,
+            rule_outcomes: Vec::new(),
+            warning: None,
+            prerequisites: Vec::new(),
+            condition_timings: Vec::new(),
         });
     }
     Err(SETTING_VAL_INVALID_MSG.to_owned())
@@ -329,7 +619,9 @@ fn eval_percentage(
     user: &User,
     key: &str,
     percentage_attr: Option<&String>,
+    hash_scheme: HashScheme,
     log: &mut EvalLogBuilder,
+    options: &EvalOptions,
 ) -> PercentageResult {
     let attr = if let Some(percentage_attr) = percentage_attr {
         percentage_attr
@@ -337,7 +629,7 @@ fn eval_percentage(
         IDENTIFIER_ATTR
     };
     let Some(user_attr) = user.get(attr) else {
-        if eval_log_enabled!() {
+        if log.is_enabled() {
             log.new_ln(Some(
                 format!("Skipping % options because the User.{attr} attribute is missing.")
                     .as_str(),
@@ -345,26 +637,33 @@ fn eval_percentage(
         }
         return PercentageResult::UserAttrMissing(attr.to_owned());
     };
-    if eval_log_enabled!() {
+    if log.is_enabled() {
         log.new_ln(Some(
             format!("Evaluating % options based on the User.{attr} attribute:").as_str(),
         ));
     }
-    let (str_attr_val, _) = user_attr.as_str();
-    let mut hash_candidate = String::with_capacity(key.len() + str_attr_val.len());
-    hash_candidate.push_str(key);
-    hash_candidate.push_str(str_attr_val.as_str());
-    let hash = &utils::sha1(hash_candidate.as_str())[..7];
-    if let Ok(num) = i64::from_str_radix(hash, 16) {
-        let scaled = num % 100;
-        if eval_log_enabled!() {
-            log.new_ln(Some(format!("- Computing hash in the [0..99] range from User.{attr} => {scaled} (this value is sticky and consistent across all SDKs)").as_str()));
+    let modulo = match options.bucketing_strategy.unwrap_or_default() {
+        BucketingStrategy::Hash => None,
+        BucketingStrategy::NumericModulo => numeric_modulo(user_attr),
+    };
+    let scaled = if let Some(scaled) = modulo {
+        if log.is_enabled() {
+            log.new_ln(Some(format!("- Computing User.{attr} modulo 100 => {scaled} (this value is sticky but NOT consistent with other SDKs)").as_str()));
         }
-        let mut bucket = 0;
+        Some(scaled)
+    } else {
+        hash_bucket(user_attr, key, hash_scheme, options.hash_seed).inspect(|scaled| {
+            if log.is_enabled() {
+                log.new_ln(Some(format!("- Computing hash in the [0..99] range from User.{attr} => {scaled} (this value is sticky and consistent across all SDKs)").as_str()));
+            }
+        })
+    };
+    if let Some(scaled) = scaled {
+        let mut bucket = 0_i64;
         for (index, opt) in opts.iter().enumerate() {
-            bucket += opt.percentage;
+            bucket = bucket.saturating_add(opt.percentage);
             if scaled < bucket {
-                if eval_log_enabled!() {
+                if log.is_enabled() {
                     log.new_ln(Some(
                         format!(
                             "- Hash value {scaled} selects % option {} ({}%), '{}'.",
@@ -379,29 +678,74 @@ fn eval_percentage(
             }
         }
     }
-    PercentageResult::Fatal("Sum of percentage option percentages is less than 100".to_owned())
+    if log.is_enabled() {
+        log.new_ln(Some(
+            "Skipping % options because the sum of their percentages is less than 100.",
+        ));
+    }
+    PercentageResult::SumUnder100
 }
 
+fn hash_bucket(
+    user_attr: &UserValue,
+    key: &str,
+    hash_scheme: HashScheme,
+    seed: Option<u64>,
+) -> Option<i64> {
+    let (str_attr_val, _) = user_attr.as_str();
+    let mut hash_candidate = String::with_capacity(key.len() + str_attr_val.len());
+    hash_candidate.push_str(key);
+    hash_candidate.push_str(str_attr_val.as_str());
+    if let Some(seed) = seed {
+        hash_candidate.push('#');
+        hash_candidate.push_str(seed.to_string().as_str());
+    }
+    hash_scheme.percentage_bucket(hash_candidate.as_str())
+}
+
+// the attribute modulo 100 when it's a whole number, `None` otherwise
+#[allow(clippy::cast_possible_truncation)]
+fn numeric_modulo(user_attr: &UserValue) -> Option<i64> {
+    match user_attr {
+        UserValue::Int(val) => Some(val.rem_euclid(100)),
+        UserValue::UInt(val) => i64::try_from(val % 100).ok(),
+        UserValue::Float(val) if val.is_finite() && val.fract() == 0.0 => {
+            Some(val.rem_euclid(100.0) as i64)
+        }
+        UserValue::String(val) => val
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .map(|val| val.rem_euclid(100)),
+        _ => None,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn eval_conditions(
     conditions: &[Condition],
     rule_srv_value: Option<&ServedValue>,
+    rule_index: usize,
     key: &str,
     user: Option<&User>,
-    salt: Option<&String>,
+    salt: Salt<'_>,
     ctx_salt: &str,
     log: &mut EvalLogBuilder,
     settings: &HashMap<String, Setting>,
-    cycle_tracker: &mut Vec<String>,
+    prerequisites: &mut PrerequisiteTracker,
+    options: &EvalOptions,
+    prepared: &Prepared,
 ) -> ConditionResult {
-    if eval_log_enabled!() {
+    if log.is_enabled() {
         log.new_ln(Some("- "));
     }
     let mut new_line_before_then = false;
     for (index, condition) in conditions.iter().enumerate() {
+        let started = options.profile.then(Instant::now);
         let mut cond_result = Fatal(
             "Condition isn't a type of user, segment, or prerequisite flag condition".to_owned(),
         );
-        if eval_log_enabled!() {
+        if log.is_enabled() {
             if index == 0 {
                 log.append("IF ").inc_indent();
             } else {
@@ -409,21 +753,23 @@ fn eval_conditions(
             }
         }
         if let Some(user_condition) = condition.user_condition.as_ref() {
-            if eval_log_enabled!() {
+            if log.is_enabled() {
                 log.append(format!("{user_condition}").as_str());
             }
             if let Some(user) = user {
-                cond_result = eval_user_cond(user_condition, key, user, salt, ctx_salt);
+                cond_result =
+                    eval_user_cond(user_condition, key, user, salt, ctx_salt, options, prepared);
             } else {
                 cond_result = NoUser;
             }
             new_line_before_then = conditions.len() > 1;
         } else if let Some(segment_condition) = condition.segment_condition.as_ref() {
-            if eval_log_enabled!() {
+            if log.is_enabled() {
                 log.append(format!("{segment_condition}").as_str());
             }
             if let Some(user) = user {
-                cond_result = eval_segment_cond(segment_condition, key, user, salt, log);
+                cond_result =
+                    eval_segment_cond(segment_condition, key, user, salt, log, options, prepared);
             } else {
                 cond_result = NoUser;
             }
@@ -437,11 +783,22 @@ fn eval_conditions(
                 user,
                 log,
                 settings,
-                cycle_tracker,
+                prerequisites,
+                options,
+                prepared,
             );
             new_line_before_then = true;
         }
-        if eval_log_enabled!() {
+        if let Some(started) = started {
+            prerequisites.timings.push(ConditionTiming {
+                key: key.to_owned(),
+                rule_index,
+                condition_index: index,
+                comparator: condition_comparator(condition),
+                duration: started.elapsed(),
+            });
+        }
+        if log.is_enabled() {
             if conditions.len() > 1 {
                 let res_msg = format!("{}", cond_result.is_match());
                 let conclusion = if cond_result.is_match() {
@@ -458,43 +815,69 @@ fn eval_conditions(
             _ => false,
         };
         if !matched {
-            if eval_log_enabled!() {
+            if log.is_enabled() {
                 log.append_then_clause(new_line_before_then, &cond_result, rule_srv_value);
             }
             return cond_result;
         }
     }
-    if eval_log_enabled!() {
+    if log.is_enabled() {
         log.append_then_clause(new_line_before_then, &Success(true), rule_srv_value);
     }
     Success(true)
 }
 
+fn condition_comparator(condition: &Condition) -> String {
+    if let Some(user_condition) = condition.user_condition.as_ref() {
+        user_condition.comparator.to_string()
+    } else if let Some(segment_condition) = condition.segment_condition.as_ref() {
+        segment_condition.segment_comparator.to_string()
+    } else if let Some(prerequisite_condition) = condition.prerequisite_flag_condition.as_ref() {
+        format!(
+            "PREREQUISITE {}",
+            prerequisite_condition.prerequisite_comparator
+        )
+    } else {
+        String::default()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn eval_prerequisite_cond(
     cond: &PrerequisiteFlagCondition,
     key: &str,
     user: Option<&User>,
     log: &mut EvalLogBuilder,
     settings: &HashMap<String, Setting>,
-    cycle_tracker: &mut Vec<String>,
+    prerequisites: &mut PrerequisiteTracker,
+    options: &EvalOptions,
+    prepared: &Prepared,
 ) -> ConditionResult {
-    if eval_log_enabled!() {
+    if log.is_enabled() {
         log.append(format!("{cond}").as_str());
     }
     let Some(prerequisite) = settings.get(&cond.flag_key) else {
         return Fatal("Prerequisite flag is missing".to_owned());
     };
-    let Some(checked) = cond.flag_value.as_val(&prerequisite.setting_type) else {
+    let Some(checked) = cond.flag_value.as_val(prerequisite.setting_type) else {
         return Fatal(format!(
             "Type mismatch between comparison value '{}' and prerequisite flag '{}'",
             cond.flag_value, cond.flag_key
         ));
     };
 
-    cycle_tracker.push(key.to_owned());
-    if cycle_tracker.contains(&cond.flag_key) {
-        cycle_tracker.push(cond.flag_key.clone());
-        let output = cycle_tracker
+    // the memoized results are only used when the evaluation log is off, as the log must show each evaluation
+    if !log.is_enabled() {
+        if let Some(value) = prerequisites.results.get(&cond.flag_key) {
+            return Success(needs_true_of(cond) == (*value == checked));
+        }
+    }
+
+    prerequisites.path.push(key.to_owned());
+    if prerequisites.path.contains(&cond.flag_key) {
+        prerequisites.path.push(cond.flag_key.clone());
+        let output = prerequisites
+            .path
             .iter()
             .map(|k| format!("'{k}'"))
             .collect::<Vec<String>>()
@@ -502,8 +885,8 @@ fn eval_prerequisite_cond(
         return Fatal(output);
     }
 
-    let needs_true = cond.prerequisite_comparator == PrerequisiteFlagComparator::Eq;
-    if eval_log_enabled!() {
+    let needs_true = needs_true_of(cond);
+    if log.is_enabled() {
         log.new_ln(Some("(")).inc_indent().new_ln(Some(
             format!("Evaluating prerequisite flag '{}':", cond.flag_key).as_str(),
         ));
@@ -515,14 +898,25 @@ fn eval_prerequisite_cond(
         user,
         settings,
         log,
-        cycle_tracker,
+        prerequisites,
+        options,
+        prepared,
     );
-    cycle_tracker.pop();
+    prerequisites.path.pop();
 
     match result {
         Ok(result) => {
             let matched = needs_true == (result.value == checked);
-            if eval_log_enabled!() {
+            let first = prerequisites
+                .results
+                .insert(cond.flag_key.clone(), result.value.clone())
+                .is_none();
+            if first {
+                prerequisites
+                    .evaluated
+                    .push((cond.flag_key.clone(), result.value.clone()));
+            }
+            if log.is_enabled() {
                 let msg = format!("{matched}");
                 log.new_ln(Some(
                     format!("Prerequisite flag evaluation result: '{}'.", result.value).as_str(),
@@ -539,18 +933,24 @@ fn eval_prerequisite_cond(
     }
 }
 
+fn needs_true_of(cond: &PrerequisiteFlagCondition) -> bool {
+    cond.prerequisite_comparator == PrerequisiteFlagComparator::Eq
+}
+
 fn eval_segment_cond(
     cond: &SegmentCondition,
     key: &str,
     user: &User,
-    salt: Option<&String>,
+    salt: Salt<'_>,
     log: &mut EvalLogBuilder,
+    options: &EvalOptions,
+    prepared: &Prepared,
 ) -> ConditionResult {
     let Some(segment) = cond.segment.as_ref() else {
         return Fatal("Segment reference is invalid".to_owned());
     };
 
-    if eval_log_enabled!() {
+    if log.is_enabled() {
         log.new_ln(Some("(")).inc_indent().new_ln(Some(
             format!("Evaluating segment '{}':", segment.name).as_str(),
         ));
@@ -560,7 +960,7 @@ fn eval_segment_cond(
     let needs_true = cond.segment_comparator == IsIn;
 
     for (index, user_condition) in segment.conditions.iter().enumerate() {
-        if eval_log_enabled!() {
+        if log.is_enabled() {
             log.new_ln(Some("- "));
             if index == 0 {
                 log.append("IF ").inc_indent();
@@ -569,8 +969,16 @@ fn eval_segment_cond(
             }
             log.append(format!("{user_condition}").as_str());
         }
-        result = eval_user_cond(user_condition, key, user, salt, segment.name.as_str());
-        if eval_log_enabled!() {
+        result = eval_user_cond(
+            user_condition,
+            key,
+            user,
+            salt,
+            segment.name.as_str(),
+            options,
+            prepared,
+        );
+        if log.is_enabled() {
             let end = if result.is_match() {
                 ""
             } else {
@@ -586,7 +994,7 @@ fn eval_segment_cond(
             break;
         }
     }
-    if eval_log_enabled!() {
+    if log.is_enabled() {
         log.new_ln(Some("Segment evaluation result: "));
         if result.is_success() {
             let msg = if result.is_match() {
@@ -620,9 +1028,18 @@ fn eval_user_cond(
     cond: &UserCondition,
     key: &str,
     user: &User,
-    salt: Option<&String>,
+    salt: Salt<'_>,
     ctx_salt: &str,
+    options: &EvalOptions,
+    prepared: &Prepared,
 ) -> ConditionResult {
+    if let (BeforeDateTime | AfterDateTime, Some(now)) = (&cond.comparator, options.now) {
+        let Some(comp_val) = cond.float_val else {
+            return CompValInvalid(None);
+        };
+        let now = UserValue::DateTime(now).as_timestamp().unwrap_or_default();
+        return eval_date(comp_val, now, &cond.comparator);
+    }
     let Some(user_attr) = user.get(&cond.comp_attr) else {
         return AttrMissing(cond.comp_attr.clone(), format!("{cond}"));
     };
@@ -633,9 +1050,15 @@ fn eval_user_cond(
             };
             let (user_val, converted) = user_attr.as_str();
             if converted {
-                log_conv(cond, key, user_val.as_str());
+                log_conv(cond, key, user_val.as_str(), options);
             }
-            eval_text_eq(comp_val, user_val, &cond.comparator, salt, ctx_salt)
+            eval_text_eq(
+                &normalized_str(comp_val, options),
+                normalized(user_val, options),
+                &cond.comparator,
+                salt,
+                ctx_salt,
+            )
         }
         OneOf | NotOneOf | OneOfHashed | NotOneOfHashed => {
             let Some(comp_val) = cond.string_vec_val.as_ref() else {
@@ -643,9 +1066,26 @@ fn eval_user_cond(
             };
             let (user_val, converted) = user_attr.as_str();
             if converted {
-                log_conv(cond, key, user_val.as_str());
+                log_conv(cond, key, user_val.as_str(), options);
             }
-            eval_one_of(comp_val, user_val, &cond.comparator, salt, ctx_salt)
+            let user_val = normalized(user_val, options);
+            if let Some(PreparedCondition::OneOf(comp_val)) = prepared.get(cond) {
+                return eval_one_of(
+                    |val| comp_val.contains(val),
+                    user_val,
+                    &cond.comparator,
+                    salt,
+                    ctx_salt,
+                );
+            }
+            let comp_val = normalized_vec(comp_val, options);
+            eval_one_of(
+                |val| comp_val.iter().any(|item| item == val),
+                user_val,
+                &cond.comparator,
+                salt,
+                ctx_salt,
+            )
         }
         StartsWithAnyOf
         | StartsWithAnyOfHashed
@@ -660,11 +1100,11 @@ fn eval_user_cond(
             };
             let (user_val, converted) = user_attr.as_str();
             if converted {
-                log_conv(cond, key, user_val.as_str());
+                log_conv(cond, key, user_val.as_str(), options);
             }
             eval_starts_ends_with(
-                comp_val,
-                user_val.as_str(),
+                &normalized_vec(comp_val, options),
+                normalized(user_val, options).as_str(),
                 &cond.comparator,
                 salt,
                 ctx_salt,
@@ -676,35 +1116,54 @@ fn eval_user_cond(
             };
             let (user_val, converted) = user_attr.as_str();
             if converted {
-                log_conv(cond, key, user_val.as_str());
+                log_conv(cond, key, user_val.as_str(), options);
             }
-            eval_contains(comp_val, user_val.as_str(), &cond.comparator)
+            eval_contains(
+                &normalized_vec(comp_val, options),
+                normalized(user_val, options).as_str(),
+                &cond.comparator,
+            )
         }
         OneOfSemver | NotOneOfSemver => {
             let Some(comp_val) = cond.string_vec_val.as_ref() else {
                 return CompValInvalid(None);
             };
-            let Some(user_val) = user_attr.as_semver() else {
+            let Some(user_val) = user_semver(user_attr, cond, key, options) else {
                 return AttrInvalid(
                     format!("'{user_attr}' is not a valid semantic version"),
                     cond.comp_attr.clone(),
                     format!("{cond}"),
                 );
             };
-            eval_semver_is_one_of(comp_val, &user_val, &cond.comparator)
+            let parsed;
+            let comp_vers =
+                if let Some(PreparedCondition::SemverOneOf(comp_vers)) = prepared.get(cond) {
+                    comp_vers.as_deref()
+                } else {
+                    parsed = parse_semvers(comp_val, cond, key, options);
+                    parsed.as_deref()
+                };
+            eval_semver_is_one_of(comp_vers, &user_val, &cond.comparator)
         }
         GreaterSemver | GreaterEqSemver | LessSemver | LessEqSemver => {
             let Some(comp_val) = cond.string_val.as_ref() else {
                 return CompValInvalid(None);
             };
-            let Some(user_val) = user_attr.as_semver() else {
+            let Some(user_val) = user_semver(user_attr, cond, key, options) else {
                 return AttrInvalid(
                     format!("'{user_attr}' is not a valid semantic version"),
                     cond.comp_attr.clone(),
                     format!("{cond}"),
                 );
             };
-            eval_semver_compare(comp_val, &user_val, &cond.comparator)
+            let parsed;
+            let comp_ver = if let Some(PreparedCondition::Semver(comp_ver)) = prepared.get(cond) {
+                comp_ver.as_ref()
+            } else {
+                parsed = parse_semver(comp_val, cond, key, options);
+                parsed.as_ref()
+            };
+            eval_semver_compare(comp_ver, &user_val, &cond.comparator)
         }
         EqNum | NotEqNum | GreaterNum | GreaterEqNum | LessNum | LessEqNum => {
             let Some(comp_val) = cond.float_val else {
@@ -745,16 +1204,69 @@ fn eval_user_cond(
                     format!("{cond}"),
                 );
             };
-            eval_array_contains(comp_val, &user_val, &cond.comparator, salt, ctx_salt)
+            let user_val = user_val
+                .into_iter()
+                .map(|val| normalized(val, options))
+                .collect::<Vec<String>>();
+            eval_array_contains(
+                &normalized_vec(comp_val, options),
+                &user_val,
+                &cond.comparator,
+                salt,
+                ctx_salt,
+            )
         }
     }
 }
 
+// normalizes a user attribute value
+fn normalized(val: String, options: &EvalOptions) -> String {
+    let trimming = options.whitespace_trimming.unwrap_or_default();
+    let val = if trimming == WhitespaceTrimming::None || val.trim() == val {
+        val
+    } else {
+        val.trim().to_owned()
+    };
+    if options.text_normalization.unwrap_or_default() == TextNormalization::Nfc && !is_nfc(&val) {
+        return val.nfc().collect();
+    }
+    val
+}
+
+// normalizes a comparison value
+fn normalized_str<'a>(val: &'a str, options: &EvalOptions) -> Cow<'a, str> {
+    let val = if options.whitespace_trimming.unwrap_or_default() == WhitespaceTrimming::All {
+        val.trim()
+    } else {
+        val
+    };
+    if options.text_normalization.unwrap_or_default() == TextNormalization::Nfc && !is_nfc(val) {
+        return Cow::Owned(val.nfc().collect());
+    }
+    Cow::Borrowed(val)
+}
+
+// normalizes a list of comparison values
+fn normalized_vec<'a>(vals: &'a [String], options: &EvalOptions) -> Cow<'a, [String]> {
+    let unchanged = |val: &String| match normalized_str(val, options) {
+        Cow::Borrowed(normalized) => normalized.len() == val.len(),
+        Cow::Owned(_) => false,
+    };
+    if vals.iter().all(unchanged) {
+        return Cow::Borrowed(vals);
+    }
+    Cow::Owned(
+        vals.iter()
+            .map(|val| normalized_str(val, options).into_owned())
+            .collect(),
+    )
+}
+
 fn eval_text_eq(
     comp_val: &str,
     user_val: String,
     comp: &UserComparator,
-    salt: Option<&String>,
+    salt: Salt<'_>,
     ctx_salt: &str,
 ) -> ConditionResult {
     let needs_true = if comp.is_sensitive() {
@@ -764,19 +1276,19 @@ fn eval_text_eq(
     };
     let mut usr_v = user_val;
     if comp.is_sensitive() {
-        let Some(st) = salt else {
+        let Some(hashed) = salt.hash(usr_v.as_bytes(), ctx_salt) else {
             return Fatal(SALT_MISSING_MSG.to_owned());
         };
-        usr_v = utils::sha256(usr_v.as_str(), st.as_str(), ctx_salt);
+        usr_v = hashed;
     }
     Success((comp_val == usr_v) == needs_true)
 }
 
 fn eval_one_of(
-    comp_val: &[String],
+    is_comp_val: impl Fn(&str) -> bool,
     user_val: String,
     comp: &UserComparator,
-    salt: Option<&String>,
+    salt: Salt<'_>,
     ctx_salt: &str,
 ) -> ConditionResult {
     let needs_true = if comp.is_sensitive() {
@@ -786,24 +1298,19 @@ fn eval_one_of(
     };
     let mut usr_v = user_val;
     if comp.is_sensitive() {
-        let Some(st) = salt else {
+        let Some(hashed) = salt.hash(usr_v.as_bytes(), ctx_salt) else {
             return Fatal(SALT_MISSING_MSG.to_owned());
         };
-        usr_v = utils::sha256(usr_v.as_str(), st.as_str(), ctx_salt);
+        usr_v = hashed;
     }
-    for item in comp_val {
-        if *item == usr_v {
-            return Success(needs_true);
-        }
-    }
-    Success(!needs_true)
+    Success(is_comp_val(&usr_v) == needs_true)
 }
 
 fn eval_starts_ends_with(
     comp_val: &[String],
     user_val: &str,
     comp: &UserComparator,
-    salt: Option<&String>,
+    salt: Salt<'_>,
     ctx_salt: &str,
 ) -> ConditionResult {
     let needs_true = if comp.is_starts_with() {
@@ -818,11 +1325,13 @@ fn eval_starts_ends_with(
         *comp == EndsWithAnyOf
     };
     if comp.is_sensitive() {
-        let user_val_len = user_val.len();
+        // the lengths of the hashed comparison values are in UTF-8 bytes, the slices of the user value
+        // are hashed as bytes even when they split a multi-byte character, like the other SDKs do
+        let user_val = user_val.as_bytes();
         for item in comp_val {
-            let Some(st) = salt else {
+            if salt.is_missing() {
                 return Fatal(SALT_MISSING_MSG.to_owned());
-            };
+            }
             let parts: Vec<&str> = item.split('_').collect();
             if parts.len() < 2 || parts[1].is_empty() {
                 return Fatal(COMP_VAL_INVALID_MSG.to_owned());
@@ -830,24 +1339,19 @@ fn eval_starts_ends_with(
             let Ok(length) = parts[0].trim().parse::<usize>() else {
                 return Fatal(COMP_VAL_INVALID_MSG.to_owned());
             };
-            if length > user_val_len {
+            if length > user_val.len() {
                 continue;
             }
-            if comp.is_starts_with() {
-                if user_val.is_char_boundary(length) {
-                    let chunk = &user_val[..length];
-                    if utils::sha256(chunk, st, ctx_salt) == parts[1] {
-                        return Success(needs_true);
-                    }
-                }
+            let chunk = if comp.is_starts_with() {
+                &user_val[..length]
             } else {
-                let index = user_val_len - length;
-                if user_val.is_char_boundary(index) {
-                    let chunk = &user_val[index..];
-                    if utils::sha256(chunk, st, ctx_salt) == parts[1] {
-                        return Success(needs_true);
-                    }
-                }
+                &user_val[user_val.len() - length..]
+            };
+            if salt
+                .hash(chunk, ctx_salt)
+                .is_some_and(|hashed| hashed == parts[1])
+            {
+                return Success(needs_true);
             }
         }
     } else {
@@ -875,45 +1379,89 @@ fn eval_contains(comp_val: &[String], user_val: &str, comp: &UserComparator) ->
     Success(!needs_true)
 }
 
-fn eval_semver_is_one_of(
+// parses the user attribute value of a semver comparator, see `SemverParsing`
+fn user_semver(
+    user_attr: &UserValue,
+    cond: &UserCondition,
+    key: &str,
+    options: &EvalOptions,
+) -> Option<Version> {
+    if options.semver_parsing.unwrap_or_default() == SemverParsing::Strict {
+        return user_attr.as_semver();
+    }
+    match user_attr {
+        UserValue::String(val) => parse_semver(val, cond, key, options),
+        UserValue::SemVer(val) if !val.build.is_empty() => {
+            let mut version = val.clone();
+            version.build = BuildMetadata::EMPTY;
+            log_semver_leniency(cond, key, &val.to_string(), &version, options);
+            Some(version)
+        }
+        _ => user_attr.as_semver(),
+    }
+}
+
+// parses a semantic version of a semver comparator, see `SemverParsing`
+fn parse_semver(
+    val: &str,
+    cond: &UserCondition,
+    key: &str,
+    options: &EvalOptions,
+) -> Option<Version> {
+    if options.semver_parsing.unwrap_or_default() == SemverParsing::Strict {
+        return utils::parse_semver(val).ok();
+    }
+    let (version, lenient) = utils::parse_semver_lenient(val).ok()?;
+    if lenient {
+        log_semver_leniency(cond, key, val, &version, options);
+    }
+    Some(version)
+}
+
+// parses the comparison values of a semver IS (NOT) ONE OF condition, `None` when one of them is invalid
+fn parse_semvers(
     comp_val: &[String],
+    cond: &UserCondition,
+    key: &str,
+    options: &EvalOptions,
+) -> Option<Vec<Version>> {
+    comp_val
+        .iter()
+        .map(|item| item.trim())
+        .filter(|trimmed| !trimmed.is_empty())
+        .map(|trimmed| parse_semver(trimmed, cond, key, options))
+        .collect()
+}
+
+fn eval_semver_is_one_of(
+    comp_vers: Option<&[Version]>,
     user_val: &Version,
     comp: &UserComparator,
 ) -> ConditionResult {
+    let Some(comp_vers) = comp_vers else {
+        // NOTE: Previous versions of the evaluation algorithm ignored invalid comparison values.
+        // We keep this behavior for backward compatibility.
+        return Success(false);
+    };
     let needs_true = *comp == OneOfSemver;
-    let mut matched = false;
-    for item in comp_val {
-        let trimmed = item.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        let Ok(comp_ver) = utils::parse_semver(trimmed) else {
-            // NOTE: Previous versions of the evaluation algorithm ignored invalid comparison values.
-            // We keep this behavior for backward compatibility.
-            return Success(false);
-        };
-        if user_val.eq(&comp_ver) {
-            matched = true;
-        }
-    }
-    Success(matched == needs_true)
+    Success(comp_vers.contains(user_val) == needs_true)
 }
 
 fn eval_semver_compare(
-    comp_val: &str,
+    comp_ver: Option<&Version>,
     user_val: &Version,
     comp: &UserComparator,
 ) -> ConditionResult {
-    let Ok(comp_ver) = utils::parse_semver(comp_val) else {
+    let Some(comp_ver) = comp_ver else {
         // NOTE: Previous versions of the evaluation algorithm ignored invalid comparison values.
         // We keep this behavior for backward compatibility.
         return Success(false);
     };
     match comp {
-        GreaterSemver => Success(user_val.gt(&comp_ver)),
-        GreaterEqSemver => Success(user_val.ge(&comp_ver)),
-        LessSemver => Success(user_val.lt(&comp_ver)),
-        LessEqSemver => Success(user_val.le(&comp_ver)),
+        GreaterSemver => Success(user_val.gt(comp_ver)),
+        GreaterEqSemver => Success(user_val.ge(comp_ver)),
+        LessSemver => Success(user_val.lt(comp_ver)),
+        LessEqSemver => Success(user_val.le(comp_ver)),
         _ => Fatal("wrong semver comparator".to_owned()),
     }
 }
@@ -942,7 +1490,7 @@ fn eval_array_contains(
     comp_val: &[String],
     user_val: &[String],
     comp: &UserComparator,
-    salt: Option<&String>,
+    salt: Salt<'_>,
     ctx_salt: &str,
 ) -> ConditionResult {
     let needs_true = if comp.is_sensitive() {
@@ -952,10 +1500,9 @@ fn eval_array_contains(
     };
     for user_item in user_val {
         if comp.is_sensitive() {
-            let Some(st) = salt else {
+            let Some(user_hashed) = salt.hash(user_item.as_bytes(), ctx_salt) else {
                 return Fatal(SALT_MISSING_MSG.to_owned());
             };
-            let user_hashed = utils::sha256(user_item.as_str(), st.as_str(), ctx_salt);
             for comp_item in comp_val {
                 if user_hashed == *comp_item {
                     return Success(needs_true);
@@ -971,22 +1518,208 @@ fn eval_array_contains(
     Success(!needs_true)
 }
 
-fn log_user_missing(key: &str) {
-    warn!(event_id = 3001; "Cannot evaluate targeting rules and % options for setting '{key}' (User Object is missing). You should pass a User Object to the evaluation methods like `get_value()`/`get_value_details()` in order to make targeting work properly. Read more: https://configcat.com/docs/advanced/user-object/");
+fn log_user_missing(key: &str, options: &EvalOptions) {
+    filtered_log!(max_log_level(options), Level::Warn, event_id = 3001; "Cannot evaluate targeting rules and % options for setting '{key}' (User Object is missing). You should pass a User Object to the evaluation methods like `get_value()`/`get_value_details()` in order to make targeting work properly. Read more: https://configcat.com/docs/advanced/user-object/");
+}
+
+fn log_attr_missing(key: &str, attr: &str, cond_str: &str, options: &EvalOptions) {
+    filtered_log!(max_log_level(options), Level::Warn, event_id = 3003; "Cannot evaluate condition ({cond_str}) for setting '{key}' (the User.{attr} attribute is missing). You should set the User.{attr} attribute in order to make targeting work properly. Read more: https://configcat.com/docs/advanced/user-object/");
+}
+
+fn log_attr_missing_percentage(key: &str, attr: &str, options: &EvalOptions) {
+    filtered_log!(max_log_level(options), Level::Warn, event_id = 3003; "Cannot evaluate % options for setting '{key}' (the User.{attr} attribute is missing). You should set the User.{attr} attribute in order to make targeting work properly. Read more: https://configcat.com/docs/advanced/user-object/");
+}
+
+fn log_percentage_sum_under_100(key: &str, options: &EvalOptions) -> String {
+    let msg = format!("The sum of the % option percentages of setting '{key}' is less than 100, the % options were skipped.");
+    filtered_log!(max_log_level(options), Level::Warn, event_id = 3006; "{msg} Please check the % options of the setting on the ConfigCat Dashboard.");
+    msg
 }
 
-fn log_attr_missing(key: &str, attr: &str, cond_str: &str) {
-    warn!(event_id = 3003; "Cannot evaluate condition ({cond_str}) for setting '{key}' (the User.{attr} attribute is missing). You should set the User.{attr} attribute in order to make targeting work properly. Read more: https://configcat.com/docs/advanced/user-object/");
+fn log_attr_invalid(key: &str, attr: &str, reason: &str, cond_str: &str, options: &EvalOptions) {
+    filtered_log!(max_log_level(options), Level::Warn, event_id = 3004; "Cannot evaluate condition ({cond_str}) for setting '{key}' ({reason}). Please check the User.{attr} attribute and make sure that its value corresponds to the comparison operator.");
 }
 
-fn log_attr_missing_percentage(key: &str, attr: &str) {
-    warn!(event_id = 3003; "Cannot evaluate % options for setting '{key}' (the User.{attr} attribute is missing). You should set the User.{attr} attribute in order to make targeting work properly. Read more: https://configcat.com/docs/advanced/user-object/");
+fn log_semver_leniency(
+    cond: &UserCondition,
+    key: &str,
+    val: &str,
+    version: &Version,
+    options: &EvalOptions,
+) {
+    filtered_log!(max_log_level(options), Level::Warn, event_id = 3007; "Evaluation of condition ({cond}) for setting '{key}' may not produce the expected result (the value '{val}' is not a valid semantic version, thus it was leniently parsed as '{version}'). Please make sure that using such a value was intended.");
 }
 
-fn log_attr_invalid(key: &str, attr: &str, reason: &str, cond_str: &str) {
-    warn!(event_id = 3004; "Cannot evaluate condition ({cond_str}) for setting '{key}' ({reason}). Please check the User.{attr} attribute and make sure that its value corresponds to the comparison operator.");
+fn log_conv(cond: &UserCondition, key: &str, attr_val: &str, options: &EvalOptions) {
+    filtered_log!(max_log_level(options), Level::Warn, event_id = 3005; "Evaluation of condition ({cond}) for setting '{key}' may not produce the expected result (the User.{} attribute is not a string value, thus it was automatically converted to the string value '{attr_val}'). Please make sure that using a non-string value was intended.", cond.comp_attr);
 }
 
-fn log_conv(cond: &UserCondition, key: &str, attr_val: &str) {
-    warn!(event_id = 3005; "Evaluation of condition ({cond}) for setting '{key}' may not produce the expected result (the User.{} attribute is not a string value, thus it was automatically converted to the string value '{attr_val}'). Please make sure that using a non-string value was intended.", cond.comp_attr);
+#[cfg(test)]
+mod evaluator_tests {
+    use crate::eval::details::EvalOptions;
+    use crate::eval::evaluator::{
+        eval, eval_prepared, eval_setting, Prepared, PrerequisiteTracker,
+    };
+    use crate::eval::log_builder::EvalLogBuilder;
+    use crate::model::config::{entry_from_json, post_process_config};
+    use crate::{evaluate_config, utils, Config, RuleOutcome, User, Value};
+    use chrono::Utc;
+
+    // 'top' depends on 'left' and 'right', which both depend on 'base'
+    static CONFIG_JSON: &str = r#"{"f":{
+        "base":{"t":0,"v":{"b":false},"r":[{"c":[{"u":{"a":"Email","c":2,"l":["@example.com"]}}],"s":{"v":{"b":true}}}]},
+        "left":{"t":0,"v":{"b":false},"r":[{"c":[{"p":{"f":"base","c":0,"v":{"b":true}}}],"s":{"v":{"b":true}}}]},
+        "right":{"t":0,"v":{"b":false},"r":[{"c":[{"p":{"f":"base","c":0,"v":{"b":true}}}],"s":{"v":{"b":true}}}]},
+        "top":{"t":0,"v":{"b":false},"r":[{"c":[{"p":{"f":"left","c":0,"v":{"b":true}}},{"p":{"f":"right","c":0,"v":{"b":true}}}],"s":{"v":{"b":true}}}]}
+    }}"#;
+
+    #[test]
+    fn memoized_prerequisites() {
+        let config: Config = serde_json::from_str(CONFIG_JSON).unwrap();
+        let user = User::new("id").email("john@example.com");
+        let mut prerequisites = PrerequisiteTracker::default();
+        let result = eval_setting(
+            &config.settings["top"],
+            "top",
+            Some(&user),
+            &config.settings,
+            &mut EvalLogBuilder::default(),
+            &mut prerequisites,
+            &EvalOptions::default(),
+            &Prepared::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.value, Value::Bool(true));
+        assert!(prerequisites.path.is_empty());
+        assert_eq!(prerequisites.results.len(), 3);
+        assert_eq!(prerequisites.results["base"], Value::Bool(true));
+    }
+
+    #[test]
+    fn reported_prerequisites() {
+        let config: Config = serde_json::from_str(CONFIG_JSON).unwrap();
+        let user = User::new("id").email("john@example.com");
+
+        let details = evaluate_config(&config, "top", Some(&user));
+        assert_eq!(details.value, Some(Value::Bool(true)));
+        assert_eq!(
+            details.prerequisites,
+            vec![
+                ("base".to_owned(), Value::Bool(true)),
+                ("left".to_owned(), Value::Bool(true)),
+                ("right".to_owned(), Value::Bool(true)),
+            ]
+        );
+
+        let details = evaluate_config(&config, "base", Some(&user));
+        assert!(details.prerequisites.is_empty());
+    }
+
+    #[test]
+    fn percentage_sum_under_100() {
+        let entry = entry_from_json(r#"{"f":{
+            "flag":{"t":0,"v":{"b":false},"p":[{"p":0,"v":{"b":true}}]},
+            "rule":{"t":0,"v":{"b":false},"r":[{"c":[{"u":{"a":"Identifier","c":2,"l":["id"]}}],"p":[{"p":0,"v":{"b":true}}]}]}
+        }}"#, "", Utc::now()).unwrap();
+        let config = &entry.config;
+        let user = User::new("id");
+
+        let details = evaluate_config(config, "flag", Some(&user));
+        assert_eq!(details.value, Some(Value::Bool(false)));
+        assert!(!details.is_default_value);
+        assert!(details.matched_percentage_option.is_none());
+        assert_eq!(
+            details.error.unwrap().message,
+            "The sum of the % option percentages of setting 'flag' is less than 100, the % options were skipped."
+        );
+
+        let details = evaluate_config(config, "rule", Some(&user));
+        assert_eq!(details.value, Some(Value::Bool(false)));
+        assert_eq!(
+            details.rule_outcomes,
+            vec![RuleOutcome::PercentageOptionsIncomplete]
+        );
+        assert!(details.error.is_some());
+    }
+
+    #[test]
+    fn prepared_conditions() {
+        let entry = entry_from_json(r#"{"s":[{"n":"Beta","r":[{"a":"Version","c":9,"s":"1.5.0"}]}],"f":{
+            "base":{"t":0,"v":{"b":false},"r":[{"c":[{"u":{"a":"Email","c":0,"l":["a@example.com"," b@example.com"]}}],"s":{"v":{"b":true}}}]},
+            "semver":{"t":0,"v":{"b":false},"r":[{"c":[{"u":{"a":"Version","c":4,"l":["1.0.0","","2.0.0"]}}],"s":{"v":{"b":true}}}]},
+            "invalid":{"t":0,"v":{"b":false},"r":[{"c":[{"u":{"a":"Version","c":5,"l":["1.0.0","x"]}}],"s":{"v":{"b":true}}}]},
+            "top":{"t":0,"v":{"b":false},"r":[{"c":[{"p":{"f":"base","c":0,"v":{"b":true}}},{"s":{"s":0,"c":0}}],"s":{"v":{"b":true}}}]}
+        }}"#, "", Utc::now()).unwrap();
+        let settings = &entry.config.settings;
+        let options = EvalOptions::default();
+        let users = [
+            User::new("1")
+                .email("a@example.com")
+                .custom("Version", "2.0.0"),
+            User::new("2")
+                .email("b@example.com")
+                .custom("Version", "1.0.0"),
+            User::new("3")
+                .email("c@example.com")
+                .custom("Version", "1.0.0"),
+        ];
+        let expected = [
+            ("base", [true, false, false]),
+            ("semver", [true, true, true]),
+            ("invalid", [false, false, false]),
+            ("top", [true, false, false]),
+        ];
+
+        for (key, values) in expected {
+            let prepared = Prepared::new(&settings[key], key, settings, &options);
+            for (user, value) in users.iter().zip(values) {
+                let result =
+                    eval_prepared(&settings[key], key, user, settings, &options, &prepared);
+                assert_eq!(result.unwrap().value, Value::Bool(value), "{key}");
+                let result = eval(&settings[key], key, Some(user), settings, None, &options);
+                assert_eq!(result.unwrap().value, Value::Bool(value), "{key}");
+            }
+        }
+        // the conditions of the prerequisite flag and the segment are prepared too
+        let prepared = Prepared::new(&settings["top"], "top", settings, &options);
+        assert_eq!(prepared.conditions.len(), 2);
+    }
+
+    #[test]
+    fn sensitive_starts_ends_with_utf8_bytes() {
+        // 'é' and 'ö' are encoded on two bytes, the 1 byte long prefix and suffix split them
+        let user = User::new("id").email("é@ö");
+        let prefix = |len: usize| {
+            let hash = utils::sha256(&"é@ö".as_bytes()[..len], "salt", "flag");
+            format!("{len}_{hash}")
+        };
+        let suffix = |len: usize| {
+            let hash = utils::sha256(&"é@ö".as_bytes()["é@ö".len() - len..], "salt", "flag");
+            format!("{len}_{hash}")
+        };
+        let eval = |comparator: u8, comp_val: String| {
+            let mut config: Config = serde_json::from_str(&format!(
+                r#"{{"p":{{"s":"salt"}},"f":{{"flag":{{"t":0,"v":{{"b":false}},"r":[{{"c":[{{"u":{{"a":"Email","c":{comparator},"l":["{comp_val}"]}}}}],"s":{{"v":{{"b":true}}}}}}]}}}}}}"#
+            ))
+            .unwrap();
+            post_process_config(&mut config);
+            evaluate_config(&config, "flag", Some(&user)).value
+        };
+
+        assert_eq!(eval(22, prefix(1)), Some(Value::Bool(true)));
+        assert_eq!(eval(22, prefix(2)), Some(Value::Bool(true)));
+        assert_eq!(eval(22, prefix(3)), Some(Value::Bool(true)));
+        assert_eq!(eval(23, prefix(1)), Some(Value::Bool(false)));
+        assert_eq!(eval(24, suffix(1)), Some(Value::Bool(true)));
+        assert_eq!(eval(24, suffix(3)), Some(Value::Bool(true)));
+        assert_eq!(eval(25, suffix(1)), Some(Value::Bool(false)));
+        // the 1 byte long prefix isn't the same as the 1 byte long suffix
+        assert_eq!(eval(22, suffix(1)), Some(Value::Bool(false)));
+        // longer than the 5 bytes of the attribute
+        assert_eq!(
+            eval(24, suffix(1).replacen('1', "6", 1)),
+            Some(Value::Bool(false))
+        );
+    }
 }
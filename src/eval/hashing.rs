@@ -0,0 +1,87 @@
+use crate::model::config::Setting;
+use crate::utils;
+
+/// Describes how a config JSON format version hashes the confidential comparison values of the
+/// sensitive comparators and the user attributes that assign users to % options.
+///
+/// A future config JSON format version that changes the hashing gets its own variant here, selected by
+/// [`HashScheme::for_format_version`], so the comparators keep calling the same methods.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashScheme {
+    /// The hashing of the v6 config JSON format: SHA-256 of the value, the config salt and the context salt
+    /// for the sensitive comparators, and the first 7 hex digits of SHA-1 for the % options.
+    #[default]
+    V6,
+}
+
+impl HashScheme {
+    /// Selects the scheme of the config JSON format `version`.
+    /// Every format version known so far uses [`HashScheme::V6`], as do unknown versions.
+    pub fn for_format_version(_version: u8) -> Self {
+        HashScheme::V6
+    }
+
+    /// Hashes the UTF-8 bytes in `value` to compare them with the confidential comparison values of a sensitive comparator.
+    pub fn comparison_hash(self, value: &[u8], salt: &str, ctx_salt: &str) -> String {
+        match self {
+            HashScheme::V6 => utils::sha256(value, salt, ctx_salt),
+        }
+    }
+
+    /// Returns the bucket in the `[0..99]` range that `hash_input` (the key and the user attribute,
+    /// optionally decorated with a seed) assigns the user to.
+    pub fn percentage_bucket(self, hash_input: &str) -> Option<i64> {
+        match self {
+            HashScheme::V6 => {
+                let hash = &utils::sha1(hash_input)[..7];
+                i64::from_str_radix(hash, 16).ok().map(|num| num % 100)
+            }
+        }
+    }
+}
+
+/// The salt of a setting together with the [`HashScheme`] of the config JSON it belongs to,
+/// passed down to the sensitive comparators.
+#[derive(Clone, Copy)]
+pub struct Salt<'a> {
+    scheme: HashScheme,
+    salt: Option<&'a str>,
+}
+
+impl<'a> Salt<'a> {
+    pub fn of(setting: &'a Setting) -> Self {
+        Self {
+            scheme: setting.hash_scheme,
+            salt: setting.salt.as_deref(),
+        }
+    }
+
+    pub fn is_missing(self) -> bool {
+        self.salt.is_none()
+    }
+
+    /// Hashes `value` with the scheme's comparison hash, or returns `None` when the salt is missing.
+    pub fn hash(self, value: &[u8], ctx_salt: &str) -> Option<String> {
+        self.salt
+            .map(|salt| self.scheme.comparison_hash(value, salt, ctx_salt))
+    }
+}
+
+#[cfg(test)]
+mod hashing_tests {
+    use crate::constants::CONFIG_FORMAT_VERSION;
+    use crate::eval::hashing::HashScheme;
+    use crate::utils;
+
+    #[test]
+    fn v6_scheme() {
+        let scheme = HashScheme::for_format_version(CONFIG_FORMAT_VERSION);
+        assert_eq!(scheme, HashScheme::V6);
+        assert_eq!(
+            scheme.comparison_hash(b"test_payload", "salt", "ctx_salt"),
+            utils::sha256(b"test_payload", "salt", "ctx_salt")
+        );
+        let bucket = scheme.percentage_bucket("flagKeyuser-id").unwrap();
+        assert!((0..100).contains(&bucket));
+    }
+}
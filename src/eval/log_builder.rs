@@ -5,12 +5,24 @@ use crate::ServedValue;
 pub struct EvalLogBuilder {
     content: String,
     indent: usize,
+    enabled: bool,
 }
 
 impl EvalLogBuilder {
     const NEW_LINE_CHAR: char = '\n';
     const INDENT_SEQ: &'static str = "  ";
 
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
     pub fn reset_indent(&mut self) -> &mut Self {
         self.indent = 0;
         self
@@ -1,3 +1,6 @@
 pub mod details;
 pub mod evaluator;
+pub mod hashing;
 mod log_builder;
+pub mod shadow;
+pub mod simulation;
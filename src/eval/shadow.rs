@@ -0,0 +1,241 @@
+use crate::client::eval_flag;
+use crate::eval::details::{warning_error, EvalOptions, EvaluationDetails};
+use crate::modes::KeyLookupMode;
+use crate::{Config, User, Value};
+use std::collections::{BTreeSet, HashMap};
+
+/// A difference between the evaluation of a feature flag or setting on two configs for one user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowDiff {
+    /// Key of the feature flag or setting.
+    pub key: String,
+    /// Identifier of the user.
+    pub identifier: String,
+    /// Value evaluated on the current config, or `None` when the evaluation failed.
+    pub current: Option<Value>,
+    /// Value evaluated on the proposed config, or `None` when the evaluation failed.
+    pub proposed: Option<Value>,
+    /// Variation ID evaluated on the current config (if available).
+    pub current_variation_id: Option<String>,
+    /// Variation ID evaluated on the proposed config (if available).
+    pub proposed_variation_id: Option<String>,
+}
+
+/// The evaluations of one user that differ between two configs, yielded by [`impacted_users`].
+#[derive(Debug, Clone)]
+pub struct UserImpact {
+    /// The user.
+    pub user: User,
+    /// The differing evaluations of the user, ordered by key.
+    pub diffs: Vec<ShadowDiff>,
+}
+
+/// Evaluates the feature flag or setting identified by `key` on the given `config`,
+/// independently of any [`crate::Client`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use configcat::{evaluate_config, Config, Setting, User, Value};
+///
+/// let config = Config::default().patched(HashMap::from([
+///     ("flag".to_owned(), Setting::from(&Value::Bool(true))),
+/// ]));
+///
+/// let details = evaluate_config(&config, "flag", Some(&User::new("user-id")));
+/// assert_eq!(details.value, Some(Value::Bool(true)));
+/// ```
+pub fn evaluate_config(
+    config: &Config,
+    key: &str,
+    user: Option<&User>,
+) -> EvaluationDetails<Option<Value>> {
+    evaluate_config_with(config, key, user, &EvalOptions::default())
+}
+
+/// The same as [`evaluate_config`] but evaluates with the given [`EvalOptions`].
+pub(crate) fn evaluate_config_with(
+    config: &Config,
+    key: &str,
+    user: Option<&User>,
+    options: &EvalOptions,
+) -> EvaluationDetails<Option<Value>> {
+    match eval_flag(
+        &config.settings,
+        key,
+        user,
+        None,
+        KeyLookupMode::Exact,
+        options,
+    ) {
+        Ok(eval_result) => EvaluationDetails {
+            value: Some(eval_result.value),
+            key: key.to_owned(),
+            user: user.cloned(),
+            variation_id: eval_result.variation_id,
+            setting_type: Some(eval_result.setting_type),
+            matched_targeting_rule: eval_result.rule,
+            matched_percentage_option: eval_result.option,
+            matched_rule_index: eval_result.rule_index,
+            rule_outcomes: eval_result.rule_outcomes,
+            prerequisites: eval_result.prerequisites,
+            condition_timings: eval_result.condition_timings,
+            error: eval_result.warning.map(warning_error),
+            ..EvaluationDetails::default()
+        },
+        Err(err) => EvaluationDetails::from_err(None, key, user.cloned(), err),
+    }
+}
+
+/// Evaluates every feature flag and setting of both the `current` and the `proposed` config for each of `users`,
+/// and returns the evaluations whose value or variation ID differ.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use configcat::{shadow_diff, Config, Setting, User, Value};
+///
+/// let current = Config::default().patched(HashMap::from([
+///     ("flag".to_owned(), Setting::from(&Value::Bool(false))),
+/// ]));
+/// let proposed = current.patched(HashMap::from([
+///     ("flag".to_owned(), Setting::from(&Value::Bool(true))),
+/// ]));
+///
+/// let diffs = shadow_diff(&current, &proposed, &[User::new("user-id")]);
+/// assert_eq!(diffs.len(), 1);
+/// ```
+pub fn shadow_diff(current: &Config, proposed: &Config, users: &[User]) -> Vec<ShadowDiff> {
+    let keys = current
+        .settings
+        .keys()
+        .chain(proposed.settings.keys())
+        .collect::<BTreeSet<&String>>();
+    users
+        .iter()
+        .flat_map(|user| diff_user(current, proposed, keys.iter().copied(), user))
+        .collect()
+}
+
+/// Returns the keys of the feature flags and settings whose evaluation may differ between the `current` and the
+/// `proposed` config: the added, removed and changed ones, and the ones depending on those through
+/// prerequisite flag conditions (transitively).
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use configcat::{impacted_keys, Config, Setting, Value};
+///
+/// let current = Config::default().patched(HashMap::from([
+///     ("flag".to_owned(), Setting::from(&Value::Bool(false))),
+///     ("other".to_owned(), Setting::from(&Value::Bool(false))),
+/// ]));
+/// let proposed = current.patched(HashMap::from([
+///     ("flag".to_owned(), Setting::from(&Value::Bool(true))),
+/// ]));
+///
+/// assert_eq!(impacted_keys(&current, &proposed).into_iter().collect::<Vec<_>>(), vec!["flag"]);
+/// ```
+pub fn impacted_keys(current: &Config, proposed: &Config) -> BTreeSet<String> {
+    let mut impacted = current
+        .settings
+        .keys()
+        .chain(proposed.settings.keys())
+        .filter(|key| current.settings.get(*key) != proposed.settings.get(*key))
+        .cloned()
+        .collect::<BTreeSet<String>>();
+
+    // the keys of the settings that have a prerequisite flag condition on the given key, in either config
+    let mut dependents = HashMap::<&str, Vec<&str>>::new();
+    for ctx in current.conditions().chain(proposed.conditions()) {
+        if let Some(prerequisite) = ctx.condition.prerequisite_flag_condition.as_ref() {
+            dependents
+                .entry(prerequisite.flag_key.as_str())
+                .or_default()
+                .push(ctx.setting_key);
+        }
+    }
+    let mut pending = impacted.iter().cloned().collect::<Vec<String>>();
+    while let Some(key) = pending.pop() {
+        for dependent in dependents.get(key.as_str()).into_iter().flatten() {
+            if impacted.insert((*dependent).to_owned()) {
+                pending.push((*dependent).to_owned());
+            }
+        }
+    }
+    impacted
+}
+
+/// Evaluates the feature flags and settings returned by [`impacted_keys`] on both the `current` and the `proposed`
+/// config for each of `users`, and lazily yields the users with differing evaluations (value or variation ID).
+///
+/// As only the impacted feature flags and settings are evaluated, this scales to large user sets better than
+/// [`shadow_diff`], e.g. to list the users whose evaluated values a new targeting rule would change.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use configcat::{impacted_users, Config, Setting, User, Value};
+///
+/// let current = Config::default().patched(HashMap::from([
+///     ("flag".to_owned(), Setting::from(&Value::Bool(false))),
+/// ]));
+/// let proposed = current.patched(HashMap::from([
+///     ("flag".to_owned(), Setting::from(&Value::Bool(true))),
+/// ]));
+///
+/// let users = (0..1000).map(|i| User::new(&format!("user-{i}")));
+/// for impact in impacted_users(&current, &proposed, users).take(10) {
+///     println!("{}: {:?}", impact.user[User::IDENTIFIER], impact.diffs);
+/// }
+/// ```
+pub fn impacted_users<'a, I>(
+    current: &'a Config,
+    proposed: &'a Config,
+    users: I,
+) -> impl Iterator<Item = UserImpact> + 'a
+where
+    I: IntoIterator<Item = User>,
+    I::IntoIter: 'a,
+{
+    let keys = impacted_keys(current, proposed);
+    users.into_iter().filter_map(move |user| {
+        let diffs = diff_user(current, proposed, keys.iter(), &user);
+        (!diffs.is_empty()).then_some(UserImpact { user, diffs })
+    })
+}
+
+/// Evaluates `keys` on both configs for `user`, and returns the evaluations whose value or variation ID differ.
+fn diff_user<'k>(
+    current: &Config,
+    proposed: &Config,
+    keys: impl Iterator<Item = &'k String>,
+    user: &User,
+) -> Vec<ShadowDiff> {
+    let mut diffs = Vec::new();
+    for key in keys {
+        let current_details = evaluate_config(current, key, Some(user));
+        let proposed_details = evaluate_config(proposed, key, Some(user));
+        if current_details.value == proposed_details.value
+            && current_details.variation_id == proposed_details.variation_id
+        {
+            continue;
+        }
+        diffs.push(ShadowDiff {
+            key: key.clone(),
+            identifier: user
+                .get(User::IDENTIFIER)
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            current: current_details.value,
+            proposed: proposed_details.value,
+            current_variation_id: current_details.variation_id,
+            proposed_variation_id: proposed_details.variation_id,
+        });
+    }
+    diffs
+}
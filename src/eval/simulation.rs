@@ -0,0 +1,91 @@
+use crate::eval::shadow::evaluate_config_with;
+use crate::{Config, EvalOptions, EvaluationDetails, User, Value};
+
+/// Evaluates feature flags and settings with a seeded percentage option bucketing, for rollout simulations.
+///
+/// The seed is mixed into the hash input of the % options (the setting key and the evaluated user attribute),
+/// so the same seed always puts a user into the same bucket, while a different seed yields an independent
+/// assignment without changing the keys of the settings. Targeting rules, segments and the hashing of
+/// confidential comparison values are not affected.
+///
+/// A simulation never affects the evaluations of [`crate::Client`]s, which use the unseeded bucketing
+/// of the ConfigCat SDKs unless [`EvalOptions::hash_seed`] is passed to them.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{Config, Simulation, User};
+///
+/// let config: Config = serde_json::from_str(r#"{"f":{"rollout":{"t":0,"v":{"b":false},
+///     "p":[{"p":30,"v":{"b":true}},{"p":70,"v":{"b":false}}]}}}"#).unwrap();
+///
+/// let simulation = Simulation::new(42);
+/// let user = User::new("user-id");
+/// let first = simulation.evaluate(&config, "rollout", Some(&user));
+/// let second = simulation.evaluate(&config, "rollout", Some(&user));
+/// assert_eq!(first.value, second.value);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Simulation {
+    seed: u64,
+}
+
+impl Simulation {
+    /// Creates a new [`Simulation`] that buckets users with `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Returns the seed of the simulation.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The same as [`crate::evaluate_config`] but with the % options bucketed with the seed of the simulation.
+    pub fn evaluate(
+        &self,
+        config: &Config,
+        key: &str,
+        user: Option<&User>,
+    ) -> EvaluationDetails<Option<Value>> {
+        let options = EvalOptions {
+            hash_seed: Some(self.seed),
+            ..EvalOptions::default()
+        };
+        evaluate_config_with(config, key, user, &options)
+    }
+}
+
+#[cfg(test)]
+mod simulation_tests {
+    use crate::{evaluate_config, Config, Simulation, User, Value};
+
+    static CONFIG_JSON: &str = r#"{"f":{"rollout":{"t":0,"v":{"b":false},"p":[{"p":50,"v":{"b":true}},{"p":50,"v":{"b":false}}]}}}"#;
+
+    fn values(eval: impl Fn(&User) -> Option<Value>) -> Vec<Option<Value>> {
+        (0..100)
+            .map(|i| eval(&User::new(format!("user{i}").as_str())))
+            .collect()
+    }
+
+    #[test]
+    fn seeded_bucketing() {
+        let config: Config = serde_json::from_str(CONFIG_JSON).unwrap();
+        let production = values(|user| evaluate_config(&config, "rollout", Some(user)).value);
+        let seeded = |seed| {
+            values(|user| {
+                Simulation::new(seed)
+                    .evaluate(&config, "rollout", Some(user))
+                    .value
+            })
+        };
+
+        assert_eq!(seeded(1), seeded(1));
+        assert_ne!(seeded(1), seeded(2));
+        assert_ne!(seeded(1), production);
+        assert_eq!(
+            production,
+            values(|user| evaluate_config(&config, "rollout", Some(user)).value)
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{User, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A feature flag or setting served to a user, passed to the hook set with [`crate::ClientBuilder::on_flag_evaluated`].
+///
+/// Only the successful evaluations of single feature flags are reported, the evaluations that fall back
+/// to the default value and the methods evaluating all feature flags (e.g. [`crate::Client::get_all_values`]) aren't.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use configcat::Client;
+///
+/// let builder = Client::builder("sdk-key")
+///     .exposure_dedup(100_000, Duration::from_secs(30 * 60))
+///     .on_flag_evaluated(|exposure| {
+///         println!("{} got {} of '{}'", exposure.user_id(), exposure.value, exposure.key);
+///     });
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExposureEvent {
+    /// Key of the feature flag or setting.
+    pub key: String,
+    /// The evaluated value.
+    pub value: Value,
+    /// Variation ID of the evaluated value (if available).
+    pub variation_id: Option<String>,
+    /// The User Object used for the evaluation (if available).
+    pub user: Option<User>,
+}
+
+impl ExposureEvent {
+    /// Returns the identifier of the [`ExposureEvent::user`], or an empty text without a user.
+    pub fn user_id(&self) -> String {
+        user_id(self.user.as_ref())
+    }
+}
+
+pub(crate) fn user_id(user: Option<&User>) -> String {
+    user.and_then(|user| user.get(User::IDENTIFIER))
+        .map(ToString::to_string)
+        .unwrap_or_default()
+}
+
+// identifies an exposure: the user's identifier, the key and the variation ID (or value)
+type ExposureKey = (String, String, String);
+
+/// Lets through the first exposure of each user, feature flag and variation within `ttl`,
+/// remembering at most `capacity` exposures (the least recently seen ones are forgotten first).
+pub(crate) struct ExposureDeduper {
+    capacity: usize,
+    ttl: Duration,
+    seen: Mutex<SeenExposures>,
+}
+
+#[derive(Default)]
+struct SeenExposures {
+    // the time of the reported exposure and the recency stamp of each key
+    entries: HashMap<ExposureKey, (Instant, u64)>,
+    // the keys by recency stamp, the oldest first
+    recency: BTreeMap<u64, ExposureKey>,
+    next_stamp: u64,
+}
+
+impl ExposureDeduper {
+    pub(crate) fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            ttl,
+            seen: Mutex::new(SeenExposures::default()),
+        }
+    }
+
+    /// Records the exposure and returns whether it should be reported. Checking and recording happen
+    /// under one lock, so concurrent evaluations report the same exposure once.
+    pub(crate) fn first_exposure(&self, user_id: String, key: &str, variation: String) -> bool {
+        let now = Instant::now();
+        let exposure = (user_id, key.to_owned(), variation);
+        let mut seen = self.seen.lock().unwrap();
+        let stamp = seen.next_stamp;
+        seen.next_stamp += 1;
+        let previous = seen.entries.get(&exposure).copied();
+        if let Some((_, old_stamp)) = previous {
+            seen.recency.remove(&old_stamp);
+        }
+        let (reported_at, first) = match previous {
+            Some((reported_at, _)) if now.duration_since(reported_at) < self.ttl => {
+                (reported_at, false)
+            }
+            _ => (now, true),
+        };
+        seen.recency.insert(stamp, exposure.clone());
+        seen.entries.insert(exposure, (reported_at, stamp));
+        while seen.entries.len() > self.capacity {
+            let Some((_, oldest)) = seen.recency.pop_first() else {
+                break;
+            };
+            seen.entries.remove(&oldest);
+        }
+        first
+    }
+}
+
+#[cfg(test)]
+mod exposure_tests {
+    use crate::exposure::ExposureDeduper;
+    use std::time::Duration;
+
+    fn expose(deduper: &ExposureDeduper, user_id: &str, key: &str, variation: &str) -> bool {
+        deduper.first_exposure(user_id.to_owned(), key, variation.to_owned())
+    }
+
+    #[test]
+    fn dedup() {
+        let deduper = ExposureDeduper::new(10, Duration::from_secs(60));
+
+        assert!(expose(&deduper, "a", "flag", "v1"));
+        assert!(!expose(&deduper, "a", "flag", "v1"));
+        assert!(expose(&deduper, "a", "flag", "v2"));
+        assert!(expose(&deduper, "b", "flag", "v1"));
+        assert!(expose(&deduper, "a", "other", "v1"));
+        assert!(!expose(&deduper, "b", "flag", "v1"));
+    }
+
+    #[test]
+    fn ttl() {
+        let deduper = ExposureDeduper::new(10, Duration::from_millis(50));
+
+        assert!(expose(&deduper, "a", "flag", "v1"));
+        assert!(!expose(&deduper, "a", "flag", "v1"));
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(expose(&deduper, "a", "flag", "v1"));
+        assert!(!expose(&deduper, "a", "flag", "v1"));
+    }
+
+    #[test]
+    fn least_recently_seen_evicted() {
+        let deduper = ExposureDeduper::new(2, Duration::from_secs(60));
+
+        assert!(expose(&deduper, "a", "flag", "v1"));
+        assert!(expose(&deduper, "b", "flag", "v1"));
+        // 'a' becomes the most recently seen, 'b' is evicted by 'c'
+        assert!(!expose(&deduper, "a", "flag", "v1"));
+        assert!(expose(&deduper, "c", "flag", "v1"));
+        assert!(!expose(&deduper, "a", "flag", "v1"));
+        assert!(expose(&deduper, "b", "flag", "v1"));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{User, UserValue};
+use axum_core::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum_core::response::{IntoResponse, Response};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use http::header::{AUTHORIZATION, COOKIE};
+use http::request::Parts;
+use http::StatusCode;
+use serde_json::Value as JsonValue;
+use std::fmt::{Display, Formatter};
+
+/// Describes where a user attribute is read from in a request.
+#[derive(Clone, Debug)]
+pub enum AttributeSource {
+    /// The value of an HTTP header.
+    Header(String),
+    /// The value of a cookie.
+    Cookie(String),
+    /// A claim of the JWT sent in the `Authorization: Bearer` header.
+    ///
+    /// The token's signature is **not** verified, it must be validated by the authentication layer
+    /// that runs before the handler.
+    Claim(String),
+}
+
+/// Configures how [`ConfigCatUser`] builds a [`User`] from the parts of an axum request.
+///
+/// The mapping must be added to the request extensions, e.g. with axum's `Extension` layer.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{AttributeSource, UserMapping};
+///
+/// let mapping = UserMapping::new(AttributeSource::Claim("sub".to_owned()))
+///     .email(AttributeSource::Claim("email".to_owned()))
+///     .country(AttributeSource::Header("CloudFront-Viewer-Country".to_owned()))
+///     .custom("Plan", AttributeSource::Cookie("plan".to_owned()));
+///
+/// // let app = Router::new().route("/", get(handler)).layer(Extension(mapping));
+/// ```
+#[derive(Clone, Debug)]
+pub struct UserMapping {
+    identifier: AttributeSource,
+    email: Option<AttributeSource>,
+    country: Option<AttributeSource>,
+    custom: Vec<(String, AttributeSource)>,
+}
+
+impl UserMapping {
+    /// Creates a new [`UserMapping`] that reads the user's identifier from `identifier`.
+    pub fn new(identifier: AttributeSource) -> Self {
+        Self {
+            identifier,
+            email: None,
+            country: None,
+            custom: Vec::new(),
+        }
+    }
+
+    /// Sets where the email address of the user is read from.
+    pub fn email(mut self, source: AttributeSource) -> Self {
+        self.email = Some(source);
+        self
+    }
+
+    /// Sets where the country of the user is read from.
+    pub fn country(mut self, source: AttributeSource) -> Self {
+        self.country = Some(source);
+        self
+    }
+
+    /// Adds a custom attribute read from `source`.
+    pub fn custom(mut self, key: &str, source: AttributeSource) -> Self {
+        self.custom.push((key.to_owned(), source));
+        self
+    }
+
+    fn user_of(&self, parts: &Parts) -> Option<User> {
+        let claims = bearer_claims(parts);
+        let read = |source: &AttributeSource| read_attribute(parts, claims.as_ref(), source);
+        let mut user = User::new(read(&self.identifier)?.to_string().as_str());
+        if let Some(email) = self.email.as_ref().and_then(read) {
+            user = user.email(email.to_string().as_str());
+        }
+        if let Some(country) = self.country.as_ref().and_then(read) {
+            user = user.country(country.to_string().as_str());
+        }
+        for (key, source) in &self.custom {
+            if let Some(value) = read(source) {
+                user = user.custom(key, value);
+            }
+        }
+        Some(user)
+    }
+}
+
+/// Extracts a [`User`] from the parts of an axum request as configured by the [`UserMapping`]
+/// in the request extensions.
+///
+/// The extraction is rejected when the identifier can't be found in the request. Use `Option<ConfigCatUser>`
+/// for handlers that also serve anonymous requests.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use configcat::{Client, ConfigCatUser};
+///
+/// // with axum: `async fn handler(State(client): State<Arc<Client>>, ConfigCatUser(user): ConfigCatUser)`
+/// async fn handler(client: Arc<Client>, ConfigCatUser(user): ConfigCatUser) -> String {
+///     let enabled = client.get_value("flag-key", false, Some(user)).await;
+///     format!("flag-key: {enabled}")
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConfigCatUser(pub User);
+
+/// The rejection of the [`ConfigCatUser`] extractor.
+#[derive(Debug)]
+pub enum UserRejection {
+    /// There's no [`UserMapping`] in the request extensions.
+    MissingMapping,
+    /// The identifier of the user can't be found in the request.
+    MissingIdentifier,
+}
+
+impl Display for UserRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserRejection::MissingMapping => {
+                f.write_str("No UserMapping was found in the request extensions.")
+            }
+            UserRejection::MissingIdentifier => {
+                f.write_str("The user identifier is missing from the request.")
+            }
+        }
+    }
+}
+
+impl IntoResponse for UserRejection {
+    fn into_response(self) -> Response {
+        let status = match self {
+            UserRejection::MissingMapping => StatusCode::INTERNAL_SERVER_ERROR,
+            UserRejection::MissingIdentifier => StatusCode::UNAUTHORIZED,
+        };
+        (status, self.to_string()).into_response()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ConfigCatUser {
+    type Rejection = UserRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        <Self as OptionalFromRequestParts<S>>::from_request_parts(parts, state)
+            .await?
+            .ok_or(UserRejection::MissingIdentifier)
+    }
+}
+
+impl<S: Send + Sync> OptionalFromRequestParts<S> for ConfigCatUser {
+    type Rejection = UserRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        let mapping = parts
+            .extensions
+            .get::<UserMapping>()
+            .ok_or(UserRejection::MissingMapping)?;
+        Ok(mapping.user_of(parts).map(ConfigCatUser))
+    }
+}
+
+fn read_attribute(
+    parts: &Parts,
+    claims: Option<&JsonValue>,
+    source: &AttributeSource,
+) -> Option<UserValue> {
+    match source {
+        AttributeSource::Header(name) => parts
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(UserValue::from),
+        AttributeSource::Cookie(name) => cookie(parts, name).map(UserValue::from),
+        AttributeSource::Claim(name) => claims.and_then(|claims| claim_value(&claims[name])),
+    }
+}
+
+fn cookie<'a>(parts: &'a Parts, name: &str) -> Option<&'a str> {
+    parts
+        .headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn bearer_claims(parts: &Parts) -> Option<JsonValue> {
+    let token = parts
+        .headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")?;
+    let payload = token.split('.').nth(1)?;
+    let decoded = URL_SAFE_NO_PAD.decode(payload).ok()?;
+    serde_json::from_slice(&decoded).ok()
+}
+
+fn claim_value(claim: &JsonValue) -> Option<UserValue> {
+    match claim {
+        JsonValue::String(val) => Some(UserValue::from(val.as_str())),
+        JsonValue::Bool(val) => Some(UserValue::from(val.to_string().as_str())),
+        JsonValue::Number(val) => val
+            .as_i64()
+            .map(UserValue::Int)
+            .or_else(|| val.as_u64().map(UserValue::UInt))
+            .or_else(|| val.as_f64().map(UserValue::Float)),
+        JsonValue::Array(values) => Some(UserValue::StringVec(
+            values
+                .iter()
+                .filter_map(|val| val.as_str().map(ToOwned::to_owned))
+                .collect(),
+        )),
+        JsonValue::Null | JsonValue::Object(_) => None,
+    }
+}
@@ -1,72 +1,385 @@
+use std::fmt::{Debug, Formatter};
+#[cfg(feature = "network")]
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "network")]
 use chrono::Utc;
-use log::{debug, error, warn};
+use log::{Level, LevelFilter};
+#[cfg(feature = "network")]
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+#[cfg(feature = "network")]
 use reqwest::header::{HeaderMap, ETAG, IF_NONE_MATCH};
 
-use crate::constants::{CONFIG_FILE_NAME, PKG_VERSION, SDK_KEY_PROXY_PREFIX};
+use crate::builder::DataGovernanceHook;
+#[cfg(feature = "network")]
+use crate::constants::{BACKGROUND_PARSE_THRESHOLD, CONFIG_FILE_NAME, PKG_VERSION};
+use crate::constants::{FAILOVER_SWITCH_EVENT_ID, FAILOVER_THRESHOLD, SDK_KEY_PROXY_PREFIX};
 use crate::errors::ClientError;
+#[cfg(not(feature = "network"))]
+use crate::errors::ErrorKind::NetworkUnavailable;
+use crate::errors::ErrorKind::RedirectLoop;
+#[cfg(feature = "network")]
 use crate::errors::ErrorKind::{
     HttpClientInitFailure, HttpRequestFailure, HttpRequestTimeout, InvalidHttpResponseContent,
-    InvalidSdkKey, RedirectLoop, UnexpectedHttpResponse,
+    InvalidSdkKey, UnexpectedHttpResponse,
 };
-use crate::fetch::fetcher::FetchResponse::{Failed, Fetched, NotModified};
-use crate::model::config::{entry_from_json, ConfigEntry};
+#[cfg(feature = "network")]
+use crate::fetch::fetcher::FetchResponse::NotModified;
+use crate::fetch::fetcher::FetchResponse::{Failed, Fetched};
+#[cfg(feature = "test-util")]
+use crate::fetch::mock::{fetch_response, MockFetcher};
+use crate::model::config::ConfigEntry;
+#[cfg(feature = "network")]
+use crate::model::config::{entry_from_json, Error};
 use crate::model::enums::RedirectMode;
 
+#[cfg(feature = "network")]
 const CONFIGCAT_UA_HEADER: &str = "X-ConfigCat-UserAgent";
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
 pub enum FetchResponse {
     Fetched(ConfigEntry),
     NotModified,
     Failed(ClientError, bool),
 }
 
+/// Proxy and address resolution configuration passed through to the internal [`reqwest::Client`].
+#[derive(Clone, Default)]
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
+pub struct ProxySettings {
+    #[cfg(feature = "network")]
+    pub proxies: Vec<reqwest::Proxy>,
+    // disables the proxies configured through the HTTP(S)_PROXY environment variables
+    pub no_proxy: bool,
+    #[cfg(feature = "network")]
+    pub resolver: Option<Arc<dyn Resolve>>,
+    #[cfg(feature = "network")]
+    pub ip_preference: IpPreference,
+    // static addresses per host, used instead of resolving the host
+    #[cfg(feature = "network")]
+    pub resolve_overrides: Vec<(String, Vec<SocketAddr>)>,
+}
+
+impl Debug for ProxySettings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("ProxySettings");
+        #[cfg(feature = "network")]
+        debug
+            .field("proxies", &self.proxies)
+            .field("custom_resolver", &self.resolver.is_some())
+            .field("ip_preference", &self.ip_preference)
+            .field("resolve_overrides", &self.resolve_overrides);
+        debug.field("no_proxy", &self.no_proxy).finish()
+    }
+}
+
+/// Describes which IP address families are used to connect to the remote server providing the config JSON.
+///
+/// The connections are made like in [RFC 8305](https://www.rfc-editor.org/rfc/rfc8305) (Happy Eyeballs):
+/// the addresses of the preferred family are tried first, and the other family is tried in parallel
+/// when the first attempt doesn't succeed quickly.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{Client, IpPreference};
+///
+/// let builder = Client::builder("sdk-key")
+///     .ip_preference(IpPreference::Ipv6Only);
+/// ```
+#[cfg(feature = "network")]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum IpPreference {
+    /// The addresses are tried in the order the resolver returned them.
+    #[default]
+    System,
+    /// The IPv4 addresses are tried first, the IPv6 ones are the fallback.
+    PreferIpv4,
+    /// The IPv6 addresses are tried first, the IPv4 ones are the fallback.
+    PreferIpv6,
+    /// Only the IPv4 addresses are used.
+    Ipv4Only,
+    /// Only the IPv6 addresses are used.
+    Ipv6Only,
+}
+
+#[cfg(feature = "network")]
+impl IpPreference {
+    /// Filters and orders the resolved `addrs` by this preference.
+    fn apply(self, addrs: impl Iterator<Item = SocketAddr>) -> Vec<SocketAddr> {
+        let mut addrs = addrs
+            .filter(|addr| match self {
+                IpPreference::Ipv4Only => addr.is_ipv4(),
+                IpPreference::Ipv6Only => addr.is_ipv6(),
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+        match self {
+            IpPreference::PreferIpv4 => addrs.sort_by_key(SocketAddr::is_ipv6),
+            IpPreference::PreferIpv6 => addrs.sort_by_key(SocketAddr::is_ipv4),
+            _ => {}
+        }
+        addrs
+    }
+}
+
+/// Resolves the host names with the custom resolver (or the system's one) and applies the [`IpPreference`].
+#[cfg(feature = "network")]
+struct PreferenceResolver {
+    resolver: Option<Arc<dyn Resolve>>,
+    preference: IpPreference,
+}
+
+#[cfg(feature = "network")]
+impl Resolve for PreferenceResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_owned();
+        let resolving: Resolving = if let Some(resolver) = &self.resolver {
+            resolver.resolve(name)
+        } else {
+            let host = host.clone();
+            Box::pin(async move {
+                let addrs = tokio::net::lookup_host((host, 0)).await?;
+                Ok(Box::new(addrs) as Addrs)
+            })
+        };
+        let preference = self.preference;
+        Box::pin(async move {
+            let addrs = preference.apply(resolving.await?);
+            if addrs.is_empty() {
+                return Err(format!("No address of '{host}' matches {preference:?}.").into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Describes the outcome of the last HTTP request made to download the config JSON.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FetchStats {
+    /// The HTTP status code of the response, or `None` when no response was received.
+    pub status: Option<u16>,
+    /// The time it took to download the config JSON, including redirects.
+    pub latency: Duration,
+}
+
+/// Describes whether the data governance setting of the client is in sync with the preferences on the ConfigCat Dashboard.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum DataGovernanceStatus {
+    /// No config JSON was downloaded from the ConfigCat CDN yet, or the client uses a custom base URL.
+    #[default]
+    Unknown,
+    /// The data governance setting of the client is in sync with the preferences on the ConfigCat Dashboard.
+    InSync,
+    /// The `.data_governance()` parameter specified at the client initialization is not in sync with the preferences
+    /// on the ConfigCat Dashboard, so the client was redirected to `url`.
+    Mismatch {
+        /// The URL the client was redirected to.
+        url: String,
+    },
+}
+
 pub struct Fetcher {
     is_custom_url: bool,
     fetch_url: Arc<Mutex<String>>,
+    #[cfg(feature = "network")]
     http_client: reqwest::Client,
     sdk_key: String,
+    failover: Mutex<Failover>,
+    last_status: Mutex<Option<u16>>,
+    data_governance: Mutex<DataGovernanceStatus>,
+    data_governance_hook: Option<DataGovernanceHook>,
+    log_level: LevelFilter,
+    startup_timeout: Option<Duration>,
+    // whether a fetch has completed, the startup timeout only applies until then
+    started: AtomicBool,
+    #[cfg(feature = "test-util")]
+    mock: Option<Arc<dyn MockFetcher>>,
+}
+
+#[derive(Default)]
+struct Failover {
+    urls: Vec<String>,
+    cooldown: Duration,
+    // 0 is the primary URL, `n` is `urls[n - 1]`
+    active: usize,
+    consecutive_failures: u32,
+    switched_at: Option<Instant>,
+}
+
+impl Failover {
+    fn current_url(&mut self, log_level: LevelFilter) -> Option<String> {
+        if self.active > 0
+            && self
+                .switched_at
+                .is_some_and(|switched_at| switched_at.elapsed() >= self.cooldown)
+        {
+            filtered_log!(
+                log_level,
+                Level::Debug,
+                "Failover cool-down elapsed, switching back to the primary URL"
+            );
+            self.active = 0;
+            self.consecutive_failures = 0;
+            self.switched_at = None;
+        }
+        if self.active == 0 {
+            None
+        } else {
+            Some(self.urls[self.active - 1].clone())
+        }
+    }
+
+    fn record(&mut self, response: &FetchResponse, log_level: LevelFilter) {
+        if !matches!(response, Failed(_, true)) {
+            self.consecutive_failures = 0;
+            return;
+        }
+        self.consecutive_failures += 1;
+        if self.urls.is_empty() || self.consecutive_failures < FAILOVER_THRESHOLD {
+            return;
+        }
+        self.active = (self.active + 1) % (self.urls.len() + 1);
+        self.consecutive_failures = 0;
+        self.switched_at = if self.active == 0 {
+            None
+        } else {
+            Some(Instant::now())
+        };
+        filtered_log!(log_level, Level::Warn, event_id = FAILOVER_SWITCH_EVENT_ID; "Fetching config JSON failed {FAILOVER_THRESHOLD} times in a row, switching to the next failover URL.");
+    }
 }
 
 impl Fetcher {
+    #[cfg_attr(
+        not(feature = "network"),
+        allow(unused_variables, clippy::unnecessary_wraps)
+    )]
     pub fn new(
         url: &str,
         is_custom: bool,
         sdk_key: &str,
         mode: &str,
         timeout: Duration,
+        proxy: &ProxySettings,
     ) -> Result<Self, ClientError> {
-        let mut headers = HeaderMap::new();
-        if let Ok(ua_header) = format!("ConfigCat-Rust/{mode}-{PKG_VERSION}").parse() {
-            headers.insert(CONFIGCAT_UA_HEADER, ua_header);
+        Ok(Self {
+            sdk_key: sdk_key.to_owned(),
+            fetch_url: Arc::new(Mutex::new(url.to_owned())),
+            is_custom_url: is_custom,
+            #[cfg(feature = "network")]
+            http_client: http_client(mode, timeout, proxy)?,
+            failover: Mutex::new(Failover::default()),
+            last_status: Mutex::new(None),
+            data_governance: Mutex::new(DataGovernanceStatus::Unknown),
+            data_governance_hook: None,
+            log_level: LevelFilter::Trace,
+            startup_timeout: None,
+            started: AtomicBool::new(false),
+            #[cfg(feature = "test-util")]
+            mock: None,
+        })
+    }
+
+    /// Sets the URLs used in order when fetching from the current URL keeps failing transiently.
+    /// The fetcher switches back to the primary URL after `cooldown`.
+    pub fn with_failover(self, urls: Vec<String>, cooldown: Duration) -> Self {
+        {
+            let mut failover = self.failover.lock().unwrap();
+            failover.urls = urls;
+            failover.cooldown = cooldown;
         }
+        self
+    }
+
+    /// Sets the hook called each time the [`DataGovernanceStatus`] changes.
+    pub fn with_data_governance_hook(mut self, hook: Option<DataGovernanceHook>) -> Self {
+        self.data_governance_hook = hook;
+        self
+    }
+
+    /// Sets the [`MockFetcher`] that replaces the HTTP requests.
+    #[cfg(feature = "test-util")]
+    pub fn with_mock(mut self, mock: Option<Arc<dyn MockFetcher>>) -> Self {
+        self.mock = mock;
+        self
+    }
+
+    /// Sets the timeout of the HTTP requests made until the first fetch completes, overriding the
+    /// timeout passed to [`Fetcher::new`] for those requests.
+    pub fn with_startup_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum level of the messages logged by the fetcher.
+    pub fn with_log_level(mut self, log_level: LevelFilter) -> Self {
+        self.log_level = log_level;
+        self
+    }
+
+    pub fn data_governance_status(&self) -> DataGovernanceStatus {
+        self.data_governance.lock().unwrap().clone()
+    }
 
-        let http_client = reqwest::Client::builder()
-            .timeout(timeout)
-            .default_headers(headers)
-            .build();
-
-        match http_client {
-            Ok(client) => Ok(Self {
-                sdk_key: sdk_key.to_owned(),
-                fetch_url: Arc::new(Mutex::new(url.to_owned())),
-                is_custom_url: is_custom,
-                http_client: client,
-            }),
-            Err(err) => Err(ClientError::new(
-                HttpClientInitFailure,
-                format!("Failed to initialize reqwest client: {err}"),
-            )),
+    /// Records a detected mismatch, or the absence of one when the status isn't known yet.
+    fn record_data_governance(&self, mismatch_url: Option<String>) {
+        let new_status = {
+            let mut status = self.data_governance.lock().unwrap();
+            let new_status = match mismatch_url {
+                Some(url) => DataGovernanceStatus::Mismatch { url },
+                None if *status == DataGovernanceStatus::Unknown => DataGovernanceStatus::InSync,
+                None => return,
+            };
+            if *status == new_status {
+                return;
+            }
+            *status = new_status.clone();
+            new_status
+        };
+        if let Some(hook) = self.data_governance_hook.as_ref() {
+            hook(&new_status);
         }
     }
 
+    /// Returns the HTTP status code of the last response, or `None` when the last request failed without a response.
+    pub fn last_status(&self) -> Option<u16> {
+        *self.last_status.lock().unwrap()
+    }
+
     pub async fn fetch(&self, etag: &str) -> FetchResponse {
+        #[cfg(feature = "test-util")]
+        if let Some(mock) = self.mock.as_ref() {
+            let (response, status) = fetch_response(mock.fetch(etag));
+            *self.last_status.lock().unwrap() = status;
+            self.started.store(true, Ordering::SeqCst);
+            return response;
+        }
+        let timeout = self
+            .startup_timeout
+            .filter(|_| !self.started.load(Ordering::SeqCst));
+        let failover_url = self.failover.lock().unwrap().current_url(self.log_level);
+        let response = if let Some(url) = failover_url {
+            self.fetch_http(url.as_str(), etag, timeout).await
+        } else {
+            self.fetch_primary(etag, timeout).await
+        };
+        self.started.store(true, Ordering::SeqCst);
+        self.failover
+            .lock()
+            .unwrap()
+            .record(&response, self.log_level);
+        response
+    }
+
+    async fn fetch_primary(&self, etag: &str, timeout: Option<Duration>) -> FetchResponse {
         for _ in 0..3 {
             let fetch_url = self.fetch_url();
-            let response = self.fetch_http(fetch_url.as_str(), etag).await;
+            let response = self.fetch_http(fetch_url.as_str(), etag, timeout).await;
             match &response {
                 Fetched(entry) => match &entry.config.preferences {
                     Some(pref) => {
@@ -75,8 +388,9 @@ impl Fetcher {
                             .clone()
                             .is_some_and(|pref_url| pref_url == fetch_url)
                         {
+                            self.record_data_governance(None);
                             return response;
-                        };
+                        }
 
                         let redirect = pref.redirect.clone().unwrap_or(RedirectMode::No);
                         if self.is_custom_url
@@ -91,9 +405,11 @@ impl Fetcher {
                         }
 
                         if redirect == RedirectMode::No {
+                            self.record_data_governance(None);
                             return response;
                         } else if redirect == RedirectMode::Should {
-                            warn!(event_id = 3002; "The `.data_governance()` parameter specified at the client initialization is not in sync with the preferences on the ConfigCat Dashboard. Read more: https://configcat.com/docs/advanced/data-governance");
+                            self.record_data_governance(pref.url.clone());
+                            filtered_log!(self.log_level, Level::Warn, event_id = 3002; "The `.data_governance()` parameter specified at the client initialization is not in sync with the preferences on the ConfigCat Dashboard. Read more: https://configcat.com/docs/advanced/data-governance");
                         }
                     }
                     _ => return response,
@@ -102,27 +418,39 @@ impl Fetcher {
             }
         }
         let msg = "Redirection loop encountered while trying to fetch config JSON. Please contact us at https://configcat.com/support".to_owned();
-        error!(event_id = RedirectLoop.as_u8(); "{}", msg);
+        filtered_log!(self.log_level, Level::Error, event_id = RedirectLoop.as_u8(); "{msg}");
         Failed(ClientError::new(RedirectLoop, msg), true)
     }
 
-    async fn fetch_http(&self, url: &str, etag: &str) -> FetchResponse {
+    #[cfg(feature = "network")]
+    async fn fetch_http(&self, url: &str, etag: &str, timeout: Option<Duration>) -> FetchResponse {
         let final_url = format!(
             "{url}/configuration-files/{sdk_key}/{config_json_name}",
-            sdk_key = self.sdk_key,
+            sdk_key = encode_sdk_key(&self.sdk_key),
             config_json_name = CONFIG_FILE_NAME
         );
         let mut builder = self.http_client.get(final_url);
         if !etag.is_empty() {
             builder = builder.header(IF_NONE_MATCH, etag.to_owned());
         }
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
 
         let result = builder.send().await;
+        *self.last_status.lock().unwrap() = result
+            .as_ref()
+            .ok()
+            .map(|response| response.status().as_u16());
 
         match result {
             Ok(response) => match response.status().as_u16() {
                 200 => {
-                    debug!("Fetch was successful: new config fetched");
+                    filtered_log!(
+                        self.log_level,
+                        Level::Debug,
+                        "Fetch was successful: new config fetched"
+                    );
                     let headers = response.headers().clone();
                     let etag = if let Some(header) = headers.get(ETAG) {
                         header.to_str().unwrap_or("")
@@ -132,52 +460,71 @@ impl Fetcher {
                     let body_result = response.text().await;
                     match body_result {
                         Ok(body_str) => {
-                            let parse_result = entry_from_json(body_str.as_str(), etag, Utc::now());
+                            let parse_result = parse_entry(body_str, etag.to_owned()).await;
                             match parse_result {
                                 Ok(entry) => Fetched(entry),
                                 Err(parse_error) => {
                                     let msg = format!("Fetching config JSON was successful but the HTTP response content was invalid. {parse_error}");
-                                    error!(event_id = InvalidHttpResponseContent.as_u8(); "{}", msg);
+                                    filtered_log!(self.log_level, Level::Error, event_id = InvalidHttpResponseContent.as_u8(); "{msg}");
                                     Failed(ClientError::new(InvalidHttpResponseContent, msg), true)
                                 }
                             }
                         }
                         Err(body_error) => {
                             let msg = format!("Fetching config JSON was successful but the HTTP response content was invalid. {body_error}");
-                            error!(event_id = InvalidHttpResponseContent.as_u8(); "{}", msg);
+                            filtered_log!(self.log_level, Level::Error, event_id = InvalidHttpResponseContent.as_u8(); "{msg}");
                             Failed(ClientError::new(InvalidHttpResponseContent, msg), true)
                         }
                     }
                 }
                 304 => {
-                    debug!("Fetch was successful: not modified");
+                    filtered_log!(
+                        self.log_level,
+                        Level::Debug,
+                        "Fetch was successful: not modified"
+                    );
                     NotModified
                 }
                 code @ (404 | 403) => {
                     let msg = format!("Your SDK Key seems to be wrong. You can find the valid SDK Key at https://app.configcat.com/sdkkey. Status code: {code}");
-                    error!(event_id = InvalidSdkKey.as_u8(); "{}", msg);
+                    filtered_log!(self.log_level, Level::Error, event_id = InvalidSdkKey.as_u8(); "{msg}");
                     Failed(ClientError::new(InvalidSdkKey, msg), false)
                 }
                 code => {
                     let msg = format!("Unexpected HTTP response was received while trying to fetch config JSON. Status code: {code}");
-                    error!(event_id = UnexpectedHttpResponse.as_u8(); "{}", msg);
+                    filtered_log!(self.log_level, Level::Error, event_id = UnexpectedHttpResponse.as_u8(); "{msg}");
                     Failed(ClientError::new(UnexpectedHttpResponse, msg), true)
                 }
             },
             Err(error) => {
                 if error.is_timeout() {
                     let msg = "Request timed out while trying to fetch config JSON.".to_owned();
-                    error!(event_id = HttpRequestTimeout.as_u8(); "{}", msg);
+                    filtered_log!(self.log_level, Level::Error, event_id = HttpRequestTimeout.as_u8(); "{msg}");
                     Failed(ClientError::new(HttpRequestTimeout, msg), true)
                 } else {
                     let msg = format!("Unexpected error occurred while trying to fetch config JSON. It is most likely due to a local network issue. Please make sure your application can reach the ConfigCat CDN servers (or your proxy server) over HTTP. {error}");
-                    error!(event_id = HttpRequestFailure.as_u8(); "{}", msg);
+                    filtered_log!(self.log_level, Level::Error, event_id = HttpRequestFailure.as_u8(); "{msg}");
                     Failed(ClientError::new(HttpRequestFailure, msg), true)
                 }
             }
         }
     }
 
+    #[cfg(not(feature = "network"))]
+    #[allow(clippy::unused_async)]
+    async fn fetch_http(
+        &self,
+        _url: &str,
+        _etag: &str,
+        _timeout: Option<Duration>,
+    ) -> FetchResponse {
+        let msg =
+            "The SDK was built without the `network` feature, it cannot download the config JSON."
+                .to_owned();
+        filtered_log!(self.log_level, Level::Error, event_id = NetworkUnavailable.as_u8(); "{msg}");
+        Failed(ClientError::new(NetworkUnavailable, msg), false)
+    }
+
     fn fetch_url(&self) -> String {
         let url = self.fetch_url.lock().unwrap();
         url.to_owned()
@@ -189,16 +536,122 @@ impl Fetcher {
     }
 }
 
+#[cfg(feature = "network")]
+fn http_client(
+    mode: &str,
+    timeout: Duration,
+    proxy: &ProxySettings,
+) -> Result<reqwest::Client, ClientError> {
+    let mut headers = HeaderMap::new();
+    if let Ok(ua_header) = format!("ConfigCat-Rust/{mode}-{PKG_VERSION}").parse() {
+        headers.insert(CONFIGCAT_UA_HEADER, ua_header);
+    }
+
+    let mut http_client = reqwest::Client::builder()
+        .timeout(timeout)
+        .default_headers(headers);
+    if proxy.no_proxy {
+        http_client = http_client.no_proxy();
+    }
+    for p in &proxy.proxies {
+        http_client = http_client.proxy(p.clone());
+    }
+    for (host, addrs) in &proxy.resolve_overrides {
+        http_client = http_client.resolve_to_addrs(host, addrs);
+    }
+    if proxy.resolver.is_some() || proxy.ip_preference != IpPreference::System {
+        http_client = http_client.dns_resolver(Arc::new(PreferenceResolver {
+            resolver: proxy.resolver.clone(),
+            preference: proxy.ip_preference,
+        }));
+    }
+    build_http_client(http_client)
+}
+
+#[cfg(feature = "network")]
+fn build_http_client(builder: reqwest::ClientBuilder) -> Result<reqwest::Client, ClientError> {
+    builder.build().map_err(|err| {
+        ClientError::new(
+            HttpClientInitFailure,
+            format!("Failed to initialize reqwest client: {err}"),
+        )
+    })
+}
+
+// percent-encodes the characters of the SDK key that aren't allowed in a URL path
+// (keys of the ConfigCat Proxy may contain any character)
+#[cfg(feature = "network")]
+fn encode_sdk_key(sdk_key: &str) -> String {
+    let mut encoded = String::with_capacity(sdk_key.len());
+    for byte in sdk_key.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(format!("%{byte:02X}").as_str());
+        }
+    }
+    encoded
+}
+
+#[cfg(feature = "network")]
+async fn parse_entry(body: String, etag: String) -> Result<ConfigEntry, Error> {
+    let fetch_time = Utc::now();
+    if body.len() < BACKGROUND_PARSE_THRESHOLD {
+        return entry_from_json(body.as_str(), etag.as_str(), fetch_time);
+    }
+    // large bodies are parsed on the blocking pool so they don't stall the async worker thread
+    match tokio::task::spawn_blocking(move || {
+        entry_from_json(body.as_str(), etag.as_str(), fetch_time)
+    })
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => Err(Error::Parse(err.to_string())),
+    }
+}
+
 #[cfg(test)]
+#[cfg(feature = "network")]
 mod fetch_tests {
+    use std::net::SocketAddr;
+    use std::sync::Arc;
     use std::time::Duration;
 
+    use reqwest::dns::{Addrs, Name, Resolve, Resolving};
     use reqwest::header::{ETAG, IF_NONE_MATCH};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
     use crate::constants::test_constants::{MOCK_KEY, MOCK_PATH};
-    use crate::constants::PKG_VERSION;
-    use crate::fetch::fetcher::FetchResponse::{Fetched, NotModified};
-    use crate::fetch::fetcher::{FetchResponse, Fetcher, CONFIGCAT_UA_HEADER};
+    use crate::constants::{BACKGROUND_PARSE_THRESHOLD, PKG_VERSION};
+    use crate::errors::ErrorKind;
+    use crate::fetch::fetcher::FetchResponse::{Failed, Fetched, NotModified};
+    use crate::fetch::fetcher::{
+        build_http_client, encode_sdk_key, FetchResponse, Fetcher, IpPreference, ProxySettings,
+        CONFIGCAT_UA_HEADER,
+    };
+
+    #[test]
+    fn http_client_init_failure() {
+        // a header value can't contain a line break
+        let builder = reqwest::Client::builder().user_agent("ConfigCat\n");
+        let err = build_http_client(builder).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::HttpClientInitFailure);
+        assert!(err
+            .message
+            .starts_with("Failed to initialize reqwest client: "));
+    }
+
+    #[test]
+    fn sdk_key_encoding() {
+        assert_eq!(
+            encode_sdk_key("configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/HhOWfwVtZ0mb30i9wi17GQ"),
+            "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/HhOWfwVtZ0mb30i9wi17GQ"
+        );
+        assert_eq!(
+            encode_sdk_key("configcat-proxy/my key?#é"),
+            "configcat-proxy/my%20key%3F%23%C3%A9"
+        );
+    }
 
     #[tokio::test]
     async fn fetch_http() {
@@ -220,12 +673,293 @@ mod fetch_tests {
             MOCK_KEY,
             "mode",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         let response = fetcher.fetch("").await;
         assert!(matches!(response, Fetched(_)));
     }
 
+    #[tokio::test]
+    async fn fetch_http_startup_timeout() {
+        // responds to each request after a delay
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0; 1024];
+                    _ = stream.read(&mut buf).await;
+                    tokio::time::sleep(Duration::from_millis(300)).await;
+                    let body = r#"{"f": {}, "s": []}"#;
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{body}",
+                        body.len()
+                    );
+                    _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        let fetcher = Fetcher::new(
+            format!("http://{addr}").as_str(),
+            false,
+            MOCK_KEY,
+            "mode",
+            Duration::from_secs(30),
+            &ProxySettings {
+                no_proxy: true,
+                ..ProxySettings::default()
+            },
+        )
+        .unwrap()
+        .with_startup_timeout(Some(Duration::from_millis(50)));
+        let response = fetcher.fetch("").await;
+        assert!(matches!(response, Failed(err, true) if err.kind == ErrorKind::HttpRequestTimeout));
+
+        let response = fetcher.fetch("").await;
+        assert!(matches!(response, Fetched(_)));
+    }
+
+    #[tokio::test]
+    async fn fetch_http_through_proxy() {
+        let mut proxy = mockito::Server::new_async().await;
+        let m = proxy
+            .mock("GET", MOCK_PATH)
+            .with_status(200)
+            .with_body(r#"{"f": {}, "s": []}"#)
+            .create_async()
+            .await;
+
+        let fetcher = Fetcher::new(
+            "http://cdn.configcat.invalid",
+            false,
+            MOCK_KEY,
+            "mode",
+            Duration::from_secs(30),
+            &ProxySettings {
+                proxies: vec![reqwest::Proxy::http(proxy.url()).unwrap()],
+                no_proxy: true,
+                ..ProxySettings::default()
+            },
+        )
+        .unwrap();
+        let response = fetcher.fetch("").await;
+        assert!(matches!(response, Fetched(_)));
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn fetch_http_resolve_override() {
+        let mut server = mockito::Server::new_async().await;
+        let m = server
+            .mock("GET", MOCK_PATH)
+            .with_status(200)
+            .with_body(r#"{"f": {}, "s": []}"#)
+            .create_async()
+            .await;
+
+        let addr = server.socket_address();
+        let fetcher = Fetcher::new(
+            format!("http://cdn.configcat.invalid:{}", addr.port()).as_str(),
+            false,
+            MOCK_KEY,
+            "mode",
+            Duration::from_secs(30),
+            &ProxySettings {
+                no_proxy: true,
+                resolve_overrides: vec![("cdn.configcat.invalid".to_owned(), vec![addr])],
+                ..ProxySettings::default()
+            },
+        )
+        .unwrap();
+        let response = fetcher.fetch("").await;
+        assert!(matches!(response, Fetched(_)));
+        m.assert_async().await;
+    }
+
+    struct StaticResolver(SocketAddr);
+
+    impl Resolve for StaticResolver {
+        fn resolve(&self, _name: Name) -> Resolving {
+            let addr = self.0;
+            Box::pin(async move { Ok(Box::new(std::iter::once(addr)) as Addrs) })
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_http_custom_resolver() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", MOCK_PATH)
+            .with_status(200)
+            .with_body(r#"{"f": {}, "s": []}"#)
+            .create_async()
+            .await;
+
+        let addr = server.socket_address();
+        let fetcher = |preference: IpPreference| {
+            Fetcher::new(
+                format!("http://cdn.configcat.invalid:{}", addr.port()).as_str(),
+                false,
+                MOCK_KEY,
+                "mode",
+                Duration::from_secs(30),
+                &ProxySettings {
+                    no_proxy: true,
+                    resolver: Some(Arc::new(StaticResolver(addr))),
+                    ip_preference: preference,
+                    ..ProxySettings::default()
+                },
+            )
+            .unwrap()
+        };
+        let response = fetcher(IpPreference::PreferIpv6).fetch("").await;
+        assert!(matches!(response, Fetched(_)));
+        let response = fetcher(IpPreference::Ipv6Only).fetch("").await;
+        assert!(matches!(response, Failed(_, _)));
+    }
+
+    #[test]
+    fn ip_preference() {
+        let v4 = SocketAddr::from(([192, 0, 2, 1], 0));
+        let v6 = SocketAddr::from(([0x2001, 0xdb8, 0, 0, 0, 0, 0, 1], 0));
+        let addrs = [v6, v4, v6];
+        assert_eq!(IpPreference::System.apply(addrs.into_iter()), [v6, v4, v6]);
+        assert_eq!(
+            IpPreference::PreferIpv4.apply(addrs.into_iter()),
+            [v4, v6, v6]
+        );
+        assert_eq!(
+            IpPreference::PreferIpv6.apply(addrs.into_iter()),
+            [v6, v6, v4]
+        );
+        assert_eq!(IpPreference::Ipv4Only.apply(addrs.into_iter()), [v4]);
+        assert_eq!(IpPreference::Ipv6Only.apply(addrs.into_iter()), [v6, v6]);
+    }
+
+    #[tokio::test]
+    async fn fetch_http_large_body() {
+        let mut server = mockito::Server::new_async().await;
+        let value = "a".repeat(BACKGROUND_PARSE_THRESHOLD);
+        server
+            .mock("GET", MOCK_PATH)
+            .with_status(200)
+            .with_body(format!(
+                r#"{{"f": {{"large": {{"t": 1, "v": {{"s": "{value}"}}}}}}, "s": []}}"#
+            ))
+            .create_async()
+            .await;
+
+        let fetcher = Fetcher::new(
+            server.url().as_str(),
+            false,
+            MOCK_KEY,
+            "mode",
+            Duration::from_secs(30),
+            &ProxySettings::default(),
+        )
+        .unwrap();
+        let response = fetcher.fetch("").await;
+        let Fetched(entry) = response else {
+            panic!("unexpected fetch response: {response:?}");
+        };
+        let setting = entry.config.settings.get("large").unwrap();
+        assert_eq!(
+            setting.value.string_val.as_ref().unwrap().len(),
+            value.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_failover() {
+        let mut primary = mockito::Server::new_async().await;
+        let mut secondary = mockito::Server::new_async().await;
+        let p_mock = primary
+            .mock("GET", MOCK_PATH)
+            .with_status(500)
+            .expect(3)
+            .create_async()
+            .await;
+        let s_mock = secondary
+            .mock("GET", MOCK_PATH)
+            .with_status(200)
+            .with_body(r#"{"f": {}, "s": []}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let fetcher = Fetcher::new(
+            primary.url().as_str(),
+            false,
+            MOCK_KEY,
+            "mode",
+            Duration::from_secs(30),
+            &ProxySettings::default(),
+        )
+        .unwrap()
+        .with_failover(vec![secondary.url()], Duration::from_secs(300));
+
+        for _ in 0..3 {
+            let response = fetcher.fetch("").await;
+            assert!(matches!(response, FetchResponse::Failed(_, true)));
+        }
+        assert!(matches!(fetcher.fetch("").await, Fetched(_)));
+        assert!(matches!(fetcher.fetch("").await, Fetched(_)));
+
+        p_mock.assert_async().await;
+        s_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn fetch_failover_cooldown() {
+        let mut primary = mockito::Server::new_async().await;
+        let mut secondary = mockito::Server::new_async().await;
+        let p_fail = primary
+            .mock("GET", MOCK_PATH)
+            .with_status(500)
+            .expect(3)
+            .create_async()
+            .await;
+        let s_mock = secondary
+            .mock("GET", MOCK_PATH)
+            .with_status(200)
+            .with_body(r#"{"f": {}, "s": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let fetcher = Fetcher::new(
+            primary.url().as_str(),
+            false,
+            MOCK_KEY,
+            "mode",
+            Duration::from_secs(30),
+            &ProxySettings::default(),
+        )
+        .unwrap()
+        .with_failover(vec![secondary.url()], Duration::from_millis(100));
+
+        for _ in 0..3 {
+            fetcher.fetch("").await;
+        }
+        assert!(matches!(fetcher.fetch("").await, Fetched(_)));
+        p_fail.assert_async().await;
+        s_mock.assert_async().await;
+
+        p_fail.remove_async().await;
+        let p_ok = primary
+            .mock("GET", MOCK_PATH)
+            .with_status(200)
+            .with_body(r#"{"f": {}, "s": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(matches!(fetcher.fetch("").await, Fetched(_)));
+        p_ok.assert_async().await;
+    }
+
     #[tokio::test]
     async fn fetch_http_etag() {
         let mut server = mockito::Server::new_async().await;
@@ -251,6 +985,7 @@ mod fetch_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         let response = fetcher.fetch("").await;
@@ -260,7 +995,7 @@ mod fetch_tests {
         match response {
             Fetched(entry) => {
                 etag = entry.etag;
-                assert_eq!("etag1", etag)
+                assert_eq!("etag1", etag);
             }
             _ => panic!(),
         }
@@ -299,6 +1034,7 @@ mod fetch_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         let response = fetcher.fetch("").await;
@@ -351,6 +1087,7 @@ mod fetch_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         let response = fetcher.fetch("").await;
@@ -374,12 +1111,14 @@ mod fetch_tests {
 }
 
 #[cfg(test)]
+#[cfg(feature = "network")]
 mod data_governance_tests {
+    use std::sync::{Arc, Mutex};
     use std::time::Duration;
 
     use crate::constants::test_constants::{MOCK_KEY, MOCK_PATH};
     use crate::constants::SDK_KEY_PROXY_PREFIX;
-    use crate::fetch::fetcher::Fetcher;
+    use crate::fetch::fetcher::{DataGovernanceStatus, Fetcher, ProxySettings};
 
     #[tokio::test]
     async fn stay_on_server() {
@@ -399,6 +1138,7 @@ mod data_governance_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         fetcher.fetch("").await;
@@ -425,6 +1165,7 @@ mod data_governance_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         fetcher.fetch("").await;
@@ -451,6 +1192,7 @@ mod data_governance_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         fetcher.fetch("").await;
@@ -461,6 +1203,8 @@ mod data_governance_tests {
 
     #[tokio::test]
     async fn should_redirect() {
+        let statuses = Arc::new(Mutex::new(Vec::<DataGovernanceStatus>::new()));
+        let hook_statuses = Arc::clone(&statuses);
         let mut global = mockito::Server::new_async().await;
         let mut eu = mockito::Server::new_async().await;
         let g_mock = global
@@ -482,10 +1226,22 @@ mod data_governance_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
-        .unwrap();
+        .unwrap()
+        .with_data_governance_hook(Some(Arc::new(move |status: &DataGovernanceStatus| {
+            hook_statuses.lock().unwrap().push(status.clone());
+        })));
+        assert_eq!(
+            fetcher.data_governance_status(),
+            DataGovernanceStatus::Unknown
+        );
         fetcher.fetch("").await;
 
+        let mismatch = DataGovernanceStatus::Mismatch { url: eu.url() };
+        assert_eq!(fetcher.data_governance_status(), mismatch);
+        assert_eq!(*statuses.lock().unwrap(), vec![mismatch]);
+
         g_mock.assert_async().await;
         eu_mock.assert_async().await;
     }
@@ -513,10 +1269,16 @@ mod data_governance_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         fetcher.fetch("").await;
 
+        assert_eq!(
+            fetcher.data_governance_status(),
+            DataGovernanceStatus::InSync
+        );
+
         g_mock.assert_async().await;
         eu_mock.assert_async().await;
     }
@@ -545,6 +1307,7 @@ mod data_governance_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         fetcher.fetch("").await;
@@ -578,6 +1341,7 @@ mod data_governance_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         fetcher.fetch("").await;
@@ -612,6 +1376,7 @@ mod data_governance_tests {
             MOCK_KEY,
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         fetcher.fetch("").await;
@@ -654,6 +1419,7 @@ mod data_governance_tests {
             format!("{SDK_KEY_PROXY_PREFIX}{MOCK_KEY}").as_str(),
             "",
             Duration::from_secs(30),
+            &ProxySettings::default(),
         )
         .unwrap();
         fetcher.fetch("").await;
@@ -663,11 +1429,12 @@ mod data_governance_tests {
         cu_mock.assert_async().await;
     }
 
+    #[allow(clippy::needless_pass_by_value)]
     fn format_body(url: String, redirect_mode: u8) -> String {
-        return "{ \"p\": { \"u\": \"".to_owned()
+        "{ \"p\": { \"u\": \"".to_owned()
             + url.as_str()
             + "\", \"r\": "
             + redirect_mode.to_string().as_str()
-            + ", \"s\": \"test-salt\" }, \"f\": {}, \"s\":[] }";
+            + ", \"s\": \"test-salt\" }, \"f\": {}, \"s\":[] }"
     }
 }
@@ -0,0 +1,172 @@
+use crate::errors::{ClientError, ErrorKind};
+use crate::fetch::fetcher::FetchResponse;
+use crate::model::config::entry_from_json;
+use chrono::Utc;
+use std::sync::{Mutex, PoisonError};
+
+/// The outcome of a config JSON download returned by a [`MockFetcher`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum MockResponse {
+    /// A new config JSON was downloaded (like a `200 OK` response).
+    Fetched {
+        /// The downloaded config JSON.
+        config_json: String,
+        /// The ETag of the downloaded config JSON.
+        etag: String,
+    },
+    /// The config JSON wasn't modified since the ETag sent with the request (like a `304 Not Modified` response).
+    NotModified,
+    /// The download failed.
+    Failed {
+        /// The kind of the failure, e.g. [`ErrorKind::HttpRequestTimeout`].
+        kind: ErrorKind,
+        /// The text representation of the failure.
+        message: String,
+        /// Whether the failure is temporary. After a non-transient failure (like an invalid SDK Key),
+        /// the SDK doesn't retry the download until the next polling interval even if the held config JSON is expired.
+        transient: bool,
+    },
+}
+
+impl MockResponse {
+    /// Creates a [`MockResponse::Fetched`] response.
+    pub fn fetched(config_json: &str, etag: &str) -> Self {
+        Self::Fetched {
+            config_json: config_json.to_owned(),
+            etag: etag.to_owned(),
+        }
+    }
+
+    /// Creates a transient [`MockResponse::Failed`] response.
+    pub fn failed(kind: ErrorKind, message: &str) -> Self {
+        Self::Failed {
+            kind,
+            message: message.to_owned(),
+            transient: true,
+        }
+    }
+}
+
+/// Replaces the HTTP requests of a [`crate::Client`] built with [`crate::ClientBuilder::mock_fetcher`], so
+/// the polling and caching behavior can be tested without a network connection.
+///
+/// Only available with the `test-util` feature.
+pub trait MockFetcher: Send + Sync {
+    /// Returns the outcome of a config JSON download, `etag` is the ETag of the config JSON held by the client
+    /// (empty when it holds none).
+    fn fetch(&self, etag: &str) -> MockResponse;
+}
+
+/// A [`MockFetcher`] that returns the scripted responses in order, then keeps repeating the last one.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use configcat::{Client, MockResponse, PollingMode, ScriptedFetcher};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let fetcher = Arc::new(ScriptedFetcher::new([
+///         MockResponse::fetched(r#"{"f":{"flag":{"t":0,"v":{"b":true}}}}"#, "etag1"),
+///         MockResponse::NotModified,
+///     ]));
+///     let client = Client::builder("configcat-sdk-1/abcdefghijklmnopqrstuv/abcdefghijklmnopqrstuv")
+///         .polling_mode(PollingMode::Manual)
+///         .mock_fetcher(fetcher.clone())
+///         .build()
+///         .unwrap();
+///
+///     client.refresh().await.unwrap();
+///     client.refresh().await.unwrap();
+///     assert!(client.get_value("flag", false, None).await);
+///     assert_eq!(fetcher.requests(), vec!["".to_owned(), "etag1".to_owned()]);
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ScriptedFetcher {
+    script: Mutex<Script>,
+    requests: Mutex<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+struct Script {
+    responses: Vec<MockResponse>,
+    // the index of the response returned by the next download
+    next: usize,
+}
+
+impl ScriptedFetcher {
+    /// Creates a new [`ScriptedFetcher`] that returns `responses` in order.
+    pub fn new(responses: impl IntoIterator<Item = MockResponse>) -> Self {
+        Self {
+            script: Mutex::new(Script {
+                responses: responses.into_iter().collect(),
+                next: 0,
+            }),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Appends `response` to the scripted responses, it's returned once the ones before it were returned.
+    pub fn push(&self, response: MockResponse) {
+        self.script
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .responses
+            .push(response);
+    }
+
+    /// Returns the ETags sent with the downloads made so far, in order.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl MockFetcher for ScriptedFetcher {
+    fn fetch(&self, etag: &str) -> MockResponse {
+        self.requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(etag.to_owned());
+        let mut script = self.script.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = script.next.min(script.responses.len().saturating_sub(1));
+        script.next = index + 1;
+        script.responses.get(index).cloned().unwrap_or_else(|| {
+            MockResponse::failed(ErrorKind::HttpRequestFailure, "No scripted response left.")
+        })
+    }
+}
+
+/// Converts `response` to the outcome of a download and the HTTP status code it stands for.
+pub(crate) fn fetch_response(response: MockResponse) -> (FetchResponse, Option<u16>) {
+    match response {
+        MockResponse::Fetched { config_json, etag } => {
+            match entry_from_json(config_json.as_str(), etag.as_str(), Utc::now()) {
+                Ok(entry) => (FetchResponse::Fetched(entry), Some(200)),
+                Err(err) => (
+                    FetchResponse::Failed(
+                        ClientError::new(
+                            ErrorKind::InvalidHttpResponseContent,
+                            format!("Fetching config JSON was successful but the HTTP response content was invalid. {err}"),
+                        ),
+                        true,
+                    ),
+                    Some(200),
+                ),
+            }
+        }
+        MockResponse::NotModified => (FetchResponse::NotModified, Some(304)),
+        MockResponse::Failed {
+            kind,
+            message,
+            transient,
+        } => (
+            FetchResponse::Failed(ClientError::new(kind, message), transient),
+            None,
+        ),
+    }
+}
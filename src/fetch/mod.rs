@@ -1,2 +1,4 @@
 pub mod fetcher;
+#[cfg(feature = "test-util")]
+pub mod mock;
 pub mod service;
@@ -1,40 +1,233 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::sync::Once;
-use std::time::Duration;
-
-use chrono::{DateTime, Utc};
-use log::warn;
-use tokio::sync::Semaphore;
+use std::sync::{Once, OnceLock};
+use std::time::{Duration, Instant};
+
+use arc_swap::ArcSwap;
+use chrono::{DateTime, TimeDelta, Utc};
+use log::Level;
+use serde::Serialize;
+use tokio::sync::{broadcast, watch, Semaphore};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
 use tokio_util::sync::CancellationToken;
 
 use crate::builder::Options;
-use crate::constants::{CONFIG_FILE_NAME, SERIALIZATION_FORMAT_VERSION};
-use crate::errors::ClientError;
-use crate::fetch::fetcher::{FetchResponse, Fetcher};
-use crate::model::config::{entry_from_cached_json, process_overrides, Config, ConfigEntry};
-use crate::model::enums::DataGovernance;
-use crate::modes::PollingMode;
-use crate::r#override::OptionalOverrides;
-use crate::utils::sha1;
+use crate::constants::{
+    CONFIG_FILE_NAME, EARLY_REFRESH_DEFAULT_DELTA, EVENT_CHANNEL_CAPACITY,
+    LEGACY_SERIALIZATION_FORMAT_VERSIONS, PKG_VERSION, SERIALIZATION_FORMAT_VERSION,
+};
+use crate::constants::{
+    LEGACY_CACHE_MIGRATION_FAILED_EVENT_ID, OVERRIDES_REAPPLY_DEFERRED_EVENT_ID,
+    OVERRIDES_REAPPLY_FAILED_EVENT_ID, PRELOADED_CACHE_ENTRY_INVALID_EVENT_ID,
+};
+use crate::errors::{ClientError, ErrorKind};
+use crate::fetch::fetcher::{DataGovernanceStatus, FetchResponse, FetchStats, Fetcher};
+use crate::model::config::{
+    entry_from_cached_json, entry_from_json, log_percentage_options_issues, migrate_cache_str,
+    process_overrides, Config, ConfigEntry,
+};
+use crate::model::enums::{ConfigSource, DataGovernance};
+use crate::modes::{
+    InitBehavior, KeyLookupMode, LogArea, MemoryMode, NotReadyBehavior, PollingMode,
+};
+use crate::r#override::{FlagOverrides, OptionalOverrides};
+use crate::usage::UsageTracker;
+use crate::utils::{is_offline_by_env, random_unit, sha1, spawn_named};
 use crate::ClientCacheState::{
     HasCachedFlagDataOnly, HasLocalOverrideFlagDataOnly, HasUpToDateFlagData, NoFlagData,
 };
-use crate::{ClientCacheState, OverrideBehavior};
+use crate::{CacheEntry, ClientCacheState, OverrideBehavior, OverrideReport, UsageReport};
 
 pub enum ServiceResult {
     Ok(ConfigResult),
     Err(ClientError, ConfigResult),
 }
 
+/// The result of [`crate::Client::wait_for_ready`], describing the config JSON the [`crate::Client`] was initialized with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadyReport {
+    /// The state of the client's config cache.
+    pub state: ClientCacheState,
+    /// The time (in millisecond precision) when the config JSON was downloaded, or `None` when there's
+    /// no config JSON or the client runs with local-only flag overrides.
+    pub fetch_time: Option<DateTime<Utc>>,
+    /// The time elapsed from the creation of the client until its initialization completed.
+    pub init_duration: Duration,
+    /// Where the config JSON was obtained from.
+    pub source: ConfigSource,
+}
+
+/// Counts the outcomes of the config JSON downloads of a [`crate::Client`], e.g. to show how often the config
+/// JSON actually changes.
+///
+/// Returned by [`crate::Client::fetch_counters`] and passed to the hook set with
+/// [`crate::ClientBuilder::on_fetch_completed`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct FetchCounters {
+    /// The number of downloads that returned a new config JSON.
+    pub fetched: u64,
+    /// The number of downloads where the ConfigCat CDN responded that the config JSON wasn't modified.
+    pub not_modified: u64,
+    /// The number of failed downloads.
+    pub failed: u64,
+    /// A local revision number that starts at 0 and increases each time a new config JSON version (one with a different ETag or content) is applied,
+    /// regardless of whether it was downloaded by this client or read from the [`crate::ConfigCache`].
+    pub revision: u64,
+}
+
+/// Describes how [`crate::Client::refresh`] and [`crate::Client::refresh_if_older`] obtained the config JSON.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RefreshOutcome {
+    /// The config JSON was downloaded, or the server responded that the cached one is still up-to-date.
+    Fetched,
+    /// The config JSON held in memory wasn't older than the given age, so no download was made.
+    Skipped,
+    /// The config JSON read from the [`crate::ConfigCache`] wasn't older than the given age
+    /// (e.g. another instance sharing the cache has refreshed it), so no download was made.
+    ServedFromCache,
+    /// Another [`crate::Client::refresh`] call was already downloading the config JSON, so this call waited
+    /// for that download instead of making one.
+    Coalesced,
+    /// The client is switched to offline mode with [`crate::Client::offline`], so no download was made.
+    SkippedOffline,
+}
+
+/// Describes whether a [`crate::Client`] initiates HTTP requests to download the config JSON.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClientMode {
+    /// The client downloads the config JSON.
+    Online,
+    /// The client doesn't initiate HTTP requests.
+    Offline,
+}
+
+/// An event reported by a [`crate::Client`] to the receivers returned by [`crate::Client::events`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClientEvent {
+    /// The client was switched to the given mode with [`crate::Client::online`] / [`crate::Client::offline`].
+    ModeChanged(ClientMode),
+    /// A refresh initiated by [`crate::Client::refresh`] or [`crate::Client::refresh_if_older`] completed.
+    RefreshCompleted(Result<RefreshOutcome, ClientError>),
+    /// The circuit breaker set with [`crate::ClientBuilder::circuit_breaker`] changed its state.
+    CircuitStateChanged(CircuitState),
+    /// A config JSON with the given ETag was downloaded but not applied,
+    /// because the config is pinned to other ETags with [`crate::Client::pin_config`].
+    UpdateWithheld(String),
+}
+
+/// The state of the circuit breaker set with [`crate::ClientBuilder::circuit_breaker`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CircuitState {
+    /// Config JSON downloads are made as usual.
+    Closed,
+    /// Downloading the config JSON failed too many times in a row, downloads are skipped until the cool-down elapses.
+    Open,
+    /// The cool-down elapsed, the next download probes whether the server is reachable again.
+    /// The circuit closes when it succeeds and opens again when it fails.
+    HalfOpen,
+}
+
+/// The ETags set with [`ConfigService::pin_config`]. While there's any, config JSON versions with other ETags
+/// don't replace the current one.
+#[derive(Default)]
+struct PinnedEtags(std::sync::Mutex<Vec<String>>);
+
+impl PinnedEtags {
+    fn withholds(&self, etag: &str) -> bool {
+        let pinned = self.0.lock().unwrap();
+        !pinned.is_empty() && !pinned.iter().any(|pinned| pinned == etag)
+    }
+}
+
+struct CircuitBreaker {
+    failure_threshold: u32,
+    cool_down: Duration,
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn new(failure_threshold: u32, cool_down: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cool_down,
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+        }
+    }
+
+    /// Returns the remaining cool-down when the download has to be skipped, otherwise the new state
+    /// when the circuit just became half-open.
+    fn before_fetch(&mut self) -> Result<Option<CircuitState>, Duration> {
+        if self.state != CircuitState::Open {
+            return Ok(None);
+        }
+        let elapsed = self.opened_at.map_or(self.cool_down, |at| at.elapsed());
+        if let Some(remaining) = self
+            .cool_down
+            .checked_sub(elapsed)
+            .filter(|rem| !rem.is_zero())
+        {
+            return Err(remaining);
+        }
+        self.state = CircuitState::HalfOpen;
+        Ok(Some(self.state))
+    }
+
+    /// Records the outcome of a download and returns the new state when it changed.
+    fn record(&mut self, response: &FetchResponse) -> Option<CircuitState> {
+        let new_state = if matches!(response, FetchResponse::Failed(_, true)) {
+            self.consecutive_failures += 1;
+            if self.state == CircuitState::HalfOpen
+                || self.consecutive_failures >= self.failure_threshold
+            {
+                self.opened_at = Some(Instant::now());
+                CircuitState::Open
+            } else {
+                self.state
+            }
+        } else {
+            self.consecutive_failures = 0;
+            self.opened_at = None;
+            CircuitState::Closed
+        };
+        if new_state == self.state {
+            return None;
+        }
+        self.state = new_state;
+        Some(new_state)
+    }
+}
+
 pub struct ConfigResult {
     config: Arc<Config>,
     fetch_time: DateTime<Utc>,
+    etag: String,
+    last_fetch: Option<FetchStats>,
+    outcome: RefreshOutcome,
 }
 
 impl ConfigResult {
-    fn new(config: Arc<Config>, fetch_time: DateTime<Utc>) -> Self {
-        Self { config, fetch_time }
+    fn new(config: Arc<Config>, fetch_time: DateTime<Utc>, etag: String) -> Self {
+        Self {
+            config,
+            fetch_time,
+            etag,
+            last_fetch: None,
+            outcome: RefreshOutcome::Skipped,
+        }
+    }
+
+    fn with_outcome(mut self, outcome: RefreshOutcome) -> Self {
+        self.outcome = outcome;
+        self
+    }
+
+    fn from_entry(entry: &ConfigEntry) -> Self {
+        Self::new(entry.config.clone(), entry.fetch_time, entry.etag.clone())
     }
 
     pub fn config(&self) -> &Arc<Config> {
@@ -44,25 +237,199 @@ impl ConfigResult {
     pub fn fetch_time(&self) -> &DateTime<Utc> {
         &self.fetch_time
     }
+
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    pub fn last_fetch(&self) -> Option<FetchStats> {
+        self.last_fetch
+    }
+
+    /// The time elapsed since the config was fetched, or `None` when the fetch time is in the future.
+    pub fn config_age(&self) -> Option<Duration> {
+        (Utc::now() - self.fetch_time).to_std().ok()
+    }
 }
 
 struct ServiceState {
     fetcher: Fetcher,
     cached_entry: Arc<tokio::sync::Mutex<ConfigEntry>>,
     cache_key: String,
+    legacy_cache_keys: Vec<String>,
+    legacy_cache_checked: AtomicBool,
     offline: AtomicBool,
     initialized: AtomicBool,
+    implicit_refresh_done: AtomicBool,
     init: Once,
     init_wait: Semaphore,
+    cache_state: watch::Sender<ClientCacheState>,
+    last_fetch: std::sync::Mutex<Option<FetchStats>>,
+    // a config JSON downloaded during a freeze window, applied when the window ends
+    deferred_entry: std::sync::Mutex<Option<ConfigEntry>>,
+    freeze_bypassed: AtomicBool,
+    pinned_etags: PinnedEtags,
+    // the current interval of AutoPoll, the polling loop restarts when it changes
+    poll_interval: watch::Sender<Option<Duration>>,
+    override_report: std::sync::Mutex<Option<Arc<OverrideReport>>>,
+    // the number of completed AutoPoll iterations
+    poll_ticks: watch::Sender<u64>,
+    // the last config result published for synchronous reads, never waits for the entry lock
+    snapshot: ArcSwap<ConfigResult>,
+    created: Instant,
+    // the time elapsed from `created` until the initialization completed
+    init_duration: OnceLock<Duration>,
+    fetch_counters: std::sync::Mutex<FetchCounters>,
+    // the ETag and content hash of the config JSON that `FetchCounters::revision` was last increased for
+    revision_version: std::sync::Mutex<Option<(String, u64)>>,
+    events: broadcast::Sender<ClientEvent>,
+    circuit_breaker: Option<std::sync::Mutex<CircuitBreaker>>,
+    usage: Option<Arc<UsageTracker>>,
+    // held while a `refresh` call downloads, the calls arriving meanwhile get its result
+    refresh_lock: tokio::sync::Mutex<()>,
+    // the number of completed `refresh` downloads and the result of the last one
+    last_refresh: std::sync::Mutex<(u64, Option<Result<RefreshOutcome, ClientError>>)>,
 }
 
 impl ServiceState {
     fn initialized(&self) {
         self.init.call_once(|| {
+            _ = self.init_duration.set(self.created.elapsed());
             self.initialized.store(true, Ordering::SeqCst);
             self.init_wait.add_permits(1);
         });
     }
+
+    fn update_cache_state(&self, options: &Options, entry: &ConfigEntry) {
+        let new_state = cache_state_of(options, self.poll_interval(), entry);
+        let changed = self.cache_state.send_if_modified(|state| {
+            if *state == new_state {
+                return false;
+            }
+            *state = new_state;
+            true
+        });
+        if changed {
+            if let Some(hook) = options.cache_state_hook() {
+                hook(new_state);
+            }
+        }
+    }
+
+    fn publish_snapshot(&self, entry: &ConfigEntry) {
+        let current = self.snapshot.load();
+        if Arc::ptr_eq(&current.config, &entry.config) && current.fetch_time == entry.fetch_time {
+            return;
+        }
+        self.snapshot
+            .store(Arc::new(ConfigResult::from_entry(entry)));
+    }
+
+    fn count_fetch(&self, response: &FetchResponse) {
+        let mut counters = self.fetch_counters.lock().unwrap();
+        match response {
+            FetchResponse::Fetched(_) => counters.fetched += 1,
+            FetchResponse::NotModified => counters.not_modified += 1,
+            FetchResponse::Failed(..) => counters.failed += 1,
+        }
+    }
+
+    fn update_revision(&self, options: &Options, entry: &ConfigEntry) {
+        if entry.is_empty() {
+            return;
+        }
+        {
+            let mut version = self.revision_version.lock().unwrap();
+            if version
+                .as_ref()
+                .is_some_and(|(etag, hash)| *etag == entry.etag && *hash == entry.content_hash)
+            {
+                return;
+            }
+            *version = Some((entry.etag.clone(), entry.content_hash));
+            self.fetch_counters.lock().unwrap().revision += 1;
+        }
+        // each config JSON version is reported once per pool, by its primary client
+        if !options.pooled() {
+            check_sdk_version(options, &entry.config);
+            log_percentage_options_issues(&entry.config, options.log_level(LogArea::Fetch));
+        }
+    }
+
+    fn fetch_counters(&self) -> FetchCounters {
+        *self.fetch_counters.lock().unwrap()
+    }
+
+    fn circuit_changed(&self, options: &Options, new_state: Option<CircuitState>) {
+        let Some(new_state) = new_state else {
+            return;
+        };
+        if new_state == CircuitState::Open {
+            filtered_log!(options.log_level(LogArea::Fetch), Level::Warn, "Fetching config JSON failed too many times in a row, downloads are paused for the circuit breaker cool-down.");
+        }
+        if let Some(hook) = options.circuit_hook() {
+            hook(new_state);
+        }
+        // there may be no receivers, which is fine
+        _ = self
+            .events
+            .send(ClientEvent::CircuitStateChanged(new_state));
+    }
+
+    fn update_override_report(&self, options: &Options, entry: &ConfigEntry) {
+        let Some(report) = entry.override_report.as_ref() else {
+            return;
+        };
+        {
+            let mut current = self.override_report.lock().unwrap();
+            if current.as_ref().is_some_and(|cur| Arc::ptr_eq(cur, report)) {
+                return;
+            }
+            *current = Some(Arc::clone(report));
+        }
+        if let Some(hook) = options.override_report_hook() {
+            hook(report);
+        }
+    }
+
+    fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::SeqCst) || is_offline_by_env()
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        *self.poll_interval.borrow()
+    }
+
+    fn is_frozen(&self, options: &Options) -> bool {
+        !self.freeze_bypassed.load(Ordering::SeqCst)
+            && options
+                .freeze_schedule()
+                .is_some_and(|schedule| schedule.is_frozen(Utc::now()))
+    }
+
+    fn usage_report(&self, options: &Options, flush: bool) -> Option<UsageReport> {
+        let usage = self.usage.as_ref()?;
+        let snapshot = self.snapshot.load();
+        let keys = snapshot
+            .config()
+            .settings
+            .keys()
+            .filter_map(|key| match options.key_prefix() {
+                Some(prefix) => key.strip_prefix(prefix),
+                None => Some(key.as_str()),
+            });
+        Some(usage.report(
+            keys,
+            options.key_lookup_mode() == KeyLookupMode::CaseInsensitive,
+            flush,
+        ))
+    }
+
+    async fn wait_for_initialized(&self) {
+        if !self.initialized.load(Ordering::SeqCst) {
+            _ = self.init_wait.acquire().await;
+        }
+    }
 }
 
 pub struct ConfigService {
@@ -70,6 +437,8 @@ pub struct ConfigService {
     options: Arc<Options>,
     cancellation_token: CancellationToken,
     close: Once,
+    // the handles of the background tasks, awaited by `shutdown`
+    tasks: std::sync::Mutex<Vec<JoinHandle<()>>>,
 }
 
 impl ConfigService {
@@ -77,6 +446,20 @@ impl ConfigService {
     const EU_CDN_URL: &'static str = "https://cdn-eu.configcat.com";
 
     pub fn new(opts: Arc<Options>) -> Result<Self, ClientError> {
+        let usage = opts.track_usage().then(|| Arc::new(UsageTracker::new()));
+        Self::with_usage(opts, usage)
+    }
+
+    /// Creates the service of a pooled client that records the evaluated keys into the usage tracker
+    /// of the pool's `primary` service, so the pool delivers one usage report per period.
+    pub fn pooled(opts: Arc<Options>, primary: &ConfigService) -> Result<Self, ClientError> {
+        Self::with_usage(opts, primary.state.usage.clone())
+    }
+
+    fn with_usage(
+        opts: Arc<Options>,
+        usage: Option<Arc<UsageTracker>>,
+    ) -> Result<Self, ClientError> {
         let url = if let Some(base_url) = opts.base_url() {
             base_url.as_str()
         } else {
@@ -91,53 +474,167 @@ impl ConfigService {
             opts.sdk_key(),
             opts.polling_mode().mode_identifier(),
             *opts.http_timeout(),
+            opts.proxy(),
         ) {
             Ok(fetcher) => {
+                let fetcher = fetcher
+                    .with_failover(opts.failover_urls().to_vec(), opts.failover_cooldown())
+                    .with_data_governance_hook(opts.data_governance_hook().cloned())
+                    .with_startup_timeout(opts.startup_http_timeout())
+                    .with_log_level(opts.log_level(LogArea::Fetch));
+                #[cfg(feature = "test-util")]
+                let fetcher = fetcher.with_mock(opts.mock_fetcher().cloned());
+                let preloaded = preloaded_entry(&opts);
+                let preloaded_version = (!preloaded.is_empty())
+                    .then(|| (preloaded.etag.clone(), preloaded.content_hash));
                 let service = Self {
                     state: Arc::new(ServiceState {
-                        cache_key: sha1(
-                            format!(
-                                "{}_{CONFIG_FILE_NAME}_{SERIALIZATION_FORMAT_VERSION}",
-                                opts.sdk_key()
-                            )
-                            .as_str(),
-                        ),
+                        cache_key: cache_key(opts.sdk_key(), SERIALIZATION_FORMAT_VERSION),
+                        legacy_cache_keys: LEGACY_SERIALIZATION_FORMAT_VERSIONS
+                            .iter()
+                            .map(|version| cache_key(opts.sdk_key(), version))
+                            .collect(),
+                        legacy_cache_checked: AtomicBool::new(false),
                         fetcher,
                         offline: AtomicBool::new(opts.offline()),
                         initialized: AtomicBool::new(false),
+                        implicit_refresh_done: AtomicBool::new(false),
                         init: Once::new(),
                         init_wait: Semaphore::new(0),
-                        cached_entry: Arc::new(tokio::sync::Mutex::new(ConfigEntry::default())),
+                        cache_state: watch::channel(if opts.overrides().is_local() {
+                            HasLocalOverrideFlagDataOnly
+                        } else {
+                            NoFlagData
+                        })
+                        .0,
+                        last_fetch: std::sync::Mutex::new(None),
+                        deferred_entry: std::sync::Mutex::new(None),
+                        freeze_bypassed: AtomicBool::new(false),
+                        pinned_etags: PinnedEtags::default(),
+                        poll_interval: watch::channel(opts.polling_mode().poll_interval()).0,
+                        override_report: std::sync::Mutex::new(None),
+                        poll_ticks: watch::channel(0).0,
+                        snapshot: ArcSwap::from_pointee(ConfigResult::from_entry(&preloaded)),
+                        created: Instant::now(),
+                        init_duration: OnceLock::new(),
+                        fetch_counters: std::sync::Mutex::new(FetchCounters {
+                            revision: u64::from(preloaded_version.is_some()),
+                            ..FetchCounters::default()
+                        }),
+                        revision_version: std::sync::Mutex::new(preloaded_version),
+                        events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+                        circuit_breaker: opts.circuit_breaker().map(|(threshold, cool_down)| {
+                            std::sync::Mutex::new(CircuitBreaker::new(threshold, cool_down))
+                        }),
+                        usage,
+                        refresh_lock: tokio::sync::Mutex::new(()),
+                        last_refresh: std::sync::Mutex::new((0, None)),
+                        cached_entry: Arc::new(tokio::sync::Mutex::new(preloaded)),
                     }),
                     options: opts,
                     cancellation_token: CancellationToken::new(),
                     close: Once::new(),
+                    tasks: std::sync::Mutex::new(Vec::new()),
                 };
-                match service.options.polling_mode() {
-                    PollingMode::AutoPoll(interval)
-                        if !service.options.offline()
-                            && !service.options.overrides().is_local() =>
-                    {
-                        service.start_poll(*interval);
-                    }
-                    _ => service.state.initialized(),
-                }
+                service.start_background_tasks()?;
                 Ok(service)
             }
             Err(err) => Err(err),
         }
     }
 
+    /// Starts the polling, the async override refresh and the usage reports as configured.
+    fn start_background_tasks(&self) -> Result<(), ClientError> {
+        let poll_interval = self
+            .options
+            .polling_mode()
+            .poll_interval()
+            // pooled clients read the config JSON downloaded by the pool's polling client
+            .filter(|_| {
+                !self.options.offline()
+                    && !self.options.overrides().is_local()
+                    && !self.options.pooled()
+            });
+        let override_refresh_interval = self.override_refresh_interval();
+        let usage_report_period = self.options.usage_report_hook().map(|(period, _)| *period);
+        if (poll_interval.is_some()
+            || override_refresh_interval.is_some()
+            || usage_report_period.is_some())
+            && tokio::runtime::Handle::try_current().is_err()
+        {
+            return Err(ClientError::new(ErrorKind::NoAsyncRuntime, "The client must be created within a tokio runtime to run its background tasks (auto polling, async flag overrides or usage reports). Create it within a runtime, or use PollingMode::Manual or PollingMode::LazyLoad.".to_owned()));
+        }
+        match poll_interval {
+            Some(interval) => self.start_poll(interval),
+            // local-only clients with an async override source get initialized by the first override refresh
+            None if !self.options.pooled()
+                && self.options.overrides().is_local()
+                && override_refresh_interval.is_some() => {}
+            None => self.state.initialized(),
+        }
+        if let Some(interval) = override_refresh_interval {
+            self.start_override_refresh(interval);
+        }
+        if let Some(period) = usage_report_period {
+            self.start_usage_report(period);
+        }
+        Ok(())
+    }
+
     pub async fn config(&self) -> ConfigResult {
-        let initialized = self.state.initialized.load(Ordering::SeqCst);
-        let threshold = match self.options.polling_mode() {
-            PollingMode::LazyLoad(cache_ttl) => Utc::now() - *cache_ttl,
-            PollingMode::AutoPoll(interval) if !initialized => Utc::now() - *interval,
+        let mut result = self.resolve_config().await;
+        result.last_fetch = *self.state.last_fetch.lock().unwrap();
+        result
+    }
+
+    async fn resolve_config(&self) -> ConfigResult {
+        let mut initialized = self.state.initialized.load(Ordering::SeqCst);
+        let polling_mode = self.options.polling_mode();
+        if let (Some(_), Some(behavior), false) = (
+            polling_mode.poll_interval(),
+            self.options.init_behavior(),
+            initialized,
+        ) {
+            match behavior {
+                InitBehavior::BlockUntilFirstFetch(wait_timeout) => {
+                    if timeout(wait_timeout, self.state.wait_for_initialized())
+                        .await
+                        .is_err()
+                    {
+                        return read_cached(&self.state, &self.options)
+                            .unwrap_or_else(|| ConfigResult::from_entry(&ConfigEntry::default()));
+                    }
+                    initialized = true;
+                }
+                InitBehavior::ServeCacheImmediately => {
+                    return read_cached(&self.state, &self.options)
+                        .unwrap_or_else(|| ConfigResult::from_entry(&ConfigEntry::default()));
+                }
+                InitBehavior::RequireCache => {
+                    if let Some(result) = read_cached(&self.state, &self.options) {
+                        return result;
+                    }
+                }
+            }
+        }
+        let threshold = match (polling_mode.cache_ttl(), self.state.poll_interval()) {
+            (Some(cache_ttl), _) => {
+                early_refresh_threshold(&self.state, &self.options, Utc::now() - cache_ttl)
+            }
+            (_, Some(interval)) if !initialized => Utc::now() - interval,
             _ => DateTime::<Utc>::MIN_UTC,
         };
-        let prefer_cached = match self.options.polling_mode() {
-            PollingMode::LazyLoad(_) => false,
-            _ => initialized,
+        let prefer_cached = if polling_mode.cache_ttl().is_some() {
+            false
+        } else if matches!(polling_mode, PollingMode::Manual)
+            && self.options.not_ready_behavior() == NotReadyBehavior::RefreshOnce
+        {
+            // an empty entry gets fetched once, later evaluations wait for an explicit refresh
+            self.state
+                .implicit_refresh_done
+                .swap(true, Ordering::SeqCst)
+        } else {
+            initialized
         };
         let result = fetch_if_older(&self.state, &self.options, threshold, prefer_cached).await;
         match result {
@@ -147,83 +644,328 @@ impl ConfigService {
         }
     }
 
-    pub async fn refresh(&self) -> Result<(), ClientError> {
+    pub async fn refresh(&self) -> Result<RefreshOutcome, ClientError> {
+        let completed_before = self.state.last_refresh.lock().unwrap().0;
+        let _guard = if let Ok(guard) = self.state.refresh_lock.try_lock() {
+            guard
+        } else {
+            let guard = self.state.refresh_lock.lock().await;
+            let coalesced = {
+                let last_refresh = self.state.last_refresh.lock().unwrap();
+                (last_refresh.0 != completed_before)
+                    .then(|| last_refresh.1.clone())
+                    .flatten()
+            };
+            if let Some(ongoing) = coalesced {
+                let result = ongoing.map(|_| RefreshOutcome::Coalesced);
+                self.refresh_completed(&result);
+                return result;
+            }
+            guard
+        };
         let result =
             fetch_if_older(&self.state, &self.options, DateTime::<Utc>::MAX_UTC, false).await;
-        match result {
-            ServiceResult::Ok(_) => Ok(()),
+        let result = match result {
+            ServiceResult::Ok(config_result) => Ok(config_result.outcome),
             ServiceResult::Err(err, _) => Err(err),
+        };
+        {
+            let mut last_refresh = self.state.last_refresh.lock().unwrap();
+            last_refresh.0 += 1;
+            last_refresh.1 = Some(result.clone());
+        }
+        self.refresh_completed(&result);
+        result
+    }
+
+    pub async fn refresh_if_older(&self, max_age: Duration) -> Result<RefreshOutcome, ClientError> {
+        let threshold = TimeDelta::from_std(max_age)
+            .ok()
+            .and_then(|max_age| Utc::now().checked_sub_signed(max_age))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let result = fetch_if_older(&self.state, &self.options, threshold, false).await;
+        let result = match result {
+            ServiceResult::Ok(config_result) => Ok(config_result.outcome),
+            ServiceResult::Err(err, _) => Err(err),
+        };
+        self.refresh_completed(&result);
+        result
+    }
+
+    fn refresh_completed(&self, result: &Result<RefreshOutcome, ClientError>) {
+        if let Some(hook) = self.options.refresh_hook() {
+            hook(result);
         }
+        // there may be no receivers, which is fine
+        _ = self
+            .state
+            .events
+            .send(ClientEvent::RefreshCompleted(result.clone()));
     }
 
     pub fn close(&self) {
         self.close.call_once(|| self.cancellation_token.cancel());
     }
 
+    /// Closes the service and waits until its background tasks and the config JSON download in progress (if any) end.
+    pub async fn shutdown(&self) {
+        self.close();
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        for task in tasks {
+            // a task that panicked has ended too
+            _ = task.await;
+        }
+        // downloads hold the entry lock, so taking it waits for the one started by an evaluation or refresh
+        drop(self.state.cached_entry.lock().await);
+    }
+
+    fn track_task(&self, task: JoinHandle<()>) {
+        self.tasks.lock().unwrap().push(task);
+    }
+
     pub fn set_mode(&self, offline: bool) {
-        self.state.offline.store(offline, Ordering::SeqCst);
+        if self.state.offline.swap(offline, Ordering::SeqCst) == offline {
+            return;
+        }
+        let mode = if offline {
+            ClientMode::Offline
+        } else {
+            ClientMode::Online
+        };
+        if let Some(hook) = self.options.mode_hook() {
+            hook(mode);
+        }
+        _ = self.state.events.send(ClientEvent::ModeChanged(mode));
+    }
+
+    pub fn subscribe_events(&self) -> broadcast::Receiver<ClientEvent> {
+        self.state.events.subscribe()
     }
 
     pub fn is_offline(&self) -> bool {
-        self.state.offline.load(Ordering::SeqCst)
+        self.state.is_offline()
     }
 
-    pub async fn wait_for_init(&self) -> ClientCacheState {
-        if !self.state.initialized.load(Ordering::SeqCst) {
-            _ = self.state.init_wait.acquire().await;
+    /// Changes the interval of the running AutoPoll loop, returns `false` when the polling mode isn't AutoPoll.
+    pub fn set_poll_interval(&self, interval: Duration) -> bool {
+        if self.state.poll_interval().is_none() {
+            return false;
         }
-        self.determine_cache_state().await
+        self.state.poll_interval.send_if_modified(|current| {
+            let changed = *current != Some(interval);
+            *current = Some(interval);
+            changed
+        });
+        true
+    }
+
+    pub fn set_freeze_bypass(&self, bypass: bool) {
+        self.state.freeze_bypassed.store(bypass, Ordering::SeqCst);
+    }
+
+    pub fn pin_config(&self, etag: &str) {
+        let mut pinned = self.state.pinned_etags.0.lock().unwrap();
+        if !pinned.iter().any(|pinned| pinned == etag) {
+            pinned.push(etag.to_owned());
+        }
+    }
+
+    pub fn unpin_config(&self) {
+        self.state.pinned_etags.0.lock().unwrap().clear();
+    }
+
+    pub async fn wait_for_init(&self) -> ReadyReport {
+        self.state.wait_for_initialized().await;
+        self.ready_report().await
+    }
+
+    pub async fn cache_entry(&self) -> Option<CacheEntry> {
+        // a dropped config JSON is read back from the external cache, before taking the entry lock
+        let from_cache_str = if self.options.memory_mode() == MemoryMode::Standard {
+            String::new()
+        } else {
+            read_cache_str(&self.state, &self.options)
+        };
+        let entry = self.state.cached_entry.lock().await;
+        if entry.is_empty() || entry.etag == ConfigEntry::local().etag {
+            return None;
+        }
+        let mut entry = entry.clone();
+        if !entry.restore_config_json(&from_cache_str) {
+            return None;
+        }
+        Some(CacheEntry {
+            config_json: entry.config_json.as_str()?.to_owned(),
+            etag: entry.etag,
+            fetch_time: entry.fetch_time,
+        })
+    }
+
+    pub fn record_usage(&self, key: &str) {
+        if let Some(usage) = self.state.usage.as_ref() {
+            usage.record(key);
+        }
+    }
+
+    pub fn usage_report(&self, flush: bool) -> Option<UsageReport> {
+        self.state.usage_report(&self.options, flush)
+    }
+
+    /// Returns the last published config result without waiting for the entry lock or a fetch.
+    pub fn snapshot(&self) -> Arc<ConfigResult> {
+        self.state.snapshot.load_full()
+    }
+
+    pub fn override_report(&self) -> Option<Arc<OverrideReport>> {
+        self.state.override_report.lock().unwrap().clone()
+    }
+
+    pub fn data_governance_status(&self) -> DataGovernanceStatus {
+        self.state.fetcher.data_governance_status()
+    }
+
+    pub fn fetch_counters(&self) -> FetchCounters {
+        self.state.fetch_counters()
+    }
+
+    pub fn watch_poll_ticks(&self) -> watch::Receiver<u64> {
+        self.state.poll_ticks.subscribe()
+    }
+
+    pub fn watch_cache_state(&self) -> watch::Receiver<ClientCacheState> {
+        self.state.cache_state.subscribe()
     }
 
-    async fn determine_cache_state(&self) -> ClientCacheState {
+    async fn ready_report(&self) -> ReadyReport {
+        let init_duration = self.state.init_duration.get().copied().unwrap_or_default();
         if self.options.overrides().is_local() {
-            return HasLocalOverrideFlagDataOnly;
+            return ReadyReport {
+                state: HasLocalOverrideFlagDataOnly,
+                fetch_time: None,
+                init_duration,
+                source: ConfigSource::Override,
+            };
         }
 
+        let reads_cache =
+            self.options.polling_mode().poll_interval().is_none() || self.options.pooled();
+        let from_cache_str = if reads_cache {
+            read_cache_str(&self.state, &self.options)
+        } else {
+            String::new()
+        };
         let mut entry = self.state.cached_entry.lock().await;
+        sync_from_cache(
+            &mut entry,
+            &from_cache_str,
+            &self.options,
+            &self.state.pinned_etags,
+        );
+        self.state.update_cache_state(&self.options, &entry);
+        self.state.update_override_report(&self.options, &entry);
+        ReadyReport {
+            state: cache_state_of(&self.options, self.state.poll_interval(), &entry),
+            fetch_time: (!entry.is_empty()).then_some(entry.fetch_time),
+            init_duration,
+            source: entry.source,
+        }
+    }
 
-        if let PollingMode::AutoPoll(interval) = self.options.polling_mode() {
-            if !entry.is_expired(*interval) {
-                return HasUpToDateFlagData;
-            }
-            if entry.is_empty() {
-                return NoFlagData;
-            }
-            HasCachedFlagDataOnly
-        } else {
-            let from_cache =
-                read_cache(&self.state, &self.options, &entry.cache_str).unwrap_or_default();
-            if !from_cache.is_empty() && *entry != from_cache {
-                *entry = from_cache;
-            }
-            if let PollingMode::LazyLoad(interval) = self.options.polling_mode() {
-                if !entry.is_expired(*interval) {
-                    return HasUpToDateFlagData;
+    fn start_poll(&self, interval: Duration) {
+        let state = Arc::clone(&self.state);
+        let opts = Arc::clone(&self.options);
+        let token = self.cancellation_token.clone();
+
+        let task = spawn_named("configcat-auto-poll", async move {
+            let mut interval = interval;
+            let mut int = tokio::time::interval(interval);
+            let mut interval_changes = state.poll_interval.subscribe();
+            loop {
+                tokio::select! {
+                    // a closed service doesn't start another download
+                    biased;
+                    () = token.cancelled() => break,
+                    _ = int.tick() => {
+                        let threshold = early_refresh_threshold(&state, &opts, Utc::now() - (interval / 2));
+                        fetch_if_older(&state, &opts, threshold, false).await;
+                        state.poll_ticks.send_modify(|ticks| *ticks += 1);
+                    },
+                    Ok(()) = interval_changes.changed() => {
+                        if let Some(new_interval) = *interval_changes.borrow_and_update() {
+                            interval = new_interval;
+                            int = tokio::time::interval(interval);
+                        }
+                    },
                 }
             }
-            if entry.is_empty() {
-                return NoFlagData;
-            }
-            HasCachedFlagDataOnly
+        });
+        self.track_task(task);
+    }
+}
+
+impl ConfigService {
+    fn override_refresh_interval(&self) -> Option<Duration> {
+        // pooled clients pick up the values loaded by the pool's polling client
+        if self.options.pooled() {
+            return None;
         }
+        self.options
+            .overrides()
+            .and_then(FlagOverrides::refresh_interval)
     }
 
-    fn start_poll(&self, interval: Duration) {
+    fn start_override_refresh(&self, interval: Duration) {
         let state = Arc::clone(&self.state);
         let opts = Arc::clone(&self.options);
         let token = self.cancellation_token.clone();
 
-        tokio::spawn(async move {
+        let task = spawn_named("configcat-override-refresh", async move {
             let mut int = tokio::time::interval(interval);
             loop {
                 tokio::select! {
+                    biased;
+                    () = token.cancelled() => break,
                     _ = int.tick() => {
-                        fetch_if_older(&state, &opts, Utc::now() - (interval / 2), false).await;
+                        if let Some(ov) = opts.overrides() {
+                            if let Err(err) = ov.refresh().await {
+                                filtered_log!(opts.log_level(LogArea::Override), Level::Warn, event_id = 1302; "Failed to load flag overrides from the async source. ({err})");
+                            }
+                            if opts.overrides().is_local() {
+                                state.initialized();
+                            }
+                            let entry = state.cached_entry.lock().await;
+                            state.update_cache_state(&opts, &entry);
+                        }
                     },
-                    () = token.cancelled() => break
                 }
             }
         });
+        self.track_task(task);
+    }
+
+    fn start_usage_report(&self, period: Duration) {
+        let state = Arc::clone(&self.state);
+        let opts = Arc::clone(&self.options);
+        let token = self.cancellation_token.clone();
+
+        let task = spawn_named("configcat-usage-report", async move {
+            let mut int = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                let closed = tokio::select! {
+                    _ = int.tick() => false,
+                    () = token.cancelled() => true,
+                };
+                if let (Some(report), Some((_, hook))) =
+                    (state.usage_report(&opts, true), opts.usage_report_hook())
+                {
+                    hook(&report);
+                }
+                if closed {
+                    break;
+                }
+            }
+        });
+        self.track_task(task);
     }
 }
 
@@ -239,96 +981,495 @@ async fn fetch_if_older(
     threshold: DateTime<Utc>,
     prefer_cached: bool,
 ) -> ServiceResult {
+    let from_cache_str = read_cache_str(state, options);
     let mut entry = state.cached_entry.lock().await;
+    let counters_before = state.fetch_counters();
+    let result = fetch_entry_if_older(
+        state,
+        options,
+        &mut entry,
+        &from_cache_str,
+        threshold,
+        prefer_cached,
+    )
+    .await;
+    state.update_cache_state(options, &entry);
+    state.update_override_report(options, &entry);
+    state.publish_snapshot(&entry);
+    state.update_revision(options, &entry);
+    if let Some(hook) = options.fetch_hook() {
+        let counters = state.fetch_counters();
+        if counters != counters_before {
+            hook(&counters);
+        }
+    }
+    result
+}
+
+#[allow(clippy::too_many_lines)]
+async fn fetch_entry_if_older(
+    state: &Arc<ServiceState>,
+    options: &Arc<Options>,
+    entry: &mut ConfigEntry,
+    from_cache_str: &str,
+    threshold: DateTime<Utc>,
+    prefer_cached: bool,
+) -> ServiceResult {
     if let Some(ov) = options.overrides() {
         if matches!(ov.behavior(), OverrideBehavior::LocalOnly) {
-            if entry.is_empty() {
+            if entry.is_empty() || entry.override_version != ov.version() {
                 *entry = ConfigEntry {
+                    override_version: ov.version(),
                     config: Arc::new(Config {
-                        settings: ov.source().settings().clone(),
+                        settings: ov.settings(),
                         ..Config::default()
                     }),
+                    source: ConfigSource::Override,
                     ..ConfigEntry::local()
                 };
             }
             return ServiceResult::Ok(ConfigResult::new(
                 entry.config.clone(),
                 DateTime::<Utc>::MIN_UTC,
+                entry.etag.clone(),
             ));
         }
     }
 
-    let from_cache = read_cache(state, options, &entry.cache_str).unwrap_or_default();
+    // while frozen, neither the deferred nor other clients' config JSON versions get applied
+    let frozen = !entry.is_empty() && state.is_frozen(options);
+    let synced_from_cache = !frozen
+        && (apply_deferred(state, options, entry, from_cache_str)
+            || sync_from_cache(entry, from_cache_str, options, &state.pinned_etags));
 
-    if !from_cache.is_empty() && *entry != from_cache {
-        *entry = from_cache;
-    }
+    reapply_overrides(entry, from_cache_str, options);
 
-    if entry.fetch_time > threshold || state.offline.load(Ordering::SeqCst) || prefer_cached {
+    if entry.fetch_time > threshold || state.is_offline() || prefer_cached {
         state.initialized();
-        return ServiceResult::Ok(ConfigResult::new(entry.config.clone(), entry.fetch_time));
+        return ServiceResult::Ok(
+            ConfigResult::new(entry.config.clone(), entry.fetch_time, entry.etag.clone())
+                .with_outcome(if synced_from_cache {
+                    RefreshOutcome::ServedFromCache
+                } else if entry.fetch_time > threshold || prefer_cached {
+                    RefreshOutcome::Skipped
+                } else {
+                    RefreshOutcome::SkippedOffline
+                }),
+        );
     }
 
-    let response = state.fetcher.fetch(&entry.etag).await;
+    if let Some(breaker) = state.circuit_breaker.as_ref() {
+        let before_fetch = breaker.lock().unwrap().before_fetch();
+        match before_fetch {
+            Ok(new_state) => state.circuit_changed(options, new_state),
+            Err(remaining) => {
+                state.initialized();
+                return ServiceResult::Err(
+                    ClientError::new(
+                        ErrorKind::CircuitOpen,
+                        format!("Fetching config JSON was skipped because the circuit breaker is open for another {}ms.", remaining.as_millis()),
+                    ),
+                    ConfigResult::new(entry.config.clone(), entry.fetch_time, entry.etag.clone()),
+                );
+            }
+        }
+    }
+
+    let deferred_etag = state
+        .deferred_entry
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|deferred| deferred.etag.clone());
+    let started = Instant::now();
+    let response = state
+        .fetcher
+        .fetch(deferred_etag.as_ref().unwrap_or(&entry.etag))
+        .await;
+    *state.last_fetch.lock().unwrap() = Some(FetchStats {
+        status: state.fetcher.last_status(),
+        latency: started.elapsed(),
+    });
+    state.count_fetch(&response);
+    if let Some(breaker) = state.circuit_breaker.as_ref() {
+        let new_state = breaker.lock().unwrap().record(&response);
+        state.circuit_changed(options, new_state);
+    }
     state.initialized();
     match response {
+        FetchResponse::Fetched(new_entry)
+            if !entry.is_empty() && state.pinned_etags.withholds(&new_entry.etag) =>
+        {
+            withhold_entry(state, options, entry, new_entry.etag)
+        }
+        FetchResponse::Fetched(new_entry) if frozen => {
+            defer_entry(state, options, entry, Some(new_entry))
+        }
         FetchResponse::Fetched(mut new_entry) => {
-            process_overrides(&mut new_entry, options.overrides());
+            new_entry.source = ConfigSource::Http;
+            process_overrides(
+                &mut new_entry,
+                options.overrides(),
+                options.log_level(LogArea::Override),
+            );
             *entry = new_entry;
-            options
-                .cache()
-                .write(&state.cache_key, entry.cache_str.as_str());
-            ServiceResult::Ok(ConfigResult::new(entry.config.clone(), entry.fetch_time))
+            write_cache(state, options, entry, from_cache_str);
+            ServiceResult::Ok(
+                ConfigResult::new(entry.config.clone(), entry.fetch_time, entry.etag.clone())
+                    .with_outcome(RefreshOutcome::Fetched),
+            )
+        }
+        FetchResponse::NotModified if deferred_etag.is_some() => {
+            defer_entry(state, options, entry, None)
         }
         FetchResponse::NotModified => {
             entry.set_fetch_time(Utc::now());
-            options
-                .cache()
-                .write(&state.cache_key, entry.cache_str.as_str());
-            ServiceResult::Ok(ConfigResult::new(entry.config.clone(), entry.fetch_time))
+            entry.source = ConfigSource::Http;
+            write_cache(state, options, entry, from_cache_str);
+            ServiceResult::Ok(
+                ConfigResult::new(entry.config.clone(), entry.fetch_time, entry.etag.clone())
+                    .with_outcome(RefreshOutcome::Fetched),
+            )
         }
         FetchResponse::Failed(err, transient) => {
             if !transient && !entry.is_empty() {
                 entry.set_fetch_time(Utc::now());
-                options
-                    .cache()
-                    .write(&state.cache_key, entry.cache_str.as_str());
+                write_cache(state, options, entry, from_cache_str);
             }
             ServiceResult::Err(
                 err,
-                ConfigResult::new(entry.config.clone(), entry.fetch_time),
+                ConfigResult::new(entry.config.clone(), entry.fetch_time, entry.etag.clone()),
             )
         }
     }
 }
 
-fn read_cache(
+/// Stores `new_entry` (or refreshes the fetch time of the already deferred one) until the freeze window ends.
+/// The fetch time of `entry` is refreshed too, so the polling doesn't treat it as expired meanwhile.
+fn defer_entry(
     state: &Arc<ServiceState>,
     options: &Arc<Options>,
-    from_memory_str: &String,
-) -> Option<ConfigEntry> {
-    let from_cache_str = options.cache().read(&state.cache_key).unwrap_or_default();
-    if from_cache_str.is_empty() || from_cache_str.as_str() == from_memory_str {
+    entry: &mut ConfigEntry,
+    new_entry: Option<ConfigEntry>,
+) -> ServiceResult {
+    {
+        let mut deferred = state.deferred_entry.lock().unwrap();
+        if let Some(new_entry) = new_entry {
+            filtered_log!(options.log_level(LogArea::Fetch), Level::Info, "A new config JSON was downloaded during a freeze window, it will be applied when the window ends.");
+            *deferred = Some(new_entry);
+        } else if let Some(deferred) = deferred.as_mut() {
+            deferred.set_fetch_time(Utc::now());
+        }
+    }
+    entry.set_fetch_time(Utc::now());
+    ServiceResult::Ok(
+        ConfigResult::new(entry.config.clone(), entry.fetch_time, entry.etag.clone())
+            .with_outcome(RefreshOutcome::Fetched),
+    )
+}
+
+/// Keeps `entry` instead of the downloaded config JSON with `etag`, as the config is pinned to other ETags.
+/// The fetch time of `entry` is refreshed, so the polling doesn't treat it as expired meanwhile.
+fn withhold_entry(
+    state: &Arc<ServiceState>,
+    options: &Arc<Options>,
+    entry: &mut ConfigEntry,
+    etag: String,
+) -> ServiceResult {
+    filtered_log!(options.log_level(LogArea::Fetch), Level::Info, "A new config JSON (ETag: {etag}) was downloaded, but it isn't applied because the config is pinned.");
+    // there may be no receivers, which is fine
+    _ = state.events.send(ClientEvent::UpdateWithheld(etag));
+    entry.set_fetch_time(Utc::now());
+    ServiceResult::Ok(
+        ConfigResult::new(entry.config.clone(), entry.fetch_time, entry.etag.clone())
+            .with_outcome(RefreshOutcome::Fetched),
+    )
+}
+
+/// Applies the config JSON deferred during a freeze window and returns whether there was one.
+/// A deferred config JSON that isn't pinned stays deferred until the config is unpinned.
+fn apply_deferred(
+    state: &Arc<ServiceState>,
+    options: &Arc<Options>,
+    entry: &mut ConfigEntry,
+    from_cache_str: &str,
+) -> bool {
+    let deferred = {
+        let mut deferred = state.deferred_entry.lock().unwrap();
+        if !entry.is_empty()
+            && deferred
+                .as_ref()
+                .is_some_and(|deferred| state.pinned_etags.withholds(&deferred.etag))
+        {
+            return false;
+        }
+        deferred.take()
+    };
+    let Some(mut deferred) = deferred else {
+        return false;
+    };
+    filtered_log!(
+        options.log_level(LogArea::Fetch),
+        Level::Info,
+        "Applying the config JSON downloaded during the freeze window."
+    );
+    process_overrides(
+        &mut deferred,
+        options.overrides(),
+        options.log_level(LogArea::Override),
+    );
+    deferred.source = ConfigSource::Http;
+    *entry = deferred;
+    write_cache(state, options, entry, from_cache_str);
+    true
+}
+
+/// Writes `entry` to the external cache, then drops its config JSON from memory in [`MemoryMode::Bounded`].
+///
+/// A config JSON dropped earlier is restored from `from_cache_str` (read by [`read_cache_str`]) first, when that
+/// still holds the same config JSON version; otherwise the write is skipped.
+fn write_cache(
+    state: &Arc<ServiceState>,
+    options: &Arc<Options>,
+    entry: &mut ConfigEntry,
+    from_cache_str: &str,
+) {
+    if entry.cache_str().is_none() {
+        entry.restore_config_json(from_cache_str);
+    }
+    if let Some(cache_str) = entry.cache_str() {
+        options.cache().write(&state.cache_key, cache_str.as_str());
+    }
+    if options.memory_mode() == MemoryMode::Bounded {
+        entry.drop_config_json();
+    }
+}
+
+/// Returns the cached config without waiting for an ongoing fetch, or `None` when there's no cached config.
+fn read_cached(state: &Arc<ServiceState>, options: &Arc<Options>) -> Option<ConfigResult> {
+    let from_cache_str = read_cache_str(state, options);
+    let Ok(mut entry) = state.cached_entry.try_lock() else {
+        // a fetch is in progress, so fall back to what the external cache holds
+        let mut from_cache = ConfigEntry::default();
+        sync_from_cache(
+            &mut from_cache,
+            &from_cache_str,
+            options,
+            &state.pinned_etags,
+        );
+        return (!from_cache.is_empty() && !state.pinned_etags.withholds(&from_cache.etag))
+            .then(|| ConfigResult::from_entry(&from_cache));
+    };
+    sync_from_cache(&mut entry, &from_cache_str, options, &state.pinned_etags);
+    reapply_overrides(&mut entry, &from_cache_str, options);
+    state.update_cache_state(options, &entry);
+    state.update_override_report(options, &entry);
+    state.publish_snapshot(&entry);
+    state.update_revision(options, &entry);
+    if entry.is_empty() {
         return None;
     }
-    let parsed = entry_from_cached_json(from_cache_str.as_str());
-    match parsed {
+    Some(ConfigResult::from_entry(&entry))
+}
+
+/// Reports when the preferences of `config` require a newer SDK version than this one.
+fn check_sdk_version(options: &Options, config: &Config) {
+    let Some(required) = config.required_sdk_upgrade() else {
+        return;
+    };
+    filtered_log!(options.log_level(LogArea::Fetch), Level::Error, event_id = 1400; "The config JSON requires ConfigCat Rust SDK version {required} or newer, but this is version {PKG_VERSION}. Feature flags that rely on newer SDK features may not be evaluated correctly, please upgrade the SDK.");
+    if let Some(hook) = options.sdk_upgrade_hook() {
+        hook(&required.to_string());
+    }
+}
+
+fn preloaded_entry(options: &Options) -> ConfigEntry {
+    let Some(preloaded) = options.preloaded_entry() else {
+        return ConfigEntry::default();
+    };
+    if options.overrides().is_local() {
+        return ConfigEntry::default();
+    }
+    match entry_from_json(
+        &preloaded.config_json,
+        &preloaded.etag,
+        preloaded.fetch_time,
+    ) {
         Ok(mut entry) => {
-            process_overrides(&mut entry, options.overrides());
-            Some(entry)
+            entry.source = ConfigSource::Cache;
+            process_overrides(
+                &mut entry,
+                options.overrides(),
+                options.log_level(LogArea::Override),
+            );
+            entry
+        }
+        Err(err) => {
+            filtered_log!(options.log_level(LogArea::Cache), Level::Warn, event_id = PRELOADED_CACHE_ENTRY_INVALID_EVENT_ID; "Error occurred while reading the preloaded cache entry, the entry is ignored. ({err})");
+            ConfigEntry::default()
+        }
+    }
+}
+
+/// Treats the cached entry as expired a random amount of time earlier than `threshold` would (XFetch),
+/// so clients sharing a cache don't refresh at the same moment.
+fn early_refresh_threshold(
+    state: &ServiceState,
+    options: &Options,
+    threshold: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let Some(beta) = options.early_refresh_beta() else {
+        return threshold;
+    };
+    let delta = state
+        .last_fetch
+        .lock()
+        .unwrap()
+        .map_or(EARLY_REFRESH_DEFAULT_DELTA, |stats| stats.latency);
+    Duration::try_from_secs_f64(delta.as_secs_f64() * beta * -random_unit().ln())
+        .ok()
+        .and_then(|early| TimeDelta::from_std(early).ok())
+        .and_then(|early| threshold.checked_add_signed(early))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+/// Re-applies the flag overrides to `entry` when they changed since they were last applied.
+/// A dropped config JSON is restored from `from_cache_str` (read by [`read_cache_str`]).
+fn reapply_overrides(entry: &mut ConfigEntry, from_cache_str: &str, options: &Arc<Options>) {
+    let Some(ov) = options.overrides() else {
+        return;
+    };
+    if entry.is_empty() || entry.override_version == ov.version() {
+        return;
+    }
+    if !entry.restore_config_json(from_cache_str) {
+        entry.override_version = ov.version();
+        filtered_log!(options.log_level(LogArea::Override), Level::Warn, event_id = OVERRIDES_REAPPLY_DEFERRED_EVENT_ID; "The changed flag overrides can't be re-applied until the next config JSON download, as the config JSON isn't kept in memory and the cache doesn't hold it.");
+        return;
+    }
+    let config_json = entry.config_json.as_str().unwrap_or_default();
+    match entry_from_json(config_json, &entry.etag, entry.fetch_time) {
+        Ok(mut new_entry) => {
+            new_entry.source = entry.source;
+            process_overrides(
+                &mut new_entry,
+                Some(ov),
+                options.log_level(LogArea::Override),
+            );
+            if options.memory_mode() == MemoryMode::Bounded {
+                new_entry.drop_config_json();
+            }
+            *entry = new_entry;
+        }
+        Err(err) => {
+            entry.override_version = ov.version();
+            filtered_log!(options.log_level(LogArea::Override), Level::Warn, event_id = OVERRIDES_REAPPLY_FAILED_EVENT_ID; "Error occurred while re-applying flag overrides. ({err})");
+        }
+    }
+}
+
+fn cache_state_of(
+    options: &Options,
+    poll_interval: Option<Duration>,
+    entry: &ConfigEntry,
+) -> ClientCacheState {
+    if options.overrides().is_local() {
+        return HasLocalOverrideFlagDataOnly;
+    }
+    match poll_interval.or(options.polling_mode().cache_ttl()) {
+        Some(interval) if !entry.is_expired(interval) => HasUpToDateFlagData,
+        _ if entry.is_empty() => NoFlagData,
+        _ => HasCachedFlagDataOnly,
+    }
+}
+
+fn cache_key(sdk_key: &str, format_version: &str) -> String {
+    sha1(format!("{sdk_key}_{CONFIG_FILE_NAME}_{format_version}").as_str())
+}
+
+/// Reads the config JSON from the external cache.
+///
+/// [`crate::ConfigCache::read`] may block (e.g. on a network round trip), so this must be called
+/// before taking the entry lock, otherwise a slow read would stall every evaluation waiting for the entry.
+fn read_cache_str(state: &Arc<ServiceState>, options: &Arc<Options>) -> String {
+    if options.overrides().is_local() {
+        return String::new();
+    }
+    let from_cache_str = options.cache().read(&state.cache_key).unwrap_or_default();
+    if from_cache_str.is_empty() && !state.legacy_cache_checked.swap(true, Ordering::SeqCst) {
+        return migrate_legacy_cache(state, options).unwrap_or_default();
+    }
+    from_cache_str
+}
+
+/// Replaces `entry` with the one parsed from `from_cache_str` (read by [`read_cache_str`]) when they differ,
+/// unless the config is pinned to other ETags. Returns whether `entry` was replaced.
+fn sync_from_cache(
+    entry: &mut ConfigEntry,
+    from_cache_str: &str,
+    options: &Arc<Options>,
+    pinned_etags: &PinnedEtags,
+) -> bool {
+    if from_cache_str.is_empty() || entry.matches_cache_str(from_cache_str) {
+        return false;
+    }
+    match entry_from_cached_json(from_cache_str) {
+        Ok(mut from_cache) => {
+            // the cache was read without holding the entry lock, so a concurrent fetch may have stored a newer entry since
+            // (the cache holds the fetch time in milliseconds)
+            if !entry.is_empty()
+                && (*entry == from_cache
+                    || from_cache.fetch_time.timestamp_millis()
+                        < entry.fetch_time.timestamp_millis())
+            {
+                return false;
+            }
+            if !entry.is_empty() && pinned_etags.withholds(&from_cache.etag) {
+                return false;
+            }
+            from_cache.source = ConfigSource::Cache;
+            process_overrides(
+                &mut from_cache,
+                options.overrides(),
+                options.log_level(LogArea::Override),
+            );
+            if options.memory_mode() == MemoryMode::Bounded {
+                from_cache.drop_config_json();
+            }
+            *entry = from_cache;
+            true
         }
         Err(err) => {
-            warn!(event_id = 2201; "Error occurred while reading the cache. ({err})");
-            None
+            filtered_log!(options.log_level(LogArea::Cache), Level::Warn, event_id = 2201; "Error occurred while reading the cache. ({err})");
+            false
         }
     }
 }
 
+fn migrate_legacy_cache(state: &Arc<ServiceState>, options: &Arc<Options>) -> Option<String> {
+    for legacy_key in &state.legacy_cache_keys {
+        let Some(legacy) = options.cache().read(legacy_key) else {
+            continue;
+        };
+        if legacy.is_empty() {
+            continue;
+        }
+        if let Some(migrated) = migrate_cache_str(legacy.as_str()) {
+            options.cache().write(&state.cache_key, migrated.as_str());
+            options.cache().migrated(legacy_key, &state.cache_key);
+            return Some(migrated);
+        }
+        filtered_log!(options.log_level(LogArea::Cache), Level::Warn, event_id = LEGACY_CACHE_MIGRATION_FAILED_EVENT_ID; "Error occurred while migrating a cache entry of a previous format, the entry is ignored.");
+    }
+    None
+}
+
 #[cfg(test)]
+#[cfg(feature = "network")]
 mod service_tests {
     use crate::cache::EmptyConfigCache;
     use crate::{ClientCacheState, ConfigCache};
-    use chrono::{DateTime, Utc};
+    use chrono::{DateTime, FixedOffset, TimeDelta, Utc};
     use mockito::{Mock, ServerGuard};
     use reqwest::header::{ETAG, IF_NONE_MATCH};
     use std::sync::{Arc, Mutex};
@@ -336,9 +1477,14 @@ mod service_tests {
 
     use crate::builder::{ClientBuilder, Options};
     use crate::constants::test_constants::{MOCK_KEY, MOCK_PATH};
-    use crate::fetch::service::ConfigService;
+    use crate::fetch::service::{
+        cache_key, sync_from_cache, ClientEvent, ConfigResult, ConfigService, PinnedEtags,
+        RefreshOutcome,
+    };
     use crate::model::config::entry_from_cached_json;
-    use crate::modes::PollingMode;
+    use crate::modes::{InitBehavior, NotReadyBehavior, PollingMode};
+    use crate::FreezeSchedule;
+    use std::collections::HashMap;
 
     #[test]
     fn cache_key_generation() {
@@ -352,7 +1498,7 @@ mod service_tests {
             assert_eq!(
                 service.state.cache_key.as_str(),
                 "f83ba5d45bceb4bb704410f51b704fb6dfa19942"
-            )
+            );
         }
         {
             let opts = Arc::new(
@@ -364,7 +1510,7 @@ mod service_tests {
             assert_eq!(
                 service.state.cache_key.as_str(),
                 "da7bfd8662209c8ed3f9db96daed4f8d91ba5876"
-            )
+            );
         }
     }
 
@@ -396,14 +1542,198 @@ mod service_tests {
     }
 
     #[tokio::test]
-    async fn auto_poll_failed() {
+    async fn auto_poll_failed() {
+        let mut server = mockito::Server::new_async().await;
+        let (m1, m2) = create_success_then_failure_mock(&mut server).await;
+
+        let opts = create_options(
+            server.url(),
+            PollingMode::AutoPoll(Duration::from_millis(100)),
+            None,
+        );
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        m1.assert_async().await;
+        m2.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn lazy_load() {
+        let mut server = mockito::Server::new_async().await;
+        let (m1, m2, m3) = create_success_mock_sequence(&mut server).await;
+
+        let opts = create_options(
+            server.url(),
+            PollingMode::LazyLoad(Duration::from_millis(100)),
+            None,
+        );
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test2");
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test2");
+
+        m1.assert_async().await;
+        m2.assert_async().await;
+        m3.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn lazy_load_failed() {
+        let mut server = mockito::Server::new_async().await;
+        let (m1, m2) = create_success_then_failure_mock(&mut server).await;
+
+        let opts = create_options(
+            server.url(),
+            PollingMode::LazyLoad(Duration::from_millis(100)),
+            None,
+        );
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        m1.assert_async().await;
+        m2.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn cache_state_tracking() {
+        let mut server = mockito::Server::new_async().await;
+        let (m1, m2) = create_success_then_failure_mock(&mut server).await;
+
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let hook_changes = Arc::clone(&changes);
+        let opts = Arc::new(
+            ClientBuilder::new(MOCK_KEY)
+                .base_url(server.url().as_str())
+                .polling_mode(PollingMode::LazyLoad(Duration::from_millis(100)))
+                .on_cache_state_changed(move |state| hook_changes.lock().unwrap().push(state))
+                .build_options(),
+        );
+        let service = ConfigService::new(opts).unwrap();
+        let watch = service.watch_cache_state();
+
+        assert_eq!(*watch.borrow(), ClientCacheState::NoFlagData);
+
+        service.config().await;
+        assert_eq!(*watch.borrow(), ClientCacheState::HasUpToDateFlagData);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        service.config().await;
+        assert_eq!(*watch.borrow(), ClientCacheState::HasCachedFlagDataOnly);
+
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![
+                ClientCacheState::HasUpToDateFlagData,
+                ClientCacheState::HasCachedFlagDataOnly
+            ]
+        );
+
+        m1.assert_async().await;
+        m2.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn last_fetch_stats() {
+        let mut server = mockito::Server::new_async().await;
+        let (m1, m2) = create_success_then_failure_mock(&mut server).await;
+
+        let opts = create_options(server.url(), PollingMode::Manual, None);
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        assert!(result.last_fetch().is_none());
+
+        _ = service.refresh().await;
+        let result = service.config().await;
+        assert_eq!(result.last_fetch().unwrap().status, Some(200));
+        assert!(result.config_age().unwrap() < Duration::from_secs(5));
+
+        _ = service.refresh().await;
+        let result = service.config().await;
+        assert_eq!(result.last_fetch().unwrap().status, Some(502));
+
+        m1.assert_async().await;
+        m2.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn lazy_load_early_refresh() {
+        let mut server = mockito::Server::new_async().await;
+        let m = create_success_mock(&mut server, 1).await;
+
+        let opts = Arc::new(
+            ClientBuilder::new(MOCK_KEY)
+                .cache(Box::new(SingleValueCache::new(construct_cache_payload(
+                    "test",
+                    Utc::now() - Duration::from_secs(10),
+                    "etag1",
+                ))))
+                .base_url(server.url().as_str())
+                .polling_mode(PollingMode::LazyLoad(Duration::from_secs(60)))
+                .early_refresh(1_000_000.0)
+                .build_options(),
+        );
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn manual_poll_refresh_once() {
         let mut server = mockito::Server::new_async().await;
-        let (m1, m2) = create_success_then_failure_mock(&mut server).await;
+        let m = create_success_mock(&mut server, 1).await;
 
-        let opts = create_options(
-            server.url(),
-            PollingMode::AutoPoll(Duration::from_millis(100)),
-            None,
+        let opts = Arc::new(
+            ClientBuilder::new(MOCK_KEY)
+                .base_url(server.url().as_str())
+                .polling_mode(PollingMode::Manual)
+                .not_ready_behavior(NotReadyBehavior::RefreshOnce)
+                .build_options(),
         );
         let service = ConfigService::new(opts).unwrap();
 
@@ -411,28 +1741,26 @@ mod service_tests {
         let setting = &result.config().settings["testKey"];
         assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
 
-        tokio::time::sleep(Duration::from_millis(500)).await;
-
         let result = service.config().await;
         let setting = &result.config().settings["testKey"];
         assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
 
-        m1.assert_async().await;
-        m2.assert_async().await;
+        m.assert_async().await;
     }
 
     #[tokio::test]
-    async fn lazy_load() {
+    async fn manual_poll() {
         let mut server = mockito::Server::new_async().await;
         let (m1, m2, m3) = create_success_mock_sequence(&mut server).await;
 
-        let opts = create_options(
-            server.url(),
-            PollingMode::LazyLoad(Duration::from_millis(100)),
-            None,
-        );
+        let opts = create_options(server.url(), PollingMode::Manual, None);
         let service = ConfigService::new(opts).unwrap();
 
+        let result = service.config().await;
+        assert!(result.config().settings.is_empty());
+
+        _ = service.refresh().await;
+
         let result = service.config().await;
         let setting = &result.config().settings["testKey"];
         assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
@@ -441,13 +1769,13 @@ mod service_tests {
         let setting = &result.config().settings["testKey"];
         assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
 
-        tokio::time::sleep(Duration::from_millis(200)).await;
+        _ = service.refresh().await;
 
         let result = service.config().await;
         let setting = &result.config().settings["testKey"];
         assert_eq!(setting.value.clone().string_val.unwrap(), "test2");
 
-        tokio::time::sleep(Duration::from_millis(200)).await;
+        _ = service.refresh().await;
 
         let result = service.config().await;
         let setting = &result.config().settings["testKey"];
@@ -459,71 +1787,139 @@ mod service_tests {
     }
 
     #[tokio::test]
-    async fn lazy_load_failed() {
+    async fn refresh_if_older() {
         let mut server = mockito::Server::new_async().await;
-        let (m1, m2) = create_success_then_failure_mock(&mut server).await;
+        let m = create_success_mock(&mut server, 1).await;
 
         let opts = create_options(
             server.url(),
-            PollingMode::LazyLoad(Duration::from_millis(100)),
-            None,
+            PollingMode::Manual,
+            Some(Box::new(SingleValueCache::new(String::default()))),
         );
         let service = ConfigService::new(opts).unwrap();
 
-        let result = service.config().await;
-        let setting = &result.config().settings["testKey"];
-        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+        let outcome = service.refresh_if_older(Duration::from_secs(60)).await;
+        assert_eq!(outcome.unwrap(), RefreshOutcome::Fetched);
 
-        let result = service.config().await;
-        let setting = &result.config().settings["testKey"];
-        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+        let outcome = service.refresh_if_older(Duration::from_secs(60)).await;
+        assert_eq!(outcome.unwrap(), RefreshOutcome::Skipped);
 
-        tokio::time::sleep(Duration::from_millis(200)).await;
+        service.options.cache().write(
+            service.state.clone().cache_key.as_str(),
+            construct_cache_payload("test2", Utc::now(), "etag2").as_str(),
+        );
+
+        let outcome = service.refresh_if_older(Duration::from_secs(60)).await;
+        assert_eq!(outcome.unwrap(), RefreshOutcome::ServedFromCache);
 
         let result = service.config().await;
         let setting = &result.config().settings["testKey"];
-        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test2");
 
-        m1.assert_async().await;
-        m2.assert_async().await;
+        m.assert_async().await;
     }
 
     #[tokio::test]
-    async fn manual_poll() {
+    async fn freeze_window_defers_new_config() {
         let mut server = mockito::Server::new_async().await;
         let (m1, m2, m3) = create_success_mock_sequence(&mut server).await;
 
-        let opts = create_options(server.url(), PollingMode::Manual, None);
+        let now = Utc::now();
+        let opts = Arc::new(
+            ClientBuilder::new(MOCK_KEY)
+                .cache(Box::new(SingleValueCache::new(String::default())))
+                .base_url(server.url().as_str())
+                .polling_mode(PollingMode::Manual)
+                .freeze_schedule(
+                    FreezeSchedule::new(FixedOffset::east_opt(0).unwrap())
+                        .window(now - TimeDelta::hours(1), now + TimeDelta::hours(1)),
+                )
+                .build_options(),
+        );
         let service = ConfigService::new(opts).unwrap();
+        let value = |result: ConfigResult| {
+            result.config().settings["testKey"]
+                .value
+                .clone()
+                .string_val
+                .unwrap()
+        };
 
-        let result = service.config().await;
-        assert!(result.config().settings.is_empty());
+        // the first config JSON is applied regardless of the freeze
+        service.refresh().await.unwrap();
+        assert_eq!(value(service.config().await), "test1");
 
-        _ = service.refresh().await;
+        service.refresh().await.unwrap();
+        assert_eq!(value(service.config().await), "test1");
+        let cached = service.options.cache().read(&service.state.cache_key);
+        assert!(cached.unwrap().contains("test1"));
 
-        let result = service.config().await;
-        let setting = &result.config().settings["testKey"];
-        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+        // polls with the ETag of the deferred config JSON
+        service.refresh().await.unwrap();
+        assert_eq!(value(service.config().await), "test1");
 
-        let result = service.config().await;
-        let setting = &result.config().settings["testKey"];
-        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+        service.set_freeze_bypass(true);
+        assert_eq!(value(service.config().await), "test2");
 
-        _ = service.refresh().await;
+        m1.assert_async().await;
+        m2.assert_async().await;
+        m3.assert_async().await;
+    }
 
-        let result = service.config().await;
-        let setting = &result.config().settings["testKey"];
-        assert_eq!(setting.value.clone().string_val.unwrap(), "test2");
+    #[tokio::test]
+    async fn pinned_config_withholds_new_config() {
+        let mut server = mockito::Server::new_async().await;
+        let m1 = create_success_mock(&mut server, 1).await;
+        let m2 = server
+            .mock("GET", MOCK_PATH)
+            .match_header(IF_NONE_MATCH.as_str(), "etag1")
+            .with_status(200)
+            .with_body(construct_json_payload("test2"))
+            .with_header(ETAG.as_str(), "etag2")
+            .expect(3)
+            .create_async()
+            .await;
 
-        _ = service.refresh().await;
+        let opts = create_options(
+            server.url(),
+            PollingMode::Manual,
+            Some(Box::new(SingleValueCache::new(String::default()))),
+        );
+        let service = ConfigService::new(opts).unwrap();
+        let mut events = service.subscribe_events();
+        let value = |result: ConfigResult| {
+            result.config().settings["testKey"]
+                .value
+                .clone()
+                .string_val
+                .unwrap()
+        };
 
-        let result = service.config().await;
-        let setting = &result.config().settings["testKey"];
-        assert_eq!(setting.value.clone().string_val.unwrap(), "test2");
+        // the first config JSON is applied regardless of the pinned ETags
+        service.pin_config("etag0");
+        service.refresh().await.unwrap();
+        assert_eq!(value(service.config().await), "test1");
+
+        service.pin_config("etag1");
+        service.refresh().await.unwrap();
+        service.refresh().await.unwrap();
+        assert_eq!(value(service.config().await), "test1");
+        let cached = service.options.cache().read(&service.state.cache_key);
+        assert!(cached.unwrap().contains("test1"));
+        let withheld: Vec<ClientEvent> = std::iter::from_fn(|| events.try_recv().ok())
+            .filter(|event| matches!(event, ClientEvent::UpdateWithheld(_)))
+            .collect();
+        assert_eq!(
+            withheld,
+            vec![ClientEvent::UpdateWithheld("etag2".to_owned()); 2]
+        );
+
+        service.unpin_config();
+        service.refresh().await.unwrap();
+        assert_eq!(value(service.config().await), "test2");
 
         m1.assert_async().await;
         m2.assert_async().await;
-        m3.assert_async().await;
     }
 
     #[tokio::test]
@@ -689,7 +2085,7 @@ mod service_tests {
             )))),
         );
         let service = ConfigService::new(opts).unwrap();
-        let state = service.wait_for_init().await;
+        let state = service.wait_for_init().await.state;
 
         assert!(matches!(state, ClientCacheState::HasUpToDateFlagData));
 
@@ -711,7 +2107,7 @@ mod service_tests {
             )))),
         );
         let service = ConfigService::new(opts).unwrap();
-        let state = service.wait_for_init().await;
+        let state = service.wait_for_init().await.state;
 
         assert!(matches!(state, ClientCacheState::HasUpToDateFlagData));
 
@@ -733,7 +2129,7 @@ mod service_tests {
             )))),
         );
         let service = ConfigService::new(opts).unwrap();
-        let state = service.wait_for_init().await;
+        let state = service.wait_for_init().await.state;
 
         assert!(matches!(state, ClientCacheState::HasCachedFlagDataOnly));
 
@@ -751,7 +2147,7 @@ mod service_tests {
             None,
         );
         let service = ConfigService::new(opts).unwrap();
-        let state = service.wait_for_init().await;
+        let state = service.wait_for_init().await.state;
 
         assert!(matches!(state, ClientCacheState::NoFlagData));
 
@@ -773,7 +2169,7 @@ mod service_tests {
             )))),
         );
         let service = ConfigService::new(opts).unwrap();
-        let state = service.wait_for_init().await;
+        let state = service.wait_for_init().await.state;
 
         assert!(matches!(state, ClientCacheState::HasCachedFlagDataOnly));
 
@@ -787,13 +2183,178 @@ mod service_tests {
 
         let opts = create_options(server.url(), PollingMode::Manual, None);
         let service = ConfigService::new(opts).unwrap();
-        let state = service.wait_for_init().await;
+        let state = service.wait_for_init().await.state;
 
         assert!(matches!(state, ClientCacheState::NoFlagData));
 
         m.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn cache_migrates_legacy_entry() {
+        let cache = Arc::new(KeyedCache::default());
+        let legacy_key = cache_key(MOCK_KEY, "v1");
+        cache.write(
+            &legacy_key,
+            construct_cache_payload("legacy", Utc::now(), "etag1").as_str(),
+        );
+
+        let opts = create_options(
+            "http://localhost".to_owned(),
+            PollingMode::Manual,
+            Some(Box::new(Arc::clone(&cache))),
+        );
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "legacy");
+        assert_eq!(result.etag(), "etag1");
+
+        let migrated = cache.read(&service.state.cache_key).unwrap();
+        assert_eq!(
+            entry_from_cached_json(migrated.as_str()).unwrap().etag,
+            "etag1"
+        );
+        assert_eq!(
+            *cache.migrations.lock().unwrap(),
+            vec![(legacy_key, service.state.cache_key.clone())]
+        );
+    }
+
+    #[tokio::test]
+    async fn cache_migrates_legacy_bare_json() {
+        let cache = Arc::new(KeyedCache::default());
+        cache.write(
+            &cache_key(MOCK_KEY, "v1"),
+            construct_json_payload("legacy").as_str(),
+        );
+
+        let opts = create_options(
+            "http://localhost".to_owned(),
+            PollingMode::Manual,
+            Some(Box::new(Arc::clone(&cache))),
+        );
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "legacy");
+        assert_eq!(*result.fetch_time(), DateTime::UNIX_EPOCH);
+    }
+
+    #[tokio::test]
+    async fn init_behavior_serve_cache_immediately() {
+        let mut server = mockito::Server::new_async().await;
+        let m = create_success_mock(&mut server, 1).await;
+
+        let opts = create_options_with_init_behavior(
+            server.url(),
+            InitBehavior::ServeCacheImmediately,
+            Some(Box::new(SingleValueCache::new(construct_cache_payload(
+                "test",
+                Utc::now() - Duration::from_secs(120),
+                "etag1",
+            )))),
+        );
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test");
+
+        service.wait_for_init().await;
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn init_behavior_serve_cache_immediately_empty() {
+        let mut server = mockito::Server::new_async().await;
+        let m = create_success_mock(&mut server, 1).await;
+
+        let opts = create_options_with_init_behavior(
+            server.url(),
+            InitBehavior::ServeCacheImmediately,
+            None,
+        );
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        assert!(result.config().settings.is_empty());
+
+        service.wait_for_init().await;
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn init_behavior_require_cache() {
+        let mut server = mockito::Server::new_async().await;
+        let m = create_success_mock(&mut server, 1).await;
+
+        let opts =
+            create_options_with_init_behavior(server.url(), InitBehavior::RequireCache, None);
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn init_behavior_block_until_first_fetch() {
+        let mut server = mockito::Server::new_async().await;
+        let m = create_success_mock(&mut server, 1).await;
+
+        let opts = create_options_with_init_behavior(
+            server.url(),
+            InitBehavior::BlockUntilFirstFetch(Duration::from_secs(5)),
+            None,
+        );
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test1");
+
+        m.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn init_behavior_block_until_first_fetch_timeout() {
+        // accepts the connections but never responds
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                streams.push(stream);
+            }
+        });
+
+        let opts = create_options_with_init_behavior(
+            format!("http://{addr}"),
+            InitBehavior::BlockUntilFirstFetch(Duration::from_millis(10)),
+            Some(Box::new(SingleValueCache::new(construct_cache_payload(
+                "test",
+                Utc::now() - Duration::from_secs(120),
+                "etag1",
+            )))),
+        );
+        let service = ConfigService::new(opts).unwrap();
+
+        let result = service.config().await;
+        let setting = &result.config().settings["testKey"];
+        assert_eq!(setting.value.clone().string_val.unwrap(), "test");
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
     fn create_options(
         url: String,
         mode: PollingMode,
@@ -808,6 +2369,87 @@ mod service_tests {
         )
     }
 
+    #[allow(clippy::needless_pass_by_value)]
+    fn create_options_with_init_behavior(
+        url: String,
+        init_behavior: InitBehavior,
+        cache: Option<Box<dyn ConfigCache>>,
+    ) -> Arc<Options> {
+        Arc::new(
+            ClientBuilder::new(MOCK_KEY)
+                .cache(cache.unwrap_or(Box::new(EmptyConfigCache::new())))
+                .base_url(url.as_str())
+                .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)))
+                .init_behavior(init_behavior)
+                .build_options(),
+        )
+    }
+
+    #[test]
+    fn sync_from_cache_keeps_newer_entry() {
+        let opts = Arc::new(
+            ClientBuilder::new(MOCK_KEY)
+                .polling_mode(PollingMode::Manual)
+                .build_options(),
+        );
+        let now = Utc::now();
+        let mut entry =
+            entry_from_cached_json(construct_cache_payload("test2", now, "etag2").as_str())
+                .unwrap();
+
+        // a read that raced with a fetch returns the previous entry
+        let older = construct_cache_payload("test1", now - Duration::from_secs(60), "etag1");
+        assert!(!sync_from_cache(
+            &mut entry,
+            older.as_str(),
+            &opts,
+            &PinnedEtags::default()
+        ));
+        assert_eq!(entry.etag, "etag2");
+
+        let newer = construct_cache_payload("test3", now + Duration::from_secs(60), "etag3");
+        assert!(sync_from_cache(
+            &mut entry,
+            newer.as_str(),
+            &opts,
+            &PinnedEtags::default()
+        ));
+        assert_eq!(entry.etag, "etag3");
+    }
+
+    #[test]
+    fn sync_from_cache_without_etag() {
+        let opts = Arc::new(
+            ClientBuilder::new(MOCK_KEY)
+                .polling_mode(PollingMode::Manual)
+                .build_options(),
+        );
+        let now = Utc::now();
+        let mut entry =
+            entry_from_cached_json(construct_cache_payload("test1", now, "").as_str()).unwrap();
+
+        // a proxy without ETags wrote a different config JSON
+        let newer = construct_cache_payload("test2", now + Duration::from_secs(60), "");
+        assert!(sync_from_cache(
+            &mut entry,
+            newer.as_str(),
+            &opts,
+            &PinnedEtags::default()
+        ));
+        assert_eq!(
+            entry.config.settings["testKey"].value.string_val.as_deref(),
+            Some("test2")
+        );
+
+        let refreshed = construct_cache_payload("test2", now + Duration::from_secs(120), "");
+        assert!(!sync_from_cache(
+            &mut entry,
+            refreshed.as_str(),
+            &opts,
+            &PinnedEtags::default()
+        ));
+    }
+
     async fn create_success_mock_sequence(server: &mut ServerGuard) -> (Mock, Mock, Mock) {
         let m1 = create_success_mock(server, 1).await;
 
@@ -893,6 +2535,32 @@ mod service_tests {
         format!(r#"{{"f": {{"testKey":{{"t":1,"v":{{"s": "{val}"}}}}}}, "s": []}}"#)
     }
 
+    #[derive(Default)]
+    struct KeyedCache {
+        values: Mutex<HashMap<String, String>>,
+        migrations: Mutex<Vec<(String, String)>>,
+    }
+
+    impl ConfigCache for Arc<KeyedCache> {
+        fn read(&self, key: &str) -> Option<String> {
+            self.values.lock().unwrap().get(key).cloned()
+        }
+
+        fn write(&self, key: &str, value: &str) {
+            self.values
+                .lock()
+                .unwrap()
+                .insert(key.to_owned(), value.to_owned());
+        }
+
+        fn migrated(&self, legacy_key: &str, key: &str) {
+            self.migrations
+                .lock()
+                .unwrap()
+                .push((legacy_key.to_owned(), key.to_owned()));
+        }
+    }
+
     struct SingleValueCache {
         pub val: Mutex<String>,
     }
@@ -912,7 +2580,7 @@ mod service_tests {
 
         fn write(&self, _: &str, value: &str) {
             let mut val = self.val.lock().unwrap();
-            *val = value.to_owned()
+            *val = value.to_owned();
         }
     }
 }
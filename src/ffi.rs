@@ -0,0 +1,247 @@
+//! C ABI of the SDK for embedding it in non-Rust processes, enabled by the `ffi` feature.
+//!
+//! The shared library is built with `cargo rustc --release --features ffi --crate-type cdylib`.
+//! The functions below are declared in C as:
+//!
+//! ```c
+//! typedef struct ConfigCatClient ConfigCatClient;
+//!
+//! ConfigCatClient *configcat_client_new(const char *options_json, char **error);
+//! char *configcat_get_value(ConfigCatClient *client, const char *key, const char *default_json,
+//!                           const char *user_json, char **error);
+//! int configcat_refresh(ConfigCatClient *client, char **error);
+//! void configcat_client_free(ConfigCatClient *client);
+//! void configcat_string_free(char *str);
+//! ```
+//!
+//! # Ownership
+//!
+//! - The string arguments are borrowed for the duration of the call, they must be NUL-terminated UTF-8.
+//! - The client returned by [`configcat_client_new`] is owned by the caller and must be released exactly once
+//!   with [`configcat_client_free`], which also stops the SDK's background tasks.
+//! - The strings returned by [`configcat_get_value`] and written to the `error` out-parameters are owned by the caller
+//!   and must be released with [`configcat_string_free`].
+//!
+//! # Errors and panics
+//!
+//! A failing function returns `NULL` (or `-1`) and, when `error` is not `NULL`, writes the description of
+//! the failure to `*error`. A panic never unwinds across the boundary, it's reported as a failure instead.
+//!
+//! Each client owns a tokio runtime, the functions block the calling thread until the operation completes
+//! and must not be called from a thread that drives a tokio runtime.
+
+use crate::{Client, ClientBuilder, ConfigCatOptions, User, UserValue};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// A [`Client`] together with the tokio runtime that drives it, opaque to C.
+pub struct ConfigCatClient {
+    runtime: Runtime,
+    client: Client,
+}
+
+/// Creates a client from the [`ConfigCatOptions`] in `options_json`, e.g. `{"sdk_key": "...", "polling_mode": "manual"}`.
+///
+/// Returns `NULL` when the options are invalid or the client can't be built.
+///
+/// # Safety
+///
+/// `options_json` must be a valid NUL-terminated string, and `error` must be `NULL` or point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn configcat_client_new(
+    options_json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut ConfigCatClient {
+    guarded(error, ptr::null_mut(), || {
+        let options: ConfigCatOptions =
+            serde_json::from_str(read_str(options_json, "options JSON")?)
+                .map_err(|err| format!("The options JSON is invalid. ({err})"))?;
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("configcat-ffi")
+            .enable_all()
+            .build()
+            .map_err(|err| format!("Failed to start the tokio runtime. ({err})"))?;
+        let client = {
+            let _guard = runtime.enter();
+            ClientBuilder::from_config(options)
+                .and_then(ClientBuilder::build)
+                .map_err(|err| err.message)?
+        };
+        Ok(Box::into_raw(Box::new(ConfigCatClient { runtime, client })))
+    })
+}
+
+/// Evaluates the feature flag or setting identified by `key` and returns its value as a JSON string,
+/// e.g. `true`, `42` or `"text"`.
+///
+/// `default_json` is the JSON of the value returned when the evaluation fails, and `user_json` (optional) is
+/// a JSON object of the User Object's attributes, e.g. `{"Identifier": "id", "Email": "a@b.com", "Tags": ["beta"]}`.
+///
+/// Returns `NULL` when an argument is invalid.
+///
+/// # Safety
+///
+/// `client` must be a pointer returned by [`configcat_client_new`] and not freed yet, `key` and `default_json`
+/// must be valid NUL-terminated strings, `user_json` must be `NULL` or a valid NUL-terminated string,
+/// and `error` must be `NULL` or point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn configcat_get_value(
+    client: *mut ConfigCatClient,
+    key: *const c_char,
+    default_json: *const c_char,
+    user_json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    guarded(error, ptr::null_mut(), || {
+        let ffi_client = client.as_ref().ok_or("The client is NULL.")?;
+        let key = read_str(key, "key")?;
+        let default: JsonValue =
+            serde_json::from_str(read_str(default_json, "default value JSON")?)
+                .map_err(|err| format!("The default value JSON is invalid. ({err})"))?;
+        let user = if user_json.is_null() {
+            None
+        } else {
+            Some(user_from_json(read_str(user_json, "user JSON")?)?)
+        };
+        let value = ffi_client
+            .runtime
+            .block_on(ffi_client.client.get_value(key, default, user));
+        Ok(into_c_string(&value.to_string()))
+    })
+}
+
+/// Downloads the latest config JSON, see [`Client::refresh`].
+///
+/// Returns `0` on success and `-1` on failure.
+///
+/// # Safety
+///
+/// `client` must be a pointer returned by [`configcat_client_new`] and not freed yet,
+/// and `error` must be `NULL` or point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn configcat_refresh(
+    client: *mut ConfigCatClient,
+    error: *mut *mut c_char,
+) -> c_int {
+    guarded(error, -1, || {
+        let ffi_client = client.as_ref().ok_or("The client is NULL.")?;
+        ffi_client
+            .runtime
+            .block_on(ffi_client.client.refresh())
+            .map_err(|err| err.message)?;
+        Ok(0)
+    })
+}
+
+/// Closes the client, stops its background tasks and releases it. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `client` must be `NULL` or a pointer returned by [`configcat_client_new`] that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn configcat_client_free(client: *mut ConfigCatClient) {
+    if client.is_null() {
+        return;
+    }
+    let ffi_client = Box::from_raw(client);
+    let _ = catch_unwind(AssertUnwindSafe(move || {
+        let ConfigCatClient { runtime, client } = *ffi_client;
+        {
+            let _guard = runtime.enter();
+            drop(client);
+        }
+        runtime.shutdown_timeout(Duration::from_secs(1));
+    }));
+}
+
+/// Releases a string returned by the SDK. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `str` must be `NULL` or a string returned by the SDK that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn configcat_string_free(str: *mut c_char) {
+    if !str.is_null() {
+        drop(CString::from_raw(str));
+    }
+}
+
+// runs `f` without letting a panic unwind into the caller, reports its failure through `error`
+unsafe fn guarded<T>(
+    error: *mut *mut c_char,
+    failed: T,
+    f: impl FnOnce() -> Result<T, String>,
+) -> T {
+    let message = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(result)) => return result,
+        Ok(Err(message)) => message,
+        Err(panic) => {
+            let reason = panic
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            format!("The SDK panicked. ({reason})")
+        }
+    };
+    if !error.is_null() {
+        *error = into_c_string(&message);
+    }
+    failed
+}
+
+unsafe fn read_str<'a>(str: *const c_char, name: &str) -> Result<&'a str, String> {
+    if str.is_null() {
+        return Err(format!("The {name} is NULL."));
+    }
+    CStr::from_ptr(str)
+        .to_str()
+        .map_err(|_| format!("The {name} is not valid UTF-8."))
+}
+
+fn into_c_string(str: &str) -> *mut c_char {
+    CString::new(str.replace('\0', ""))
+        .unwrap_or_default()
+        .into_raw()
+}
+
+fn user_from_json(user_json: &str) -> Result<User, String> {
+    let attributes: HashMap<String, JsonValue> = serde_json::from_str(user_json)
+        .map_err(|err| format!("The user JSON is invalid, expected an object. ({err})"))?;
+    let mut map = HashMap::with_capacity(attributes.len());
+    for (name, value) in attributes {
+        let value = match value {
+            JsonValue::String(val) => UserValue::String(val),
+            JsonValue::Number(num) => num
+                .as_i64()
+                .map(UserValue::Int)
+                .or_else(|| num.as_u64().map(UserValue::UInt))
+                .unwrap_or_else(|| UserValue::Float(num.as_f64().unwrap_or_default())),
+            JsonValue::Array(values) => UserValue::StringVec(
+                values
+                    .into_iter()
+                    .map(|val| match val {
+                        JsonValue::String(val) => Ok(val),
+                        _ => Err(format!("The user attribute '{name}' must contain texts only.")),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => {
+                return Err(format!(
+                    "The user attribute '{name}' is invalid, expected a text, number or array of texts."
+                ))
+            }
+        };
+        map.insert(name, value);
+    }
+    if !map.contains_key(User::IDENTIFIER) {
+        return Err("The user JSON is missing the 'Identifier' attribute.".to_owned());
+    }
+    Ok(User::from(map))
+}
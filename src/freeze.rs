@@ -0,0 +1,102 @@
+use chrono::{DateTime, Datelike, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Describes change-freeze windows during which the [`crate::Client`] doesn't apply new config JSON versions.
+///
+/// During a window the SDK keeps polling (so the ETag stays up to date), but a config JSON that differs from
+/// the one in use is only applied after the window ends. A client that has no config JSON yet applies
+/// the first one regardless of the schedule. Use [`crate::Client::bypass_freeze`] to roll out changes anyway.
+///
+/// Recurring windows are interpreted in the local time of the schedule's timezone, which follows the
+/// daylight saving time transitions of timezones like [`chrono::Local`] or the ones of the `chrono-tz` crate;
+/// a window whose end is earlier than its start spans midnight.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{FixedOffset, NaiveTime, Weekday};
+/// use configcat::FreezeSchedule;
+///
+/// let cet = FixedOffset::east_opt(3600).unwrap();
+/// let schedule = FreezeSchedule::new(cet)
+///     // every night between 22:00 and 06:00
+///     .daily(NaiveTime::from_hms_opt(22, 0, 0).unwrap(), NaiveTime::from_hms_opt(6, 0, 0).unwrap())
+///     // Friday afternoons
+///     .weekly(Weekday::Fri, NaiveTime::from_hms_opt(14, 0, 0).unwrap(), NaiveTime::from_hms_opt(23, 59, 59).unwrap());
+/// ```
+#[derive(Clone)]
+pub struct FreezeSchedule {
+    local_time: Arc<dyn Fn(DateTime<Utc>) -> NaiveDateTime + Send + Sync>,
+    daily: Vec<(NaiveTime, NaiveTime)>,
+    weekly: Vec<(Weekday, NaiveTime, NaiveTime)>,
+    windows: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+impl FreezeSchedule {
+    /// Creates an empty [`FreezeSchedule`] whose recurring windows are interpreted in `timezone`.
+    pub fn new<Tz: TimeZone + Send + Sync + 'static>(timezone: Tz) -> Self {
+        Self {
+            local_time: Arc::new(move |at| at.with_timezone(&timezone).naive_local()),
+            daily: Vec::new(),
+            weekly: Vec::new(),
+            windows: Vec::new(),
+        }
+    }
+
+    /// Adds a window that recurs every day between `start` and `end`.
+    pub fn daily(mut self, start: NaiveTime, end: NaiveTime) -> Self {
+        self.daily.push((start, end));
+        self
+    }
+
+    /// Adds a window that recurs every week on `weekday` between `start` and `end`.
+    pub fn weekly(mut self, weekday: Weekday, start: NaiveTime, end: NaiveTime) -> Self {
+        self.weekly.push((weekday, start, end));
+        self
+    }
+
+    /// Adds a one-off window between `start` and `end`.
+    pub fn window(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.windows.push((start, end));
+        self
+    }
+
+    /// Indicates whether `at` falls into any of the windows.
+    pub fn is_frozen(&self, at: DateTime<Utc>) -> bool {
+        let local = (self.local_time)(at);
+        let (weekday, time) = (local.weekday(), local.time());
+        self.daily
+            .iter()
+            .any(|(start, end)| in_daily_window(time, *start, *end))
+            || self.weekly.iter().any(|(day, start, end)| {
+                if start <= end {
+                    weekday == *day && *start <= time && time < *end
+                } else {
+                    (weekday == *day && *start <= time) || (weekday == day.succ() && time < *end)
+                }
+            })
+            || self
+                .windows
+                .iter()
+                .any(|(start, end)| *start <= at && at < *end)
+    }
+}
+
+impl Debug for FreezeSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FreezeSchedule")
+            .field("daily", &self.daily)
+            .field("weekly", &self.weekly)
+            .field("windows", &self.windows)
+            .finish_non_exhaustive()
+    }
+}
+
+fn in_daily_window(time: NaiveTime, start: NaiveTime, end: NaiveTime) -> bool {
+    if start <= end {
+        start <= time && time < end
+    } else {
+        start <= time || time < end
+    }
+}
@@ -0,0 +1,29 @@
+use std::net::IpAddr;
+
+/// Resolves the country of an IP address, used by [`crate::User::country_from_ip`].
+///
+/// Implementations are usually backed by a geolocation database (e.g. MaxMind GeoIP2). The returned
+/// country must be in the same format as the one used by the targeting rules (e.g. `"Hungary"` or `"HU"`).
+///
+/// # Examples
+///
+/// ```rust
+/// use std::net::IpAddr;
+/// use configcat::{GeoResolver, User};
+///
+/// struct StaticResolver {}
+///
+/// impl GeoResolver for StaticResolver {
+///     fn country(&self, ip: IpAddr) -> Option<String> {
+///         // look up `ip` in the geolocation database
+///         Some("Hungary".to_owned())
+///     }
+/// }
+///
+/// let user = User::new("user-id").country_from_ip("192.0.2.1".parse().unwrap(), &StaticResolver {});
+/// assert_eq!("Hungary", user[User::COUNTRY].to_string().as_str());
+/// ```
+pub trait GeoResolver: Sync + Send {
+    /// Returns the country of `ip`, or `None` when it's unknown.
+    fn country(&self, ip: IpAddr) -> Option<String>;
+}
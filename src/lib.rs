@@ -1,6 +1,11 @@
 //! ConfigCat SDK for Rust.
 //!
 //! For more information and code samples, see the [Rust SDK documentation](https://configcat.com/docs/sdk-reference/rust).
+//!
+//! The `network` feature (enabled by default) downloads the config JSON over HTTP. Without it, `reqwest` is left out of
+//! the dependency tree and only clients with [`OverrideBehavior::LocalOnly`] flag overrides can be built.
+//! `tokio` remains a dependency either way (without its `net` feature), as the [`Client`] API is async and
+//! runs its background tasks on the tokio runtime.
 
 #![warn(missing_docs)]
 #![warn(clippy::pedantic)]
@@ -8,46 +13,108 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::return_self_not_must_use)]
 #![allow(clippy::must_use_candidate)]
+#![allow(clippy::duration_suboptimal_units)]
 
 #[macro_use]
 mod macros;
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench;
 mod builder;
 mod cache;
 mod client;
 mod constants;
+mod defaults;
+mod diagnostics;
 mod errors;
 mod eval;
+mod exposure;
+#[cfg(feature = "axum")]
+mod extract;
 mod fetch;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod freeze;
+#[cfg(feature = "geo")]
+mod geo;
 mod model;
 mod modes;
+mod options;
 mod r#override;
+mod pool;
+mod session;
+mod usage;
 mod user;
 mod utils;
 mod value;
 
-pub use cache::ConfigCache;
+#[cfg(feature = "brotli")]
+pub use cache::CacheCompression;
+pub use cache::{CacheEntry, ConfigCache};
 pub use client::Client;
+#[cfg(feature = "macros")]
+pub use configcat_macros::include_overrides;
 pub use constants::PKG_VERSION;
+pub use defaults::DefaultValueProvider;
+pub use diagnostics::DiagnosticsReport;
 pub use errors::{ClientError, ErrorKind};
-pub use eval::details::EvaluationDetails;
+pub use eval::details::{
+    AllValuesResult, BatchEvaluation, ConditionTiming, EvalOptions, EvaluationDetails, RuleOutcome,
+    ValueSource,
+};
+pub use eval::shadow::{
+    evaluate_config, impacted_keys, impacted_users, shadow_diff, ShadowDiff, UserImpact,
+};
+pub use eval::simulation::Simulation;
+pub use exposure::ExposureEvent;
+#[cfg(feature = "axum")]
+pub use extract::{AttributeSource, ConfigCatUser, UserMapping, UserRejection};
+#[cfg(feature = "network")]
+pub use fetch::fetcher::IpPreference;
+pub use fetch::fetcher::{DataGovernanceStatus, FetchStats};
+#[cfg(feature = "test-util")]
+pub use fetch::mock::{MockFetcher, MockResponse, ScriptedFetcher};
+pub use fetch::service::{
+    CircuitState, ClientEvent, ClientMode, FetchCounters, ReadyReport, RefreshOutcome,
+};
+pub use freeze::FreezeSchedule;
+#[cfg(feature = "geo")]
+pub use geo::GeoResolver;
+pub use pool::ClientPool;
+pub use session::EvaluationSession;
+pub use usage::UsageReport;
 
 pub use model::config::{
     Condition, Config, PercentageOption, PrerequisiteFlagCondition, Segment, SegmentCondition,
-    ServedValue, Setting, SettingValue, TargetingRule, UserCondition,
+    ServedValue, Setting, SettingValue, SettingsSnapshot, TargetingRule, UserCondition,
 };
 
+pub use model::traverse::{ConditionContext, RuleContext, SegmentConditionContext};
+
 pub use model::enums::{
-    ClientCacheState, DataGovernance, PrerequisiteFlagComparator, SegmentComparator, SettingType,
-    UserComparator,
+    ClientCacheState, ConfigSource, DataGovernance, PrerequisiteFlagComparator, SegmentComparator,
+    SettingType, UserComparator,
 };
 
 pub use r#override::{
-    behavior::OverrideBehavior, file::FileDataSource, file::SimplifiedConfig, map::MapDataSource,
-    source::OverrideDataSource,
+    behavior::OverrideBehavior,
+    client::ClientDataSource,
+    error::OverrideError,
+    file::FileDataSource,
+    file::SimplifiedConfig,
+    map::MapDataSource,
+    ramp::{Ramp, RampDataSource},
+    report::OverrideReport,
+    source::{AsyncOverrideDataSource, OverrideDataSource, OverrideLoadFuture},
 };
 
 pub use builder::ClientBuilder;
-pub use modes::PollingMode;
+pub use modes::{
+    BucketingStrategy, InitBehavior, KeyLookupMode, LogArea, MemoryMode, NotReadyBehavior,
+    PollingMode, SemverParsing, StaleBehavior, TextNormalization, WhitespaceTrimming,
+};
+pub use options::ConfigCatOptions;
 
 pub use user::{User, UserValue};
+pub use utils::hash_comparison_value;
 pub use value::{Value, ValuePrimitive};
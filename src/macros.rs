@@ -17,3 +17,11 @@ macro_rules! from_val_to_enum_into {
         }
     )*)
 }
+
+macro_rules! filtered_log {
+    ($max_level:expr, $lvl:expr, $($arg:tt)+) => {
+        if $lvl <= $max_level {
+            log::log!($lvl, $($arg)+);
+        }
+    };
+}
@@ -1,14 +1,26 @@
+use crate::constants::{
+    CONFIG_FORMAT_VERSION, INCOMPLETE_PERCENTAGE_OPTIONS_EVENT_ID,
+    INVALID_PERCENTAGE_OPTIONS_EVENT_ID, PKG_VERSION,
+};
+use crate::eval::hashing::HashScheme;
 use crate::model::enums::{
-    PrerequisiteFlagComparator, RedirectMode, SegmentComparator, SettingType, UserComparator,
+    ConfigSource, PrerequisiteFlagComparator, RedirectMode, SegmentComparator, SettingType,
+    UserComparator,
 };
 use crate::r#override::FlagOverrides;
+use crate::utils::parse_semver;
 use crate::value::Value;
-use crate::OverrideBehavior;
+use crate::{OverrideBehavior, OverrideReport};
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use log::{Level, LevelFilter};
+use semver::Version;
+use serde::{Deserialize, Deserializer};
 use std::cmp::min;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
@@ -21,40 +33,92 @@ pub enum Error {
     Parse(String),
 }
 
+/// Describes an invalid percentage option list of a setting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PercentageOptionsIssue {
+    pub setting_key: String,
+    // `None` when the list belongs to the setting itself, not to one of its targeting rules
+    pub rule_index: Option<usize>,
+    // indices of the options with a percentage outside of the [0..100] range
+    pub option_indices: Vec<usize>,
+    pub sum: i128,
+}
+
+impl Display for PercentageOptionsIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "setting '{}'", self.setting_key)?;
+        if let Some(rule_index) = self.rule_index {
+            write!(f, ", targeting rule {rule_index}")?;
+        }
+        if !self.option_indices.is_empty() {
+            let indices = self
+                .option_indices
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(", ");
+            write!(
+                f,
+                ": percentages of options [{indices}] are out of the [0..100] range"
+            )?;
+        }
+        if self.sum != 100 {
+            write!(f, ": percentages sum up to {} instead of 100", self.sum)?;
+        }
+        Ok(())
+    }
+}
+
+impl PercentageOptionsIssue {
+    // the options can still be evaluated when they are only incomplete, the uncovered buckets fall through
+    fn is_incomplete_only(&self) -> bool {
+        self.option_indices.is_empty() && self.sum < 100
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ConfigEntry {
     pub config: Arc<Config>,
-    pub cache_str: String,
+    // the config JSON as downloaded, the cache string is rendered from the components on write
+    pub config_json: ConfigJson,
+    // the hash of the config JSON, so entries with equal (or without) ETags but different contents are told apart
+    pub content_hash: u64,
     pub etag: String,
     pub fetch_time: DateTime<Utc>,
+    pub override_version: u64,
+    pub override_report: Option<Arc<OverrideReport>>,
+    pub source: ConfigSource,
 }
 
 impl Default for ConfigEntry {
     fn default() -> Self {
         Self {
             config: Arc::new(Config::default()),
-            cache_str: String::default(),
+            config_json: ConfigJson::Empty,
+            content_hash: 0,
             etag: String::default(),
             fetch_time: DateTime::<Utc>::MIN_UTC,
+            override_version: 0,
+            override_report: None,
+            source: ConfigSource::None,
         }
     }
 }
 
 impl PartialEq for ConfigEntry {
     fn eq(&self, other: &Self) -> bool {
-        self.etag == other.etag
+        self.etag == other.etag && self.content_hash == other.content_hash
     }
 }
 
 impl ConfigEntry {
     pub fn is_empty(&self) -> bool {
-        self.etag.is_empty() && self.cache_str.is_empty()
+        self.etag.is_empty() && self.config_json == ConfigJson::Empty
     }
 
     pub fn local() -> Self {
         Self {
             etag: "local".to_owned(),
-            cache_str: "local".to_owned(),
             ..ConfigEntry::default()
         }
     }
@@ -64,23 +128,111 @@ impl ConfigEntry {
     }
 
     pub fn set_fetch_time(&mut self, fetch_time: DateTime<Utc>) {
-        let Some(time_index) = self.cache_str.find('\n') else {
-            return;
-        };
-        let without_time = &self.cache_str[time_index + 1..];
-        let Some(etag_index) = without_time.find('\n') else {
-            return;
-        };
-        let config_json = &self.cache_str[time_index + 1 + etag_index + 1..];
         self.fetch_time = fetch_time;
-        self.cache_str = generate_cache_str(fetch_time, &self.etag, config_json);
     }
+
+    /// Renders the string written to the [`crate::ConfigCache`], or `None` when the config JSON was dropped.
+    pub fn cache_str(&self) -> Option<String> {
+        let json = self.config_json.as_str()?;
+        Some(generate_cache_str(self.fetch_time, &self.etag, json))
+    }
+
+    /// Returns whether `cache_str` is the rendered form of this entry, without rendering it.
+    pub fn matches_cache_str(&self, cache_str: &str) -> bool {
+        match split_cache_str(cache_str) {
+            Some((time, etag, json)) => {
+                time.parse::<i64>().ok() == Some(self.fetch_time.timestamp_millis())
+                    && etag == self.etag
+                    && self.json_matches(json)
+            }
+            None => false,
+        }
+    }
+
+    /// Drops the config JSON from memory, its content hash is kept to recognize it later.
+    pub fn drop_config_json(&mut self) {
+        if let ConfigJson::Retained(_) = self.config_json {
+            self.config_json = ConfigJson::Dropped;
+        }
+    }
+
+    /// Restores the dropped config JSON from `cache_str` when it holds the same config JSON version, regardless
+    /// of its fetch time. Returns whether the config JSON is available afterwards.
+    pub fn restore_config_json(&mut self, cache_str: &str) -> bool {
+        if let ConfigJson::Dropped = self.config_json {
+            if let Some((_, etag, json)) = split_cache_str(cache_str) {
+                if etag == self.etag && self.json_matches(json) {
+                    self.config_json = ConfigJson::Retained(json.to_owned());
+                }
+            }
+        }
+        self.config_json.as_str().is_some()
+    }
+
+    fn json_matches(&self, json: &str) -> bool {
+        match self.config_json.as_str() {
+            Some(own) => own == json,
+            None => self.content_hash == content_hash(json),
+        }
+    }
+}
+
+/// Hashes the config JSON, to compare config JSON versions without holding or comparing their text.
+fn content_hash(json: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    json.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The config JSON of a [`ConfigEntry`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ConfigJson {
+    #[default]
+    Empty,
+    Retained(String),
+    // dropped from memory after it got written to the cache, see `ConfigEntry::content_hash`
+    Dropped,
+}
+
+impl ConfigJson {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ConfigJson::Empty => Some(""),
+            ConfigJson::Retained(json) => Some(json),
+            ConfigJson::Dropped => None,
+        }
+    }
+}
+
+/// Splits `cache_str` into the fetch time, the ETag and the config JSON.
+fn split_cache_str(cache_str: &str) -> Option<(&str, &str, &str)> {
+    let mut parts = cache_str.splitn(3, '\n');
+    Some((parts.next()?, parts.next()?, parts.next()?))
+}
+
+/// Removes the line breaks from `etag`, as they separate the components of the cache string.
+fn sanitize_etag(etag: &str) -> String {
+    etag.chars().filter(|c| *c != '\n' && *c != '\r').collect()
 }
 
 pub fn generate_cache_str(time: DateTime<Utc>, etag: &str, json: &str) -> String {
     time.timestamp_millis().to_string() + "\n" + etag + "\n" + json
 }
 
+/// Converts a cache entry written with a previous serialization format to the current format.
+///
+/// Entries that already follow the `fetch time\netag\nconfig JSON` layout are kept as-is,
+/// bare config JSON entries get the Unix epoch as fetch time, so they are considered expired.
+pub fn migrate_cache_str(legacy: &str) -> Option<String> {
+    if entry_from_cached_json(legacy).is_ok() {
+        return Some(legacy.to_owned());
+    }
+    if entry_from_json(legacy, "", DateTime::UNIX_EPOCH).is_ok() {
+        return Some(generate_cache_str(DateTime::UNIX_EPOCH, "", legacy));
+    }
+    None
+}
+
 pub fn entry_from_json(
     json: &str,
     etag: &str,
@@ -90,13 +242,17 @@ pub fn entry_from_json(
         Ok(config) => {
             let mut entry = ConfigEntry {
                 config: Arc::new(config),
-                etag: etag.to_owned(),
+                etag: sanitize_etag(etag),
                 fetch_time,
-                cache_str: generate_cache_str(fetch_time, etag, json),
+                config_json: ConfigJson::Retained(json.to_owned()),
+                content_hash: content_hash(json),
+                override_version: 0,
+                override_report: None,
+                source: ConfigSource::None,
             };
             if let Some(conf_mut) = Arc::get_mut(&mut entry.config) {
                 post_process_config(conf_mut);
-            };
+            }
             Ok(entry)
         }
         Err(err) => Err(Error::Parse(err.to_string())),
@@ -131,12 +287,15 @@ pub fn entry_from_cached_json(cached_json: &str) -> Result<ConfigEntry, Error> {
 }
 
 pub fn post_process_config(config: &mut Config) {
+    validate_percentage_options(config);
     config.salt = match &config.preferences {
         Some(pref) => pref.salt.clone(),
         None => None,
     };
+    config.hash_scheme = HashScheme::for_format_version(CONFIG_FORMAT_VERSION);
     for value in config.settings.values_mut() {
         value.salt.clone_from(&config.salt);
+        value.hash_scheme = config.hash_scheme;
 
         if let Some(rules) = value.targeting_rules.as_mut() {
             for rule in rules {
@@ -157,25 +316,137 @@ pub fn post_process_config(config: &mut Config) {
     }
 }
 
-pub fn process_overrides(entry: &mut ConfigEntry, overrides: Option<&FlagOverrides>) {
+// marks the settings with invalid % options, so only their evaluations fail instead of the whole config
+fn validate_percentage_options(config: &mut Config) {
+    config.percentage_options_issues.clear();
+    for (key, setting) in &mut config.settings {
+        let mut issues = Vec::<PercentageOptionsIssue>::new();
+        issues.extend(percentage_options_issue(
+            key,
+            None,
+            setting.percentage_options.as_ref(),
+        ));
+        for (index, rule) in setting.targeting_rules.iter().flatten().enumerate() {
+            issues.extend(percentage_options_issue(
+                key,
+                Some(index),
+                rule.percentage_options.as_ref(),
+            ));
+        }
+        for issue in &issues {
+            if !issue.is_incomplete_only() {
+                setting
+                    .percentage_options_issue
+                    .get_or_insert(issue.clone());
+            }
+        }
+        config.percentage_options_issues.extend(issues);
+    }
+}
+
+/// Logs the % option lists of `config` that were found invalid or incomplete while parsing it.
+pub fn log_percentage_options_issues(config: &Config, log_level: LevelFilter) {
+    for issue in &config.percentage_options_issues {
+        if issue.is_incomplete_only() {
+            filtered_log!(log_level, Level::Warn, event_id = INCOMPLETE_PERCENTAGE_OPTIONS_EVENT_ID; "The percentages of the % options sum to less than 100 ({issue}), users outside of them get the next matching value. Please check the % options of the setting on the ConfigCat Dashboard.");
+        } else {
+            filtered_log!(log_level, Level::Warn, event_id = INVALID_PERCENTAGE_OPTIONS_EVENT_ID; "The % options are invalid ({issue}), the evaluations of the setting will fail. Please check the % options of the setting on the ConfigCat Dashboard.");
+        }
+    }
+}
+
+fn percentage_options_issue(
+    key: &str,
+    rule_index: Option<usize>,
+    options: Option<&Vec<Arc<PercentageOption>>>,
+) -> Option<PercentageOptionsIssue> {
+    let options = options.filter(|options| !options.is_empty())?;
+    let option_indices = options
+        .iter()
+        .enumerate()
+        .filter(|(_, opt)| !(0..=100).contains(&opt.percentage))
+        .map(|(index, _)| index)
+        .collect::<Vec<usize>>();
+    let sum = options.iter().map(|opt| i128::from(opt.percentage)).sum();
+    if option_indices.is_empty() && sum == 100 {
+        return None;
+    }
+    Some(PercentageOptionsIssue {
+        setting_key: key.to_owned(),
+        rule_index,
+        option_indices,
+        sum,
+    })
+}
+
+pub fn process_overrides(
+    entry: &mut ConfigEntry,
+    overrides: Option<&FlagOverrides>,
+    log_level: LevelFilter,
+) {
     if let Some(ov) = overrides {
-        if matches!(ov.behavior(), OverrideBehavior::LocalOverRemote) {
-            if let Some(conf_mut) = Arc::get_mut(&mut entry.config) {
-                conf_mut.settings.extend(ov.source().settings().clone());
-            };
+        entry.override_version = ov.version();
+        if matches!(ov.behavior(), OverrideBehavior::LocalOnly) {
+            return;
         }
-        if matches!(ov.behavior(), OverrideBehavior::RemoteOverLocal) {
-            if let Some(conf_mut) = Arc::get_mut(&mut entry.config) {
-                let mut local = ov.source().settings().clone();
-                local.extend(conf_mut.settings.clone());
-                conf_mut.settings = local;
-            };
+        // clones the config when it's shared, so the overrides are never skipped
+        let conf_mut = Arc::make_mut(&mut entry.config);
+        let mut local = ov.settings();
+        let report = override_report(&conf_mut.settings, &local, *ov.behavior());
+        log_override_report(&report, log_level);
+        if matches!(ov.behavior(), OverrideBehavior::LocalOverRemote) {
+            conf_mut.settings.extend(local);
+        } else {
+            local.extend(conf_mut.settings.drain());
+            conf_mut.settings = local;
         }
+        entry.override_report = Some(Arc::new(report));
+    }
+}
+
+fn override_report(
+    remote: &HashMap<String, Setting>,
+    local: &HashMap<String, Setting>,
+    behavior: OverrideBehavior,
+) -> OverrideReport {
+    let (mut conflicts, mut added): (Vec<String>, Vec<String>) = local
+        .keys()
+        .cloned()
+        .partition(|key| remote.contains_key(key));
+    conflicts.sort();
+    added.sort();
+    let (overridden, ignored) = if matches!(behavior, OverrideBehavior::LocalOverRemote) {
+        (conflicts, Vec::new())
+    } else {
+        (Vec::new(), conflicts)
+    };
+    OverrideReport {
+        behavior,
+        overridden,
+        ignored,
+        added,
     }
 }
 
+fn log_override_report(report: &OverrideReport, log_level: LevelFilter) {
+    let (conflicts, winner) = if report.overridden.is_empty() {
+        (&report.ignored, "config JSON")
+    } else {
+        (&report.overridden, "flag overrides")
+    };
+    if conflicts.is_empty() {
+        return;
+    }
+    let keys = conflicts
+        .iter()
+        .map(|key| format!("'{key}'"))
+        .collect::<Vec<String>>()
+        .join(", ");
+    filtered_log!(log_level, Level::Info, "Settings {keys} are defined both in the config JSON and the flag overrides, the values of the {winner} are used ({:?}).", report.behavior);
+}
+
 /// Describes a ConfigCat config JSON.
-#[derive(Deserialize, Debug, Default)]
+#[derive(Deserialize, Debug, Default, Clone)]
 pub struct Config {
     /// The map of settings.
     #[serde(rename = "f")]
@@ -186,12 +457,87 @@ pub struct Config {
     /// The salt that was used to hash sensitive comparison values.
     #[serde(skip)]
     pub salt: Option<String>,
+    #[serde(skip)]
+    pub(crate) hash_scheme: HashScheme,
 
     #[serde(rename = "p")]
     pub(crate) preferences: Option<Preferences>,
+    // logged once per config JSON version by [`log_percentage_options_issues`], not on every parse
+    #[serde(skip)]
+    pub(crate) percentage_options_issues: Vec<PercentageOptionsIssue>,
+}
+
+impl Config {
+    /// Creates a copy of this config where the given `changes` replace the settings with the same keys
+    /// or get added as new settings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use configcat::{Config, Setting, Value};
+    ///
+    /// let config = Config::default();
+    /// let proposed = config.patched(HashMap::from([
+    ///     ("flag".to_owned(), Setting::from(&Value::Bool(true))),
+    /// ]));
+    ///
+    /// assert!(proposed.settings.contains_key("flag"));
+    /// ```
+    pub fn patched(&self, changes: HashMap<String, Setting>) -> Config {
+        let mut settings = self.settings.clone();
+        for (key, mut setting) in changes {
+            setting.salt.clone_from(&self.salt);
+            setting.hash_scheme = self.hash_scheme;
+            settings.insert(key, setting);
+        }
+        Config {
+            settings,
+            segments: self.segments.clone(),
+            salt: self.salt.clone(),
+            hash_scheme: self.hash_scheme,
+            preferences: None,
+            percentage_options_issues: Vec::new(),
+        }
+    }
+
+    /// Returns the minimum SDK version required by the preferences of the config JSON,
+    /// or `None` when this SDK's version satisfies it.
+    pub(crate) fn required_sdk_upgrade(&self) -> Option<&Version> {
+        let required = self.preferences.as_ref()?.min_sdk_version.as_ref()?;
+        let current = parse_semver(PKG_VERSION).ok()?;
+        (*required > current).then_some(required)
+    }
+}
+
+/// A read-only view of the settings of a [`Config`], sharing the config instead of copying the settings.
+///
+/// Dereferences to the map of settings keyed by their keys in the config JSON.
+#[derive(Debug, Clone)]
+pub struct SettingsSnapshot {
+    config: Arc<Config>,
+}
+
+impl SettingsSnapshot {
+    pub(crate) fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+
+    /// Returns the config the settings belong to.
+    pub fn config(&self) -> &Arc<Config> {
+        &self.config
+    }
+}
+
+impl Deref for SettingsSnapshot {
+    type Target = HashMap<String, Setting>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.config.settings
+    }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct Preferences {
     #[serde(rename = "u")]
     pub url: Option<String>,
@@ -199,10 +545,25 @@ pub struct Preferences {
     pub redirect: Option<RedirectMode>,
     #[serde(rename = "s")]
     pub salt: Option<String>,
+    // the minimum SDK version the config JSON was authored for, see `Config::required_sdk_upgrade`
+    #[serde(rename = "m", default, deserialize_with = "deserialize_version")]
+    pub min_sdk_version: Option<Version>,
+}
+
+/// Reads an optional semantic version, ignoring values of unknown shape (e.g. written by a newer
+/// config JSON format) instead of failing the parsing of the whole config JSON.
+fn deserialize_version<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Version>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value
+        .as_ref()
+        .and_then(serde_json::Value::as_str)
+        .and_then(|version| parse_semver(version).ok()))
 }
 
 /// Describes a feature flag or setting.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct Setting {
     /// The value that is returned when none of the targeting rules or percentage options yield a result.
     #[serde(rename = "v")]
@@ -225,6 +586,23 @@ pub struct Setting {
 
     #[serde(skip)]
     pub(crate) salt: Option<String>,
+    #[serde(skip)]
+    pub(crate) hash_scheme: HashScheme,
+    // the first invalid % option list of the setting, which fails its evaluations
+    #[serde(skip)]
+    pub(crate) percentage_options_issue: Option<PercentageOptionsIssue>,
+}
+
+impl Setting {
+    /// Returns the type of the setting, the same as [`Setting::setting_type`](#structfield.setting_type).
+    pub fn setting_type(&self) -> SettingType {
+        self.setting_type
+    }
+
+    /// Indicates whether the setting is a feature flag (its type is [`SettingType::Bool`]).
+    pub fn is_feature_flag(&self) -> bool {
+        self.setting_type == SettingType::Bool
+    }
 }
 
 impl From<&Value> for Setting {
@@ -237,11 +615,13 @@ impl From<&Value> for Setting {
             percentage_attribute: None,
             targeting_rules: None,
             salt: None,
+            hash_scheme: HashScheme::default(),
+            percentage_options_issue: None,
         }
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq)]
 /// Describes a segment.
 pub struct Segment {
     /// The name of the segment.
@@ -252,7 +632,7 @@ pub struct Segment {
     pub conditions: Vec<UserCondition>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq)]
 /// Describes a targeting rule.
 pub struct TargetingRule {
     /// The value associated with the targeting rule or nil if the targeting rule has percentage options THEN part.
@@ -266,7 +646,7 @@ pub struct TargetingRule {
     pub percentage_options: Option<Vec<Arc<PercentageOption>>>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq)]
 /// Describes a condition that can contain either a [`UserCondition`], a [`SegmentCondition`], or a [`PrerequisiteFlagCondition`].
 pub struct Condition {
     /// Describes a condition that works with User Object attributes.
@@ -280,7 +660,7 @@ pub struct Condition {
     pub prerequisite_flag_condition: Option<PrerequisiteFlagCondition>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq)]
 /// Describes a condition that is based on a [`crate::User`] attribute.
 pub struct UserCondition {
     /// The value that the User Object attribute is compared to, when the comparator works with a single text comparison value.
@@ -358,7 +738,7 @@ impl Display for UserCondition {
 }
 
 /// Describes a condition that is based on a [`Segment`].
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq)]
 pub struct SegmentCondition {
     /// Identifies the segment that the condition is based on.
     #[serde(rename = "s")]
@@ -382,7 +762,7 @@ impl Display for SegmentCondition {
 }
 
 /// Describes a condition that is based on a prerequisite flag.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq)]
 pub struct PrerequisiteFlagCondition {
     /// The key of the prerequisite flag that the condition is based on.
     #[serde(rename = "f")]
@@ -406,7 +786,7 @@ impl Display for PrerequisiteFlagCondition {
 }
 
 /// Describes a percentage option.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq)]
 pub struct PercentageOption {
     /// The served value of the percentage option.
     #[serde(rename = "v")]
@@ -420,7 +800,7 @@ pub struct PercentageOption {
 }
 
 /// Describes a setting value along with related data.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq)]
 pub struct ServedValue {
     /// The value associated with the targeting rule.
     #[serde(rename = "v")]
@@ -431,7 +811,7 @@ pub struct ServedValue {
 }
 
 /// Describes a setting's value.
-#[derive(Deserialize, Clone, Debug, Default)]
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct SettingValue {
     /// Holds a bool feature flag's value.
     #[serde(rename = "b")]
@@ -448,7 +828,7 @@ pub struct SettingValue {
 }
 
 impl SettingValue {
-    pub(crate) fn as_val(&self, setting_type: &SettingType) -> Option<Value> {
+    pub(crate) fn as_val(&self, setting_type: SettingType) -> Option<Value> {
         match setting_type {
             SettingType::Bool => {
                 if let Some(bool_val) = self.bool_val {
@@ -519,9 +899,15 @@ impl Display for SettingValue {
 
 #[cfg(test)]
 mod model_tests {
-    use crate::model::config::entry_from_cached_json;
+    use crate::model::config::{
+        entry_from_cached_json, entry_from_json, process_overrides, PercentageOptionsIssue,
+    };
+    use crate::r#override::FlagOverrides;
+    use crate::{evaluate_config, MapDataSource, OverrideBehavior, User, Value};
     use chrono::{DateTime, Utc};
+    use log::LevelFilter;
     use std::str::FromStr;
+    use std::sync::Arc;
 
     static CONFIG_JSON: &str = r#"{"p":{"u":"https://cdn-global.configcat.com","r":0,"s":"FUkC6RADjzF0vXrDSfJn7BcEBag9afw1Y6jkqjMP9BA="},"f":{"testKey":{"t":1,"v":{"s": "testValue"}}}}"#;
 
@@ -533,7 +919,106 @@ mod model_tests {
         assert_eq!(result.config.settings.len(), 1);
         assert_eq!(result.etag, "test-etag");
         assert_eq!(result.fetch_time, exp_time);
-        assert_eq!(result.cache_str, payload);
+        assert_eq!(result.cache_str(), Some(payload.clone()));
+        assert!(result.matches_cache_str(payload.as_str()));
+    }
+
+    #[test]
+    fn required_sdk_upgrade() {
+        let config_with = |min: &str| {
+            let json = format!(
+                r#"{{"p":{{"u":"https://cdn-global.configcat.com","r":0,"m":{min}}},"f":{{}}}}"#
+            );
+            entry_from_json(json.as_str(), "", Utc::now())
+                .unwrap()
+                .config
+        };
+        assert_eq!(
+            config_with(r#""999.0.0""#)
+                .required_sdk_upgrade()
+                .map(ToString::to_string),
+            Some("999.0.0".to_owned())
+        );
+        assert!(config_with(r#""0.0.1""#).required_sdk_upgrade().is_none());
+        assert!(config_with(r#""invalid""#).required_sdk_upgrade().is_none());
+        assert!(config_with(r#"{"rust":"999.0.0"}"#)
+            .required_sdk_upgrade()
+            .is_none());
+        assert!(config_with("null").required_sdk_upgrade().is_none());
+    }
+
+    #[test]
+    fn process_overrides_on_shared_config() {
+        for (behavior, expected) in [
+            (OverrideBehavior::LocalOverRemote, "overridden"),
+            (OverrideBehavior::RemoteOverLocal, "testValue"),
+        ] {
+            let overrides = FlagOverrides::new(
+                Box::new(MapDataSource::from([
+                    ("testKey", Value::String("overridden".to_owned())),
+                    ("localKey", Value::Bool(true)),
+                ])),
+                behavior,
+            );
+            let mut entry = entry_from_json(CONFIG_JSON, "etag", Utc::now()).unwrap();
+            let shared = Arc::clone(&entry.config);
+
+            process_overrides(&mut entry, Some(&overrides), LevelFilter::Off);
+
+            assert!(!Arc::ptr_eq(&shared, &entry.config));
+            assert_eq!(shared.settings.len(), 1);
+            assert_eq!(entry.config.settings.len(), 2);
+            assert_eq!(
+                entry.config.settings["testKey"].value.string_val,
+                Some(expected.to_owned())
+            );
+            assert!(entry.override_report.is_some());
+        }
+    }
+
+    #[test]
+    fn invalid_percentage_options() {
+        let json = r#"{"f":{
+            "valid":{"t":1,"v":{"s":"a"},"p":[{"p":40,"v":{"s":"a"}},{"p":60,"v":{"s":"b"}}]},
+            "incomplete":{"t":1,"v":{"s":"a"},"p":[{"p":40,"v":{"s":"a"}},{"p":55,"v":{"s":"b"}}]},
+            "outOfRange":{"t":1,"v":{"s":"a"},"p":[{"p":-20,"v":{"s":"a"}},{"p":120,"v":{"s":"b"}}]},
+            "wrongSum":{"t":1,"v":{"s":"a"},"r":[{"p":[{"p":9223372036854775807,"v":{"s":"a"}},{"p":1,"v":{"s":"b"}}]}]}
+        }}"#;
+        let entry = entry_from_json(json, "", Utc::now()).unwrap();
+        let settings = &entry.config.settings;
+
+        assert_eq!(settings.len(), 4);
+        assert_eq!(entry.config.percentage_options_issues.len(), 3);
+        assert!(settings["valid"].percentage_options_issue.is_none());
+        assert!(settings["incomplete"].percentage_options_issue.is_none());
+        assert_eq!(
+            settings["outOfRange"].percentage_options_issue,
+            Some(PercentageOptionsIssue {
+                setting_key: "outOfRange".to_owned(),
+                rule_index: None,
+                option_indices: vec![0, 1],
+                sum: 100,
+            })
+        );
+        assert_eq!(
+            settings["wrongSum"].percentage_options_issue,
+            Some(PercentageOptionsIssue {
+                setting_key: "wrongSum".to_owned(),
+                rule_index: Some(0),
+                option_indices: vec![0],
+                sum: 9_223_372_036_854_775_808,
+            })
+        );
+
+        let user = User::new("id");
+        let valid = evaluate_config(&entry.config, "valid", Some(&user));
+        assert!(valid.error.is_none());
+        let invalid = evaluate_config(&entry.config, "outOfRange", Some(&user));
+        assert!(invalid.is_default_value);
+        assert_eq!(
+            invalid.error.unwrap().message,
+            "Failed to evaluate setting 'outOfRange' (% options are invalid, setting 'outOfRange': percentages of options [0, 1] are out of the [0..100] range)"
+        );
     }
 
     #[test]
@@ -546,14 +1031,88 @@ mod model_tests {
         assert_eq!(entry.fetch_time, updated_time);
         assert_eq!(entry.etag, "test-etag");
         assert_eq!(
-            entry.cache_str,
-            format!(
+            entry.cache_str(),
+            Some(format!(
                 "{}\ntest-etag\n{CONFIG_JSON}",
                 updated_time.timestamp_millis()
-            )
+            ))
         );
     }
 
+    #[test]
+    fn weird_etags() {
+        let fetch_time = DateTime::from_timestamp_millis(1_686_756_435_844).unwrap();
+        for (etag, sanitized) in [
+            ("", ""),
+            ("W/\"abc\"", "W/\"abc\""),
+            ("multi\nline\r\netag", "multilineetag"),
+            ("\n", ""),
+            ("ünïcødé etag", "ünïcødé etag"),
+        ] {
+            let entry = entry_from_json(CONFIG_JSON, etag, fetch_time).unwrap();
+            assert_eq!(entry.etag, sanitized);
+
+            let cache_str = entry.cache_str().unwrap();
+            assert!(entry.matches_cache_str(cache_str.as_str()));
+            let parsed = entry_from_cached_json(cache_str.as_str()).unwrap();
+            assert_eq!(parsed.etag, sanitized);
+            assert_eq!(parsed.fetch_time, fetch_time);
+            assert_eq!(parsed.config_json.as_str(), Some(CONFIG_JSON));
+        }
+    }
+
+    #[test]
+    fn dropped_config_json() {
+        let fetch_time = DateTime::from_timestamp_millis(1_686_756_435_844).unwrap();
+        let mut entry = entry_from_json(CONFIG_JSON, "etag", fetch_time).unwrap();
+        let cache_str = entry.cache_str().unwrap();
+        entry.drop_config_json();
+        assert!(!entry.is_empty());
+        assert_eq!(entry.cache_str(), None);
+        assert!(entry.matches_cache_str(cache_str.as_str()));
+
+        entry.set_fetch_time(Utc::now());
+        assert!(!entry.matches_cache_str(cache_str.as_str()));
+        assert!(!entry.restore_config_json("1686756435844\nother-etag\n{\"f\":{}}"));
+        assert!(!entry.restore_config_json("1686756435844\netag\n{\"f\":{}}"));
+        assert!(entry.restore_config_json(cache_str.as_str()));
+        assert_eq!(entry.config_json.as_str(), Some(CONFIG_JSON));
+    }
+
+    #[test]
+    fn entry_equality() {
+        let other_json = r#"{"f":{}}"#;
+        let entry = entry_from_json(CONFIG_JSON, "", Utc::now()).unwrap();
+        assert_eq!(entry, entry_from_json(CONFIG_JSON, "", Utc::now()).unwrap());
+        assert_ne!(entry, entry_from_json(other_json, "", Utc::now()).unwrap());
+        assert_ne!(
+            entry,
+            entry_from_json(CONFIG_JSON, "etag", Utc::now()).unwrap()
+        );
+
+        let mut dropped = entry.clone();
+        dropped.drop_config_json();
+        assert_eq!(entry, dropped);
+        assert_ne!(
+            entry_from_json(other_json, "etag", Utc::now()).unwrap(),
+            entry_from_json(CONFIG_JSON, "etag", Utc::now()).unwrap()
+        );
+    }
+
+    #[test]
+    fn malformed_cache_str() {
+        let entry = entry_from_json(CONFIG_JSON, "etag", Utc::now()).unwrap();
+        for malformed in [
+            "",
+            "1686756435844",
+            "1686756435844\netag",
+            "not-a-time\netag\n{}",
+        ] {
+            assert!(!entry.matches_cache_str(malformed));
+            assert!(entry_from_cached_json(malformed).is_err());
+        }
+    }
+
     #[test]
     fn parse_invalid() {
         match entry_from_cached_json("") {
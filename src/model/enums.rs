@@ -3,6 +3,7 @@ use serde_repr::Deserialize_repr;
 use std::fmt::{Display, Formatter};
 
 /// Describes the internal state of the [`crate::Client`].
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ClientCacheState {
     /// The SDK has no feature flag data neither from the cache nor from the ConfigCat CDN.
     NoFlagData,
@@ -14,6 +15,21 @@ pub enum ClientCacheState {
     HasUpToDateFlagData,
 }
 
+/// Describes where the config JSON held by the [`crate::Client`] was obtained from.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ConfigSource {
+    /// The SDK has no config JSON yet.
+    #[default]
+    None,
+    /// The config JSON was read from the [`crate::ConfigCache`] or preloaded with
+    /// [`crate::ClientBuilder::preload_cache_entry`].
+    Cache,
+    /// The config JSON was downloaded from (or confirmed to be up-to-date by) the ConfigCat CDN.
+    Http,
+    /// The SDK runs with local-only flag overrides.
+    Override,
+}
+
 /// Describes the location of your feature flag and setting data within the ConfigCat CDN.
 #[derive(Clone, PartialEq, Debug)]
 pub enum DataGovernance {
@@ -32,7 +48,7 @@ pub enum RedirectMode {
 }
 
 /// The type of the feature flag or setting.
-#[derive(Debug, Clone, Deserialize_repr)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize_repr)]
 #[repr(u8)]
 pub enum SettingType {
     /// The on/off type (feature flag).
@@ -154,12 +170,16 @@ pub enum UserComparator {
     /// Checks whether the comparison attribute is not equal to the comparison value (where the comparison is performed using the salted SHA256 hashes of the values).
     NotEqHashed = 21,
     /// Checks whether the comparison attribute starts with any of the comparison values (where the comparison is performed using the salted SHA256 hashes of the values).
+    /// The lengths of the comparison values are in UTF-8 bytes, so the hashed prefix of the attribute may split a multi-byte character.
     StartsWithAnyOfHashed = 22,
     /// Checks whether the comparison attribute does not start with any of the comparison values (where the comparison is performed using the salted SHA256 hashes of the values).
+    /// See [`UserComparator::StartsWithAnyOfHashed`] for how the prefix is sliced.
     NotStartsWithAnyOfHashed = 23,
     /// Checks whether the comparison attribute ends with any of the comparison values (where the comparison is performed using the salted SHA256 hashes of the values).
+    /// The lengths of the comparison values are in UTF-8 bytes, so the hashed suffix of the attribute may split a multi-byte character.
     EndsWithAnyOfHashed = 24,
     /// Checks whether the comparison attribute does not end with any of the comparison values (where the comparison is performed using the salted SHA256 hashes of the values).
+    /// See [`UserComparator::EndsWithAnyOfHashed`] for how the suffix is sliced.
     NotEndsWithAnyOfHashed = 25,
     /// Checks whether the comparison attribute interpreted as a string list contains any of the comparison values (where the comparison is performed using the salted SHA256 hashes of the values).
     ArrayContainsAnyOfHashed = 26,
@@ -0,0 +1,190 @@
+use crate::{Condition, Config, Segment, Setting, TargetingRule, UserCondition};
+use std::sync::Arc;
+
+/// A targeting rule along with the feature flag or setting it belongs to.
+pub struct RuleContext<'a> {
+    /// The key of the feature flag or setting that owns the targeting rule.
+    pub setting_key: &'a str,
+    /// The feature flag or setting that owns the targeting rule.
+    pub setting: &'a Setting,
+    /// The position of the targeting rule within the setting's targeting rules.
+    pub rule_index: usize,
+    /// The targeting rule.
+    pub rule: &'a Arc<TargetingRule>,
+}
+
+/// A targeting rule condition along with the targeting rule and setting it belongs to.
+pub struct ConditionContext<'a> {
+    /// The key of the feature flag or setting that owns the condition.
+    pub setting_key: &'a str,
+    /// The feature flag or setting that owns the condition.
+    pub setting: &'a Setting,
+    /// The position of the targeting rule within the setting's targeting rules.
+    pub rule_index: usize,
+    /// The targeting rule that owns the condition.
+    pub rule: &'a Arc<TargetingRule>,
+    /// The position of the condition within the targeting rule's conditions.
+    pub condition_index: usize,
+    /// The condition.
+    pub condition: &'a Condition,
+}
+
+/// A segment condition along with the segment it belongs to.
+pub struct SegmentConditionContext<'a> {
+    /// The position of the segment within the config's segments.
+    pub segment_index: usize,
+    /// The segment that owns the condition.
+    pub segment: &'a Arc<Segment>,
+    /// The position of the condition within the segment's conditions.
+    pub condition_index: usize,
+    /// The condition.
+    pub condition: &'a UserCondition,
+}
+
+impl Config {
+    /// Returns an iterator over every targeting rule of every feature flag or setting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Config;
+    ///
+    /// fn rules_without_conditions(config: &Config) -> Vec<String> {
+    ///     config
+    ///         .targeting_rules()
+    ///         .filter(|ctx| ctx.rule.conditions.as_ref().is_none_or(Vec::is_empty))
+    ///         .map(|ctx| format!("{}#{}", ctx.setting_key, ctx.rule_index))
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn targeting_rules(&self) -> impl Iterator<Item = RuleContext<'_>> {
+        self.settings.iter().flat_map(|(key, setting)| {
+            setting
+                .targeting_rules
+                .iter()
+                .flatten()
+                .enumerate()
+                .map(move |(rule_index, rule)| RuleContext {
+                    setting_key: key.as_str(),
+                    setting,
+                    rule_index,
+                    rule,
+                })
+        })
+    }
+
+    /// Returns an iterator over every condition of every targeting rule, along with its parent rule and setting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use configcat::Config;
+    ///
+    /// fn used_segment_indexes(config: &Config) -> HashSet<usize> {
+    ///     config
+    ///         .conditions()
+    ///         .filter_map(|ctx| ctx.condition.segment_condition.as_ref())
+    ///         .map(|cond| cond.index)
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn conditions(&self) -> impl Iterator<Item = ConditionContext<'_>> {
+        self.targeting_rules().flat_map(|rule_ctx| {
+            rule_ctx.rule.conditions.iter().flatten().enumerate().map(
+                move |(condition_index, condition)| ConditionContext {
+                    setting_key: rule_ctx.setting_key,
+                    setting: rule_ctx.setting,
+                    rule_index: rule_ctx.rule_index,
+                    rule: rule_ctx.rule,
+                    condition_index,
+                    condition,
+                },
+            )
+        })
+    }
+
+    /// Returns an iterator over every condition of every segment, along with its parent segment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Config;
+    ///
+    /// fn segment_attributes(config: &Config) -> Vec<String> {
+    ///     config
+    ///         .segment_conditions()
+    ///         .map(|ctx| format!("{}: {}", ctx.segment.name, ctx.condition.comp_attr))
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn segment_conditions(&self) -> impl Iterator<Item = SegmentConditionContext<'_>> {
+        self.segments
+            .iter()
+            .flatten()
+            .enumerate()
+            .flat_map(|(segment_index, segment)| {
+                segment
+                    .conditions
+                    .iter()
+                    .enumerate()
+                    .map(
+                        move |(condition_index, condition)| SegmentConditionContext {
+                            segment_index,
+                            segment,
+                            condition_index,
+                            condition,
+                        },
+                    )
+            })
+    }
+}
+
+#[cfg(test)]
+mod traverse_tests {
+    use crate::model::config::entry_from_json;
+    use chrono::Utc;
+    use std::fs;
+
+    #[test]
+    fn traverse() {
+        let json = fs::read_to_string("tests/data/test_override_segments_v6.json").unwrap();
+        let entry = entry_from_json(json.as_str(), "", Utc::now()).unwrap();
+        let config = entry.config;
+
+        let rules = config.targeting_rules().collect::<Vec<_>>();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].setting_key, "developerAndBetaUserSegment");
+        assert_eq!(rules[0].rule_index, 0);
+
+        let conditions = config
+            .conditions()
+            .map(|ctx| {
+                (
+                    ctx.setting_key,
+                    ctx.condition_index,
+                    ctx.condition.segment_condition.as_ref().unwrap().index,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            conditions,
+            vec![
+                ("developerAndBetaUserSegment", 0, 1),
+                ("developerAndBetaUserSegment", 1, 0),
+            ]
+        );
+
+        let segment_conditions = config
+            .segment_conditions()
+            .map(|ctx| (ctx.segment.name.clone(), ctx.condition.comp_attr.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            segment_conditions,
+            vec![
+                ("Beta Users".to_owned(), "Email".to_owned()),
+                ("Developers".to_owned(), "Email".to_owned()),
+            ]
+        );
+    }
+}
@@ -1,7 +1,10 @@
+use log::LevelFilter;
 use std::time::Duration;
 
 /// Describes the available polling modes.
 ///
+/// [`PollingMode::AutoPoll`] and [`PollingMode::LazyLoad`] are only available with the `network` feature.
+///
 /// # Examples
 ///
 /// ```rust
@@ -12,7 +15,7 @@ use std::time::Duration;
 /// let lazy_load = PollingMode::LazyLoad(Duration::from_secs(60));
 /// let manual = PollingMode::Manual;
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum PollingMode {
     /// Specifies how frequently the locally cached config will be refreshed by fetching the latest version from the remote server.
     ///
@@ -25,6 +28,7 @@ pub enum PollingMode {
     /// let builder = Client::builder("sdk-key")
     ///     .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)));
     /// ```
+    #[cfg(feature = "network")]
     AutoPoll(Duration),
     /// Specifies how long the locally cached config can be used before refreshing it again by fetching the latest version from the remote server.
     ///
@@ -37,6 +41,7 @@ pub enum PollingMode {
     /// let builder = Client::builder("sdk-key")
     ///     .polling_mode(PollingMode::LazyLoad(Duration::from_secs(60)));
     /// ```
+    #[cfg(feature = "network")]
     LazyLoad(Duration),
     /// In this polling mode the SDK will refresh only when [`crate::Client::refresh`] is called.
     ///
@@ -53,11 +58,307 @@ pub enum PollingMode {
 }
 
 impl PollingMode {
+    /// Returns the poll interval of [`PollingMode::AutoPoll`].
+    pub(crate) fn poll_interval(&self) -> Option<Duration> {
+        match self {
+            #[cfg(feature = "network")]
+            PollingMode::AutoPoll(interval) => Some(*interval),
+            _ => None,
+        }
+    }
+
+    /// Returns the cache TTL of [`PollingMode::LazyLoad`].
+    pub(crate) fn cache_ttl(&self) -> Option<Duration> {
+        match self {
+            #[cfg(feature = "network")]
+            PollingMode::LazyLoad(cache_ttl) => Some(*cache_ttl),
+            _ => None,
+        }
+    }
+
     pub(crate) fn mode_identifier(&self) -> &str {
         match self {
+            #[cfg(feature = "network")]
             PollingMode::AutoPoll(_) => "a",
+            #[cfg(feature = "network")]
             PollingMode::LazyLoad(_) => "l",
             PollingMode::Manual => "m",
         }
     }
 }
+
+/// Describes how the SDK looks up feature flag and setting keys during evaluation.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{Client, KeyLookupMode};
+///
+/// let builder = Client::builder("sdk-key")
+///     .key_lookup_mode(KeyLookupMode::CaseInsensitive);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum KeyLookupMode {
+    /// Keys must match exactly, including their case.
+    #[default]
+    Exact,
+    /// When there's no exact match, keys are matched ignoring their case.
+    ///
+    /// If more than one key matches the requested key this way, the lookup is considered
+    /// ambiguous and the evaluation fails with [`crate::ErrorKind::SettingKeyMissing`].
+    CaseInsensitive,
+}
+
+/// Describes how the SDK normalizes strings before the text-based comparators (`EQUALS`, `IS ONE OF`,
+/// `STARTS WITH ANY OF`, `CONTAINS ANY OF`, `ARRAY CONTAINS ANY OF`, etc.) compare them.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{Client, TextNormalization};
+///
+/// let builder = Client::builder("sdk-key")
+///     .text_normalization(TextNormalization::Nfc);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TextNormalization {
+    /// Strings are compared byte-wise, without normalization.
+    #[default]
+    None,
+    /// User attribute values and comparison values are converted to Unicode Normalization Form C (NFC)
+    /// before comparison, so e.g. a precomposed `é` equals an `e` followed by a combining acute accent,
+    /// like in the SDKs that compare text culture-invariantly.
+    ///
+    /// Confidential comparison values are hashed by the ConfigCat Dashboard as entered, so the user attribute
+    /// values only match them when they were entered in NFC as well.
+    Nfc,
+}
+
+/// Describes whether the SDK removes the leading and trailing whitespace of the strings compared by the
+/// text-based comparators (`EQUALS`, `IS ONE OF`, `STARTS WITH ANY OF`, `CONTAINS ANY OF`,
+/// `ARRAY CONTAINS ANY OF`, etc.).
+///
+/// By default, the text-based comparators compare the strings as they are, like all ConfigCat SDKs do
+/// (only the number, date and semantic version comparators trim their input).
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{Client, WhitespaceTrimming};
+///
+/// let builder = Client::builder("sdk-key")
+///     .whitespace_trimming(WhitespaceTrimming::UserAttributes);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum WhitespaceTrimming {
+    /// Strings are compared as they are.
+    #[default]
+    None,
+    /// The user attribute values are trimmed before comparison (also before hashing them for the
+    /// confidential comparators).
+    UserAttributes,
+    /// Both the user attribute values and the comparison values are trimmed before comparison.
+    ///
+    /// Confidential comparison values are hashed by the ConfigCat Dashboard, so they are compared as entered.
+    All,
+}
+
+/// Describes how the SDK assigns users to the buckets of percentage options.
+///
+/// **Warning:** only [`BucketingStrategy::Hash`] is consistent with the ConfigCat Dashboard and the other ConfigCat
+/// SDKs. With any other strategy, the same user may be served a different percentage option by this client than
+/// by other SDKs, the ConfigCat Proxy or the Dashboard's test evaluations.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{BucketingStrategy, Client};
+///
+/// let builder = Client::builder("sdk-key")
+///     .bucketing_strategy(BucketingStrategy::NumericModulo);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum BucketingStrategy {
+    /// The bucket is computed from the SHA-1 hash of the setting key and the percentage evaluation attribute,
+    /// like in all ConfigCat SDKs.
+    #[default]
+    Hash,
+    /// When the percentage evaluation attribute is a whole number (e.g. a numeric tenant ID), the bucket is
+    /// the attribute modulo 100, for interoperability with legacy systems bucketing the same way.
+    /// Other attribute values fall back to [`BucketingStrategy::Hash`].
+    NumericModulo,
+}
+
+/// Describes how strictly the semantic version comparators parse the user attribute values and comparison values.
+///
+/// By default, the versions must be valid [Semantic Versioning 2.0.0](https://semver.org/) strings,
+/// like in all ConfigCat SDKs.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{Client, SemverParsing};
+///
+/// let builder = Client::builder("sdk-key")
+///     .semver_parsing(SemverParsing::Lenient);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SemverParsing {
+    /// Only valid semantic versions are accepted, the build metadata is ignored.
+    #[default]
+    Strict,
+    /// A leading `v` / `V` (e.g. `v1.2.3`) and whitespace around the build metadata are also accepted,
+    /// and the build metadata of [`crate::UserValue::SemVer`] attribute values is ignored too.
+    /// A warning is logged each time the leniency was needed to parse a version.
+    Lenient,
+}
+
+/// Describes whether the downloaded config JSON is kept in memory next to the parsed config.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{Client, MemoryMode};
+///
+/// let builder = Client::builder("sdk-key")
+///     .memory_mode(MemoryMode::Bounded);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum MemoryMode {
+    /// The config JSON is kept in memory, so it can be written to the cache and exported at any time.
+    #[default]
+    Standard,
+    /// The config JSON is dropped from memory once it's written to the cache, so large configs are held only
+    /// once, in their parsed form. When the config JSON is needed again, it's read back from the cache.
+    Bounded,
+}
+
+/// Describes what the SDK does when a feature flag is evaluated in [`PollingMode::AutoPoll`]
+/// before the first fetch of the config JSON has completed.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use configcat::{Client, InitBehavior};
+///
+/// let builder = Client::builder("sdk-key")
+///     .init_behavior(InitBehavior::BlockUntilFirstFetch(Duration::from_secs(5)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InitBehavior {
+    /// Waits for the first fetch to complete for a maximum of the given duration.
+    /// When the wait times out, the evaluation uses the cached config JSON if there's any.
+    BlockUntilFirstFetch(Duration),
+    /// Uses the cached config JSON right away (even when it's expired) without waiting for the first fetch.
+    /// When the cache is empty, the evaluation falls back to the default value.
+    ServeCacheImmediately,
+    /// Uses the cached config JSON right away when there's any, otherwise waits for the first fetch to complete.
+    RequireCache,
+}
+
+/// Describes what the SDK does when a feature flag is evaluated in [`PollingMode::Manual`]
+/// before any config JSON is available (e.g. [`crate::Client::refresh`] wasn't called yet and the cache is empty).
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{Client, NotReadyBehavior, PollingMode};
+///
+/// let builder = Client::builder("sdk-key")
+///     .polling_mode(PollingMode::Manual)
+///     .not_ready_behavior(NotReadyBehavior::Error);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum NotReadyBehavior {
+    /// The evaluation returns the default value and reports [`crate::ErrorKind::ConfigJsonNotAvailable`].
+    #[default]
+    UseDefault,
+    /// The evaluation returns the default value and reports [`crate::ErrorKind::NotReady`].
+    Error,
+    /// The first evaluation fetches the config JSON once, as if [`crate::Client::refresh`] was called.
+    RefreshOnce,
+}
+
+/// Describes what the SDK does when the config JSON is older than the age set with [`crate::ClientBuilder::max_config_age`]
+/// (e.g. because the ConfigCat CDN has been unreachable for a long time).
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use configcat::{Client, StaleBehavior};
+///
+/// let builder = Client::builder("sdk-key")
+///     .max_config_age(Duration::from_secs(24 * 60 * 60), StaleBehavior::Error);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StaleBehavior {
+    /// The evaluations return the default value and a warning is logged.
+    ServeDefaults,
+    /// The evaluations return the default value and report [`crate::ErrorKind::ConfigJsonTooOld`].
+    Error,
+}
+
+/// Identifies an area of the SDK whose log messages can be filtered separately with [`crate::ClientBuilder::log_level`].
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{Client, LogArea};
+/// use log::LevelFilter;
+///
+/// let builder = Client::builder("sdk-key")
+///     .log_level(LogArea::Evaluation, LevelFilter::Warn);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogArea {
+    /// The evaluation log and the warnings about missing or invalid User Object attributes.
+    Evaluation,
+    /// Downloading the config JSON, including redirects and failovers.
+    Fetch,
+    /// Reading and migrating the entries of the [`crate::ConfigCache`].
+    Cache,
+    /// Loading and applying flag overrides.
+    Override,
+}
+
+/// The maximum log level of each [`LogArea`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LogLevels {
+    evaluation: LevelFilter,
+    fetch: LevelFilter,
+    cache: LevelFilter,
+    r#override: LevelFilter,
+}
+
+impl Default for LogLevels {
+    fn default() -> Self {
+        Self {
+            evaluation: LevelFilter::Trace,
+            fetch: LevelFilter::Trace,
+            cache: LevelFilter::Trace,
+            r#override: LevelFilter::Trace,
+        }
+    }
+}
+
+impl LogLevels {
+    pub(crate) fn get(&self, area: LogArea) -> LevelFilter {
+        match area {
+            LogArea::Evaluation => self.evaluation,
+            LogArea::Fetch => self.fetch,
+            LogArea::Cache => self.cache,
+            LogArea::Override => self.r#override,
+        }
+    }
+
+    pub(crate) fn set(&mut self, area: LogArea, level: LevelFilter) {
+        match area {
+            LogArea::Evaluation => self.evaluation = level,
+            LogArea::Fetch => self.fetch = level,
+            LogArea::Cache => self.cache = level,
+            LogArea::Override => self.r#override = level,
+        }
+    }
+}
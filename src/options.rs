@@ -0,0 +1,226 @@
+use crate::constants::{
+    BASE_URL_ENV_VAR, DATA_GOVERNANCE_ENV_VAR, HTTP_TIMEOUT_ENV_VAR, POLLING_MODE_ENV_VAR,
+    POLL_INTERVAL_ENV_VAR, SDK_KEY_ENV_VAR,
+};
+use crate::errors::{ClientError, ErrorKind};
+use crate::{ClientBuilder, DataGovernance, PollingMode};
+use serde::Deserialize;
+use std::time::Duration;
+
+#[cfg(feature = "network")]
+const POLLING_MODES: &str = "auto, lazy, manual";
+#[cfg(not(feature = "network"))]
+const POLLING_MODES: &str = "manual";
+
+/// Options of a [`crate::Client`] that can be deserialized from a configuration file, used by
+/// [`ClientBuilder::from_config`].
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::{ClientBuilder, ConfigCatOptions};
+///
+/// let options: ConfigCatOptions = serde_json::from_str(r#"{
+///     "sdk_key": "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/HhOWfwVtZ0mb30i9wi17GQ",
+///     "polling_mode": "lazy",
+///     "poll_interval_secs": 120,
+///     "data_governance": "eu"
+/// }"#).unwrap();
+///
+/// let builder = ClientBuilder::from_config(options).unwrap();
+/// ```
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigCatOptions {
+    /// The SDK Key.
+    pub sdk_key: String,
+    /// The base URL of the remote server providing the latest version of the config JSON.
+    pub base_url: Option<String>,
+    /// The polling mode, one of `auto`, `lazy` or `manual`. Defaults to `auto` when only the interval is set.
+    pub polling_mode: Option<String>,
+    /// The interval of `auto` polling or the cache TTL of `lazy` loading in seconds, 60 when not set.
+    pub poll_interval_secs: Option<u64>,
+    /// The timeout of the HTTP requests in seconds.
+    pub http_timeout_secs: Option<u64>,
+    /// Indicates whether the SDK should be initialized in offline mode.
+    pub offline: bool,
+    /// The data governance, `global` or `eu`.
+    pub data_governance: Option<String>,
+}
+
+impl ConfigCatOptions {
+    pub(crate) fn from_vars(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, ClientError> {
+        let var = |name: &str| lookup(name).filter(|value| !value.trim().is_empty());
+        let secs = |name: &str| var(name).map(|value| parse_secs(name, &value)).transpose();
+        Ok(Self {
+            sdk_key: var(SDK_KEY_ENV_VAR).ok_or_else(|| {
+                invalid(format!(
+                    "The {SDK_KEY_ENV_VAR} environment variable is not set."
+                ))
+            })?,
+            base_url: var(BASE_URL_ENV_VAR),
+            polling_mode: var(POLLING_MODE_ENV_VAR),
+            poll_interval_secs: secs(POLL_INTERVAL_ENV_VAR)?,
+            http_timeout_secs: secs(HTTP_TIMEOUT_ENV_VAR)?,
+            offline: false,
+            data_governance: var(DATA_GOVERNANCE_ENV_VAR),
+        })
+    }
+
+    pub(crate) fn into_builder(self) -> Result<ClientBuilder, ClientError> {
+        if self.sdk_key.trim().is_empty() {
+            return Err(invalid(
+                "The SDK Key is missing from the options.".to_owned(),
+            ));
+        }
+        let mut builder = ClientBuilder::new(self.sdk_key.trim()).offline(self.offline);
+        if let Some(base_url) = self.base_url.as_deref() {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(mode) = polling_mode(self.polling_mode.as_deref(), self.poll_interval_secs)? {
+            builder = builder.polling_mode(mode);
+        }
+        if let Some(timeout) = self.http_timeout_secs {
+            builder = builder.http_timeout(Duration::from_secs(timeout));
+        }
+        if let Some(data_governance) = self.data_governance.as_deref() {
+            builder = builder.data_governance(parse_data_governance(data_governance)?);
+        }
+        Ok(builder)
+    }
+}
+
+fn polling_mode(
+    mode: Option<&str>,
+    interval_secs: Option<u64>,
+) -> Result<Option<PollingMode>, ClientError> {
+    if interval_secs == Some(0) {
+        return Err(invalid(
+            "The poll interval must be greater than 0 seconds.".to_owned(),
+        ));
+    }
+    #[cfg(feature = "network")]
+    let interval = Duration::from_secs(interval_secs.unwrap_or(60));
+    let mode = match (mode, interval_secs) {
+        (None, None) => return Ok(None),
+        (None, Some(_)) => "auto".to_owned(),
+        (Some(mode), _) => mode.trim().to_ascii_lowercase(),
+    };
+    match mode.as_str() {
+        #[cfg(feature = "network")]
+        "auto" => Ok(Some(PollingMode::AutoPoll(interval))),
+        #[cfg(feature = "network")]
+        "lazy" => Ok(Some(PollingMode::LazyLoad(interval))),
+        "manual" => Ok(Some(PollingMode::Manual)),
+        _ => Err(invalid(format!(
+            "The polling mode '{mode}' is invalid, expected one of: {POLLING_MODES}."
+        ))),
+    }
+}
+
+fn parse_secs(name: &str, value: &str) -> Result<u64, ClientError> {
+    value.trim().parse::<u64>().map_err(|_| {
+        invalid(format!(
+            "The value '{value}' of the {name} environment variable is invalid, expected a whole number of seconds."
+        ))
+    })
+}
+
+fn parse_data_governance(value: &str) -> Result<DataGovernance, ClientError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "global" => Ok(DataGovernance::Global),
+        "eu" => Ok(DataGovernance::EU),
+        _ => Err(invalid(format!(
+            "The data governance '{value}' is invalid, expected one of: global, eu."
+        ))),
+    }
+}
+
+fn invalid(message: String) -> ClientError {
+    ClientError::new(ErrorKind::InvalidConfiguration, message)
+}
+
+#[cfg(test)]
+#[cfg(feature = "network")]
+mod options_tests {
+    use crate::errors::ErrorKind;
+    use crate::{ConfigCatOptions, DataGovernance, PollingMode};
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    fn from_vars(vars: &[(&str, &str)]) -> Result<ConfigCatOptions, ErrorKind> {
+        let vars = vars.iter().copied().collect::<HashMap<&str, &str>>();
+        ConfigCatOptions::from_vars(|name| vars.get(name).map(|value| (*value).to_owned()))
+            .map_err(|err| err.kind)
+    }
+
+    #[test]
+    fn options_from_vars() {
+        let options = from_vars(&[
+            ("CONFIGCAT_SDK_KEY", "sdk-key"),
+            ("CONFIGCAT_BASE_URL", "https://cdn.example.com"),
+            ("CONFIGCAT_POLLING_MODE", "Lazy"),
+            ("CONFIGCAT_POLL_INTERVAL_SECS", " 120 "),
+            ("CONFIGCAT_HTTP_TIMEOUT_SECS", ""),
+            ("CONFIGCAT_DATA_GOVERNANCE", "eu"),
+        ])
+        .unwrap();
+        let built = options.into_builder().unwrap().build_options();
+
+        assert_eq!(built.sdk_key(), "sdk-key");
+        assert_eq!(built.base_url().unwrap(), "https://cdn.example.com");
+        assert!(
+            matches!(built.polling_mode(), PollingMode::LazyLoad(ttl) if *ttl == Duration::from_secs(120))
+        );
+        assert_eq!(*built.http_timeout(), Duration::from_secs(30));
+        assert_eq!(*built.data_governance(), DataGovernance::EU);
+    }
+
+    #[test]
+    fn invalid_options() {
+        assert_eq!(from_vars(&[]), Err(ErrorKind::InvalidConfiguration));
+        assert_eq!(
+            from_vars(&[
+                ("CONFIGCAT_SDK_KEY", "sdk-key"),
+                ("CONFIGCAT_POLL_INTERVAL_SECS", "1m")
+            ]),
+            Err(ErrorKind::InvalidConfiguration)
+        );
+
+        let invalid =
+            |options: ConfigCatOptions| options.into_builder().err().map(|err| err.message);
+        let options = ConfigCatOptions {
+            sdk_key: "sdk-key".to_owned(),
+            ..ConfigCatOptions::default()
+        };
+        assert_eq!(
+            invalid(ConfigCatOptions {
+                polling_mode: Some("sometimes".to_owned()),
+                ..options.clone()
+            }),
+            Some(
+                "The polling mode 'sometimes' is invalid, expected one of: auto, lazy, manual."
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            invalid(ConfigCatOptions {
+                poll_interval_secs: Some(0),
+                ..options.clone()
+            }),
+            Some("The poll interval must be greater than 0 seconds.".to_owned())
+        );
+        assert_eq!(
+            invalid(ConfigCatOptions {
+                data_governance: Some("us".to_owned()),
+                ..options.clone()
+            }),
+            Some("The data governance 'us' is invalid, expected one of: global, eu.".to_owned())
+        );
+        assert_eq!(
+            invalid(ConfigCatOptions::default()),
+            Some("The SDK Key is missing from the options.".to_owned())
+        );
+        assert!(invalid(options).is_none());
+    }
+}
@@ -1,5 +1,5 @@
 /// Specifies the behaviors for flag overrides.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OverrideBehavior {
     /// When evaluating values, the SDK will not use feature flags & settings from the ConfigCat CDN, but it will use
     /// all feature flags & settings that are loaded from local-override sources.
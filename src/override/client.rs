@@ -0,0 +1,96 @@
+use crate::r#override::error::OverrideError;
+use crate::r#override::source::{AsyncOverrideDataSource, OverrideLoadFuture};
+use crate::{Client, ClientBuilder, ClientCacheState, ClientError};
+use std::sync::Arc;
+
+/// Data source that gets the overridden feature flag or setting values from the config JSON of another [`Client`].
+///
+/// It makes it possible to evaluate settings split across several configs with one lookup. The precedence
+/// between the configs is controlled by the [`crate::OverrideBehavior`] passed to
+/// [`crate::ClientBuilder::async_overrides`]: with [`crate::OverrideBehavior::RemoteOverLocal`], the settings of
+/// the client being built win over the ones of the backing client; with [`crate::OverrideBehavior::LocalOverRemote`],
+/// the backing client wins. Keys defined by both configs are logged each time the settings are merged.
+///
+/// The settings keep their own targeting rules, segments and salt. Prerequisite flags are looked up
+/// in the merged settings.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use configcat::{Client, ClientDataSource, OverrideBehavior};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let platform = Arc::new(Client::new("platform-sdk-key").unwrap());
+///
+///     // the settings of the service config override the ones of the platform config
+///     let service = Client::builder("service-sdk-key")
+///         .async_overrides(
+///             Box::new(ClientDataSource::new(platform)),
+///             OverrideBehavior::RemoteOverLocal,
+///             Duration::from_secs(60),
+///         )
+///         .build()
+///         .unwrap();
+///
+///     let value = service.get_value("flag-key", false, None).await;
+/// }
+/// ```
+///
+/// It also enables environment cascading, e.g. a staging environment that inherits the values of the production
+/// environment unless it defines them itself. The backing client keeps its config JSON up-to-date with its own
+/// poller, and the changes are picked up at each refresh of the flag overrides:
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use configcat::{Client, ClientDataSource, OverrideBehavior, PollingMode};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let production = ClientDataSource::from_builder(
+///         Client::builder("production-sdk-key").polling_mode(PollingMode::AutoPoll(Duration::from_secs(60))),
+///     )
+///     .unwrap();
+///
+///     let staging = Client::builder("staging-sdk-key")
+///         .async_overrides(Box::new(production), OverrideBehavior::RemoteOverLocal, Duration::from_secs(60))
+///         .build()
+///         .unwrap();
+/// }
+/// ```
+pub struct ClientDataSource {
+    client: Arc<Client>,
+}
+
+impl ClientDataSource {
+    /// Creates a new [`ClientDataSource`] that reads the settings of `client`.
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client }
+    }
+
+    /// Creates a new [`ClientDataSource`] that reads the settings of a client built from `builder`,
+    /// typically configured with the SDK Key of another environment.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the backing client can't be built, see [`ClientBuilder::build`].
+    pub fn from_builder(builder: ClientBuilder) -> Result<Self, ClientError> {
+        Ok(Self::new(Arc::new(builder.build()?)))
+    }
+}
+
+impl AsyncOverrideDataSource for ClientDataSource {
+    fn load(&self) -> OverrideLoadFuture<'_> {
+        Box::pin(async {
+            let config = self.client.config_snapshot().await;
+            if *self.client.state_watch().borrow() == ClientCacheState::NoFlagData {
+                return Err(OverrideError::Load(
+                    "The backing client has no config JSON yet.".to_owned(),
+                ));
+            }
+            Ok(config.settings.clone())
+        })
+    }
+}
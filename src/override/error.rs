@@ -0,0 +1,38 @@
+use thiserror::Error;
+
+/// Error that represents failures of the flag override data sources.
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat::{FileDataSource, OverrideError};
+///
+/// match FileDataSource::new("path/to/file.json") {
+///     Ok(source) => { /* use the source */ }
+///     Err(OverrideError::Io(err)) => eprintln!("Can't read the overrides: {err}"),
+///     Err(OverrideError::InvalidValue { key }) => eprintln!("The override of '{key}' is invalid"),
+///     Err(err) => eprintln!("{err}"),
+/// }
+/// ```
+#[derive(Error, Debug)]
+pub enum OverrideError {
+    /// The flag overrides file couldn't be read.
+    #[error("Failed to read the flag overrides file. ({0})")]
+    Io(#[from] std::io::Error),
+    /// The content is not a valid simplified (`{"flags": {...}}`) flag overrides JSON.
+    #[error("Failed to parse the simplified flag overrides JSON. ({0})")]
+    SimplifiedParse(String),
+    /// The content is not a valid config JSON, the [`serde_json::Error`] describes the location and
+    /// category of the failure.
+    #[error("Failed to parse the flag overrides config JSON. ({0})")]
+    FullConfigParse(#[source] serde_json::Error),
+    /// The overridden value of the feature flag or setting identified by `key` is not a bool, number or text.
+    #[error("The overridden value of '{key}' is invalid, expected a bool, number or text.")]
+    InvalidValue {
+        /// The key of the feature flag or setting.
+        key: String,
+    },
+    /// An [`crate::AsyncOverrideDataSource`] failed to load the flag overrides.
+    #[error("Failed to load the flag overrides. ({0})")]
+    Load(String),
+}
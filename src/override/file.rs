@@ -1,7 +1,10 @@
+use crate::eval::hashing::HashScheme;
 use crate::model::config::{post_process_config, Config};
+use crate::r#override::error::OverrideError;
 use crate::r#override::source::OverrideDataSource;
 use crate::{Setting, Value};
 use serde::Deserialize;
+use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 use std::fs;
 
@@ -40,8 +43,10 @@ impl FileDataSource {
     /// # Errors
     ///
     /// This method fails in the following cases:
-    /// - The given file doesn't exist.
-    /// - The given file's content is not deserializable to [`SimplifiedConfig`] or [`Config`].
+    /// - The given file can't be read ([`OverrideError::Io`]).
+    /// - The given file has a `flags` map that is not deserializable to [`SimplifiedConfig`]
+    ///   ([`OverrideError::SimplifiedParse`] or [`OverrideError::InvalidValue`]).
+    /// - The given file's content is not deserializable to [`Config`] ([`OverrideError::FullConfigParse`]).
     ///
     /// # Examples
     ///
@@ -50,38 +55,43 @@ impl FileDataSource {
     ///
     /// let source = FileDataSource::new("path/to/file.json").unwrap();
     /// ```
-    pub fn new(file_path: &str) -> Result<Self, String> {
-        let content_result = fs::read_to_string(file_path);
-        match content_result {
-            Ok(content) => {
-                let simple_result = serde_json::from_str::<SimplifiedConfig>(content.as_str());
-                match simple_result {
-                    Ok(simple_config) => {
-                        let mut map: HashMap<String, Setting> = HashMap::new();
-                        for (k, value) in &simple_config.flags {
-                            map.insert(k.clone(), value.into());
-                        }
-                        Ok(FileDataSource {
-                            config: Config {
-                                settings: map,
-                                salt: None,
-                                segments: None,
-                                preferences: None,
-                            },
-                        })
-                    }
-                    Err(_) => match serde_json::from_str::<Config>(content.as_str()) {
-                        Ok(mut config) => {
-                            post_process_config(&mut config);
-                            Ok(FileDataSource { config })
-                        }
-                        Err(err) => Err(err.to_string()),
-                    },
-                }
+    pub fn new(file_path: &str) -> Result<Self, OverrideError> {
+        let content = fs::read_to_string(file_path)?;
+        let config = match serde_json::from_str::<JsonValue>(content.as_str()) {
+            Ok(JsonValue::Object(mut root)) if root.contains_key("flags") => {
+                simplified_config(root.remove("flags").unwrap_or_default())?
             }
-            Err(err) => Err(err.to_string()),
-        }
+            _ => {
+                let mut config = serde_json::from_str::<Config>(content.as_str())
+                    .map_err(OverrideError::FullConfigParse)?;
+                post_process_config(&mut config);
+                config
+            }
+        };
+        Ok(FileDataSource { config })
+    }
+}
+
+fn simplified_config(flags: JsonValue) -> Result<Config, OverrideError> {
+    let JsonValue::Object(flags) = flags else {
+        return Err(OverrideError::SimplifiedParse(
+            "'flags' must be a JSON object".to_owned(),
+        ));
+    };
+    let mut map: HashMap<String, Setting> = HashMap::new();
+    for (key, value) in flags {
+        let value = serde_json::from_value::<Value>(value)
+            .map_err(|_| OverrideError::InvalidValue { key: key.clone() })?;
+        map.insert(key, (&value).into());
     }
+    Ok(Config {
+        settings: map,
+        salt: None,
+        segments: None,
+        hash_scheme: HashScheme::default(),
+        preferences: None,
+        percentage_options_issues: Vec::new(),
+    })
 }
 
 impl OverrideDataSource for FileDataSource {
@@ -1,8 +1,10 @@
+use crate::r#override::error::OverrideError;
 use crate::r#override::source::OverrideDataSource;
 use crate::{Setting, Value};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-/// Data source that gets the overridden feature flag or setting values from a [`HashMap`] or a `[(&str, Value)]` array.
+/// Data source that gets the overridden feature flag or setting values from a [`HashMap`], a [`BTreeMap`],
+/// a `[(&str, Value)]` array or a JSON object.
 pub struct MapDataSource {
     overrides: HashMap<String, Setting>,
 }
@@ -101,3 +103,64 @@ impl<const N: usize> From<[(&str, Value); N]> for MapDataSource {
         }
     }
 }
+
+impl From<BTreeMap<String, Value>> for MapDataSource {
+    /// Creates a new [`MapDataSource`] from a [`BTreeMap`] of [`String`] and [`Value`].
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::BTreeMap;
+    /// use configcat::{MapDataSource, Value};
+    ///
+    /// let source = MapDataSource::from(BTreeMap::from([
+    ///     ("flag".to_owned(), Value::Bool(true))
+    /// ]));
+    /// ```
+    fn from(value: BTreeMap<String, Value>) -> Self {
+        Self {
+            overrides: value
+                .iter()
+                .map(|(k, v)| (k.clone(), v.into()))
+                .collect::<HashMap<String, Setting>>(),
+        }
+    }
+}
+
+impl TryFrom<serde_json::Value> for MapDataSource {
+    type Error = OverrideError;
+
+    /// Creates a new [`MapDataSource`] from a JSON object whose values are bools, numbers or texts.
+    ///
+    /// # Errors
+    ///
+    /// This method fails in the following cases:
+    /// - The given JSON value is not an object ([`OverrideError::SimplifiedParse`]).
+    /// - The value of a key is not a bool, number or text, e.g. `null`, an array or an object
+    ///   ([`OverrideError::InvalidValue`] with the first such key).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::{MapDataSource, OverrideError};
+    /// use serde_json::json;
+    ///
+    /// let source = MapDataSource::try_from(json!({"flag": true, "limit": 5, "theme": "dark"})).unwrap();
+    ///
+    /// let result = MapDataSource::try_from(json!({"flag": true, "nested": {"a": 1}}));
+    /// assert!(matches!(result, Err(OverrideError::InvalidValue { key }) if key == "nested"));
+    /// ```
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        let serde_json::Value::Object(values) = value else {
+            return Err(OverrideError::SimplifiedParse(
+                "the flag overrides must be a JSON object".to_owned(),
+            ));
+        };
+        let mut overrides = HashMap::with_capacity(values.len());
+        for (key, value) in values {
+            let value = serde_json::from_value::<Value>(value)
+                .map_err(|_| OverrideError::InvalidValue { key: key.clone() })?;
+            overrides.insert(key, (&value).into());
+        }
+        Ok(Self { overrides })
+    }
+}
@@ -1,10 +1,18 @@
-use crate::{OverrideBehavior, OverrideDataSource};
-use std::borrow::Borrow;
+use crate::r#override::error::OverrideError;
+use crate::{AsyncOverrideDataSource, OverrideBehavior, OverrideDataSource, Setting};
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
 
 pub mod behavior;
+pub mod client;
+pub mod error;
 pub mod file;
 pub mod map;
+pub mod ramp;
+pub mod report;
 pub mod source;
 
 pub trait OptionalOverrides {
@@ -13,20 +21,83 @@ pub trait OptionalOverrides {
 
 pub struct FlagOverrides {
     behavior: OverrideBehavior,
-    source: Box<dyn OverrideDataSource>,
+    source: OverrideSource,
+    version: AtomicU64,
+}
+
+enum OverrideSource {
+    Static(Box<dyn OverrideDataSource>),
+    Async {
+        source: Box<dyn AsyncOverrideDataSource>,
+        refresh_interval: Duration,
+        settings: RwLock<HashMap<String, Setting>>,
+    },
 }
 
 impl FlagOverrides {
     pub fn new(source: Box<dyn OverrideDataSource>, behavior: OverrideBehavior) -> Self {
-        Self { behavior, source }
+        Self {
+            behavior,
+            source: OverrideSource::Static(source),
+            version: AtomicU64::new(0),
+        }
+    }
+
+    pub fn new_async(
+        source: Box<dyn AsyncOverrideDataSource>,
+        behavior: OverrideBehavior,
+        refresh_interval: Duration,
+    ) -> Self {
+        Self {
+            behavior,
+            source: OverrideSource::Async {
+                source,
+                refresh_interval,
+                settings: RwLock::new(HashMap::new()),
+            },
+            version: AtomicU64::new(0),
+        }
     }
 
     pub fn behavior(&self) -> &OverrideBehavior {
         &self.behavior
     }
 
-    pub fn source(&self) -> &dyn OverrideDataSource {
-        self.source.borrow()
+    pub fn settings(&self) -> HashMap<String, Setting> {
+        match &self.source {
+            OverrideSource::Static(source) => source.settings().clone(),
+            OverrideSource::Async { settings, .. } => settings.read().unwrap().clone(),
+        }
+    }
+
+    /// Returns a counter that changes each time the overridden values are replaced.
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        match &self.source {
+            OverrideSource::Static(_) => None,
+            OverrideSource::Async {
+                refresh_interval, ..
+            } => Some(*refresh_interval),
+        }
+    }
+
+    /// Loads the overrides from an async source and returns whether the stored values were replaced.
+    ///
+    /// Returns the error of the source when the loading failed.
+    pub async fn refresh(&self) -> Result<bool, OverrideError> {
+        let OverrideSource::Async {
+            source, settings, ..
+        } = &self.source
+        else {
+            return Ok(false);
+        };
+        let loaded = source.load().await?;
+        *settings.write().unwrap() = loaded;
+        self.version.fetch_add(1, Ordering::SeqCst);
+        Ok(true)
     }
 }
 
@@ -0,0 +1,156 @@
+use crate::eval::hashing::HashScheme;
+use crate::model::enums::SettingType;
+use crate::r#override::source::{AsyncOverrideDataSource, OverrideLoadFuture};
+use crate::{PercentageOption, Setting, SettingValue};
+use chrono::{DateTime, Utc};
+use log::info;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Describes how the percentage of users receiving `true` for a feature flag grows over time.
+///
+/// Each step sets the percentage from its start time until the next step starts, the last step sets the
+/// terminal percentage. Before the first step, the ramp doesn't override the feature flag.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use configcat::Ramp;
+///
+/// let ramp = Ramp::new("new-checkout")
+///     .step(Utc.with_ymd_and_hms(2030, 1, 1, 3, 0, 0).unwrap(), 1)
+///     .step(Utc.with_ymd_and_hms(2030, 1, 1, 6, 0, 0).unwrap(), 5)
+///     .step(Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap(), 25);
+///
+/// assert_eq!(ramp.percentage_at(Utc.with_ymd_and_hms(2030, 1, 1, 7, 0, 0).unwrap()), Some(5));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Ramp {
+    key: String,
+    steps: Vec<(DateTime<Utc>, i64)>,
+}
+
+impl Ramp {
+    /// Creates a new [`Ramp`] of the feature flag identified by `key`.
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_owned(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Adds a step that serves `true` to `percentage` (clamped to 0..=100) of the users from `at`.
+    pub fn step(mut self, at: DateTime<Utc>, percentage: i64) -> Self {
+        self.steps.push((at, percentage.clamp(0, 100)));
+        self.steps.sort_by_key(|(start, _)| *start);
+        self
+    }
+
+    /// Returns the percentage in effect at `at`, or `None` before the first step.
+    pub fn percentage_at(&self, at: DateTime<Utc>) -> Option<i64> {
+        self.steps
+            .iter()
+            .take_while(|(start, _)| *start <= at)
+            .last()
+            .map(|(_, percentage)| *percentage)
+    }
+}
+
+/// Data source that overrides feature flags with the current percentages of [`Ramp`]s.
+///
+/// The overridden feature flags serve `true` to the current percentage of the users based on their
+/// identifier, and `false` to the others (and when no User Object is passed). As the SDK's bucketing is
+/// deterministic, a user who received `true` keeps receiving it while the percentage grows. Each time
+/// a ramp moves to a new step, it is logged at the `Info` level.
+///
+/// The percentages are recalculated with the refresh interval passed to [`crate::ClientBuilder::async_overrides`].
+/// Use [`crate::OverrideBehavior::LocalOverRemote`] to layer the ramps over the config JSON, or
+/// [`crate::OverrideBehavior::LocalOnly`] on deployments disconnected from the ConfigCat CDN.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use chrono::{TimeZone, Utc};
+/// use configcat::{Client, OverrideBehavior, Ramp, RampDataSource};
+///
+/// let ramp = Ramp::new("new-checkout")
+///     .step(Utc.with_ymd_and_hms(2030, 1, 1, 3, 0, 0).unwrap(), 1)
+///     .step(Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap(), 100);
+///
+/// let builder = Client::builder("sdk-key")
+///     .async_overrides(
+///         Box::new(RampDataSource::new(vec![ramp])),
+///         OverrideBehavior::LocalOverRemote,
+///         Duration::from_secs(60),
+///     );
+/// ```
+pub struct RampDataSource {
+    ramps: Vec<Ramp>,
+    applied: Mutex<HashMap<String, i64>>,
+}
+
+impl RampDataSource {
+    /// Creates a new [`RampDataSource`] from the given `ramps`.
+    pub fn new(ramps: Vec<Ramp>) -> Self {
+        Self {
+            ramps,
+            applied: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn settings_at(&self, at: DateTime<Utc>) -> HashMap<String, Setting> {
+        let mut applied = self.applied.lock().unwrap();
+        let mut settings = HashMap::new();
+        for ramp in &self.ramps {
+            let Some(percentage) = ramp.percentage_at(at) else {
+                continue;
+            };
+            if applied.insert(ramp.key.clone(), percentage) != Some(percentage) {
+                info!(
+                    "Ramp of '{}' serves `true` to {percentage}% of the users.",
+                    ramp.key
+                );
+            }
+            settings.insert(ramp.key.clone(), ramp_setting(percentage));
+        }
+        settings
+    }
+}
+
+impl AsyncOverrideDataSource for RampDataSource {
+    fn load(&self) -> OverrideLoadFuture<'_> {
+        Box::pin(async { Ok(self.settings_at(Utc::now())) })
+    }
+}
+
+fn ramp_setting(percentage: i64) -> Setting {
+    let option = |percentage: i64, value: bool| {
+        Arc::new(PercentageOption {
+            served_value: SettingValue {
+                bool_val: Some(value),
+                ..SettingValue::default()
+            },
+            percentage,
+            variation_id: None,
+        })
+    };
+    Setting {
+        value: SettingValue {
+            bool_val: Some(false),
+            ..SettingValue::default()
+        },
+        percentage_options: Some(vec![
+            option(percentage, true),
+            option(100 - percentage, false),
+        ]),
+        targeting_rules: None,
+        variation_id: None,
+        percentage_attribute: None,
+        setting_type: SettingType::Bool,
+        salt: None,
+        hash_scheme: HashScheme::default(),
+        percentage_options_issue: None,
+    }
+}
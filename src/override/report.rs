@@ -0,0 +1,27 @@
+use crate::OverrideBehavior;
+
+/// Describes how the flag overrides were merged into the config JSON downloaded from the ConfigCat CDN.
+///
+/// A new report is produced each time the overrides are applied (on a new config JSON or when the
+/// overridden values change). Clients with [`OverrideBehavior::LocalOnly`] don't produce reports, as
+/// they don't use the downloaded config JSON at all.
+///
+/// Available through [`crate::Client::override_report`] and [`crate::ClientBuilder::on_overrides_applied`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverrideReport {
+    /// The behavior that was applied.
+    pub behavior: OverrideBehavior,
+    /// Keys defined both in the config JSON and the flag overrides whose values are taken from the flag overrides.
+    pub overridden: Vec<String>,
+    /// Keys defined both in the config JSON and the flag overrides whose values are taken from the config JSON.
+    pub ignored: Vec<String>,
+    /// Keys defined only in the flag overrides.
+    pub added: Vec<String>,
+}
+
+impl OverrideReport {
+    /// Indicates whether the flag overrides changed the value of any setting defined in the config JSON.
+    pub fn has_overridden_keys(&self) -> bool {
+        !self.overridden.is_empty()
+    }
+}
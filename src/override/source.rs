@@ -1,8 +1,47 @@
+use crate::r#override::error::OverrideError;
 use crate::Setting;
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
 
 /// Data source that provides feature flag and setting value overrides.
 pub trait OverrideDataSource: Sync + Send {
     /// Gets the overridden feature flag or setting values.
     fn settings(&self) -> &HashMap<String, Setting>;
 }
+
+/// The future returned by [`AsyncOverrideDataSource::load`].
+pub type OverrideLoadFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<HashMap<String, Setting>, OverrideError>> + Send + 'a>>;
+
+/// Data source that asynchronously loads feature flag and setting value overrides from a remote backend.
+///
+/// The SDK calls [`AsyncOverrideDataSource::load`] periodically and re-applies the returned values
+/// on the currently used config.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use configcat::{AsyncOverrideDataSource, OverrideLoadFuture, Value};
+///
+/// struct RemoteSource {}
+///
+/// impl AsyncOverrideDataSource for RemoteSource {
+///     fn load(&self) -> OverrideLoadFuture<'_> {
+///         Box::pin(async {
+///             // download from the remote backend
+///             Ok(HashMap::from([("flag".to_owned(), (&Value::Bool(true)).into())]))
+///         })
+///     }
+/// }
+/// ```
+pub trait AsyncOverrideDataSource: Sync + Send {
+    /// Loads the overridden feature flag or setting values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OverrideError`] (usually [`OverrideError::Load`]) when the values couldn't be loaded.
+    /// In this case the previously loaded values remain in use.
+    fn load(&self) -> OverrideLoadFuture<'_>;
+}
@@ -0,0 +1,253 @@
+use crate::builder::Options;
+use crate::cache::EmptyConfigCache;
+use crate::{
+    Client, ClientError, ConfigCache, EvaluationDetails, ReadyReport, RefreshOutcome, User,
+    ValuePrimitive,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// A fixed set of [`Client`]s that share one config JSON download.
+///
+/// Evaluations are routed to the clients by the hash of the evaluated key and the user's identifier,
+/// which spreads concurrent evaluations across the clients while the same key and user
+/// always land on the same client.
+///
+/// Only the first client of the pool downloads the config JSON by [`crate::PollingMode::AutoPoll`],
+/// the others read it from a cache shared between the clients. With [`crate::PollingMode::LazyLoad`],
+/// each client refreshes the shared cache when it finds the cached config JSON older than the cache TTL.
+/// The hooks of the config JSON's lifecycle (e.g. [`crate::ClientBuilder::on_cache_state_changed`])
+/// are only called by the first client, so each change is reported once per pool.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use configcat::{Client, PollingMode, User};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let pool = Client::builder("sdk-key")
+///         .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)))
+///         .build_pool(4)
+///         .unwrap();
+///
+///     let user = User::new("user-id");
+///     let is_flag_enabled = pool.get_value("flag-key", false, Some(user)).await;
+/// }
+/// ```
+pub struct ClientPool {
+    clients: Vec<Client>,
+}
+
+impl ClientPool {
+    pub(crate) fn with_options(mut options: Options, size: usize) -> Result<Self, ClientError> {
+        let inner = options.swap_cache(Box::new(EmptyConfigCache::new()));
+        let shared = Arc::new(SharedCache {
+            inner,
+            latest: RwLock::new(HashMap::new()),
+        });
+        options.swap_cache(Box::new(SharedCacheHandle(Arc::clone(&shared))));
+
+        let pooled = (1..size.max(1))
+            .map(|_| options.for_pooled_client(Box::new(SharedCacheHandle(Arc::clone(&shared)))))
+            .collect::<Vec<Options>>();
+        let primary = Client::with_options(options)?;
+        let mut clients = Vec::with_capacity(pooled.len() + 1);
+        for opts in pooled {
+            clients.push(Client::pooled(opts, &primary)?);
+        }
+        clients.insert(0, primary);
+        Ok(Self { clients })
+    }
+
+    /// Returns the number of clients in the pool.
+    pub fn size(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Returns the [`Client`] that evaluations of the given `key` and `user` are routed to.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let pool = Client::builder("sdk-key").build_pool(4).unwrap();
+    ///
+    ///     let user = User::new("user-id");
+    ///     let details = pool.client("flag-key", Some(&user)).get_flag_details("flag-key", Some(user.clone())).await;
+    /// }
+    /// ```
+    pub fn client(&self, key: &str, user: Option<&User>) -> &Client {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        if let Some(identifier) = user.and_then(|u| u.get(User::IDENTIFIER)) {
+            identifier.as_str().0.hash(&mut hasher);
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let index = (hasher.finish() % self.clients.len() as u64) as usize;
+        &self.clients[index]
+    }
+
+    /// Evaluates a feature flag or setting identified by the given `key` on the client selected by [`ClientPool::client`].
+    ///
+    /// Returns `default` if the flag doesn't exist, or there was an error during the evaluation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let pool = Client::builder("sdk-key").build_pool(4).unwrap();
+    ///
+    ///     let user = User::new("user-id");
+    ///     let value = pool.get_value("flag-key", false, Some(user)).await;
+    /// }
+    /// ```
+    pub async fn get_value<T: ValuePrimitive + Clone + Default>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<User>,
+    ) -> T {
+        self.client(key, user.as_ref())
+            .get_value(key, default, user)
+            .await
+    }
+
+    /// The same as [`ClientPool::get_value`] but returns an [`EvaluationDetails`] that
+    /// contains additional information about the result of the evaluation process.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::{Client, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let pool = Client::builder("sdk-key").build_pool(4).unwrap();
+    ///
+    ///     let user = User::new("user-id");
+    ///     let details = pool.get_value_details("flag-key", String::default(), Some(user)).await;
+    /// }
+    /// ```
+    pub async fn get_value_details<T: ValuePrimitive + Clone + Default>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<User>,
+    ) -> EvaluationDetails<T> {
+        self.client(key, user.as_ref())
+            .get_value_details(key, default, user)
+            .await
+    }
+
+    /// Initiates a force refresh on the config JSON data shared by the clients of the pool.
+    ///
+    /// # Errors
+    ///
+    /// This method fails in the same cases as [`Client::refresh`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let pool = Client::builder("sdk-key").build_pool(4).unwrap();
+    ///
+    ///     pool.refresh().await.unwrap();
+    /// }
+    /// ```
+    pub async fn refresh(&self) -> Result<RefreshOutcome, ClientError> {
+        self.clients[0].refresh().await
+    }
+
+    /// Asynchronously waits for the initialization of the pool for a maximum duration specified in `wait_timeout`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the initialization takes more time than the specified `wait_timeout`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let pool = Client::builder("sdk-key").build_pool(4).unwrap();
+    ///
+    ///     let report = pool.wait_for_ready(Duration::from_secs(5)).await.unwrap();
+    /// }
+    /// ```
+    pub async fn wait_for_ready(&self, wait_timeout: Duration) -> Result<ReadyReport, ClientError> {
+        self.clients[0].wait_for_ready(wait_timeout).await
+    }
+
+    /// Stops the background tasks of the clients in the pool and completes when they have terminated,
+    /// see [`Client::shutdown`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use configcat::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let pool = Client::builder("sdk-key").build_pool(4).unwrap();
+    ///
+    ///     pool.shutdown().await;
+    /// }
+    /// ```
+    pub async fn shutdown(&self) {
+        for client in &self.clients {
+            client.shutdown().await;
+        }
+    }
+}
+
+impl Debug for ClientPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientPool")
+            .field("size", &self.clients.len())
+            .finish_non_exhaustive()
+    }
+}
+
+struct SharedCache {
+    inner: Box<dyn ConfigCache>,
+    latest: RwLock<HashMap<String, String>>,
+}
+
+struct SharedCacheHandle(Arc<SharedCache>);
+
+impl ConfigCache for SharedCacheHandle {
+    fn read(&self, key: &str) -> Option<String> {
+        self.0
+            .inner
+            .read(key)
+            .filter(|cached| !cached.is_empty())
+            .or_else(|| self.0.latest.read().unwrap().get(key).cloned())
+    }
+
+    fn write(&self, key: &str, value: &str) {
+        self.0
+            .latest
+            .write()
+            .unwrap()
+            .insert(key.to_owned(), value.to_owned());
+        self.0.inner.write(key, value);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::client::values_of;
+use crate::fetch::service::ConfigResult;
+use crate::value::ValuePrimitive;
+use crate::{Client, Config, EvalOptions, EvaluationDetails, User, Value};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Evaluates feature flags and settings on the config JSON that was current when the session began.
+///
+/// Refreshes of the [`Client`]'s config JSON don't affect an existing session, so each evaluation
+/// made through it (e.g. during the handling of one HTTP request) works on the same flag combination.
+/// As the config JSON is already resolved, the getters don't need to be awaited.
+///
+/// Created with [`Client::begin_session`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat::{Client, User};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = Client::new("sdk-key").unwrap();
+///
+///     let session = client.begin_session().await;
+///     let user = User::new("user-id");
+///     let flag = session.get_value("flag-key", false, Some(user.clone()));
+///     let other_flag = session.get_value("other-flag-key", false, Some(user));
+/// }
+/// ```
+pub struct EvaluationSession<'a> {
+    client: &'a Client,
+    result: ConfigResult,
+}
+
+impl<'a> EvaluationSession<'a> {
+    pub(crate) fn new(client: &'a Client, result: ConfigResult) -> Self {
+        Self { client, result }
+    }
+
+    /// Returns the config JSON pinned by this session.
+    pub fn config(&self) -> &Arc<Config> {
+        self.result.config()
+    }
+
+    /// Returns the time when the config JSON pinned by this session was fetched.
+    pub fn fetch_time(&self) -> &DateTime<Utc> {
+        self.result.fetch_time()
+    }
+
+    /// The same as [`Client::get_value`] but evaluates on the config JSON pinned by this session.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn get_value<T: ValuePrimitive + Clone + Default>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<User>,
+    ) -> T {
+        self.client
+            .eval_value_details(&self.result, key, default, user.as_ref())
+            .value
+    }
+
+    /// The same as [`Client::get_value_ref`] but evaluates on the config JSON pinned by this session.
+    pub fn get_value_ref<T: ValuePrimitive + Clone + Default>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<&User>,
+    ) -> T {
+        self.client
+            .eval_value_details(&self.result, key, default, user)
+            .value
+    }
+
+    /// The same as [`Client::get_value_details`] but evaluates on the config JSON pinned by this session.
+    pub fn get_value_details<T: ValuePrimitive + Clone + Default>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<User>,
+    ) -> EvaluationDetails<T> {
+        self.client.eval_value_details_with_user(
+            &self.result,
+            key,
+            default,
+            user,
+            &EvalOptions::default(),
+        )
+    }
+
+    /// The same as [`Client::get_value_details_ref`] but evaluates on the config JSON pinned by this session.
+    pub fn get_value_details_ref<T: ValuePrimitive + Clone + Default>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<&User>,
+        include_user: bool,
+    ) -> EvaluationDetails<T> {
+        if include_user {
+            return self.client.eval_value_details_with_user(
+                &self.result,
+                key,
+                default,
+                user.cloned(),
+                &EvalOptions::default(),
+            );
+        }
+        self.client
+            .eval_value_details(&self.result, key, default, user)
+    }
+
+    /// The same as [`Client::get_json_value`] but evaluates on the config JSON pinned by this session.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn get_json_value<T: DeserializeOwned + Clone + Send + Sync + 'static>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<User>,
+    ) -> T {
+        self.client
+            .eval_json_value(&self.result, key, default, user.as_ref())
+    }
+
+    /// The same as [`Client::get_enum_value`] but evaluates on the config JSON pinned by this session.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn get_enum_value<E: FromStr + Default>(&self, key: &str, user: Option<User>) -> E {
+        self.client
+            .eval_enum_value(&self.result, key, user.as_ref())
+    }
+
+    /// The same as [`Client::get_custom_value`] but evaluates on the config JSON pinned by this session.
+    pub fn get_custom_value<T>(&self, key: &str, default: T, user: Option<User>) -> T
+    where
+        T: TryFrom<Value>,
+        T::Error: Display,
+    {
+        self.get_custom_value_details(key, default, user).value
+    }
+
+    /// The same as [`Client::get_custom_value_details`] but evaluates on the config JSON pinned by this session.
+    pub fn get_custom_value_details<T>(
+        &self,
+        key: &str,
+        default: T,
+        user: Option<User>,
+    ) -> EvaluationDetails<T>
+    where
+        T: TryFrom<Value>,
+        T::Error: Display,
+    {
+        self.client
+            .eval_custom_value_details(&self.result, key, default, user)
+    }
+
+    /// The same as [`Client::get_flag_details`] but evaluates on the config JSON pinned by this session.
+    pub fn get_flag_details(
+        &self,
+        key: &str,
+        user: Option<User>,
+    ) -> EvaluationDetails<Option<Value>> {
+        self.client.eval_flag_details(&self.result, key, user)
+    }
+
+    /// The same as [`Client::get_all_values`] but evaluates on the config JSON pinned by this session.
+    pub fn get_all_values(&self, user: Option<User>) -> HashMap<String, Value> {
+        values_of(self.get_all_value_details(user))
+    }
+
+    /// The same as [`Client::get_all_value_details`] but evaluates on the config JSON pinned by this session.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn get_all_value_details(
+        &self,
+        user: Option<User>,
+    ) -> Vec<EvaluationDetails<Option<Value>>> {
+        self.client
+            .eval_all_value_details(&self.result, user.as_ref())
+    }
+
+    /// The same as [`Client::get_all_keys`] but returns the keys of the config JSON pinned by this session.
+    pub fn get_all_keys(&self) -> Vec<String> {
+        self.client.all_keys(&self.result)
+    }
+}
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// The feature flags and settings evaluated during a period, and the ones present in the config JSON
+/// that weren't evaluated at all, to help retiring the dead feature flags.
+///
+/// Only the evaluations of single feature flags count, the methods evaluating all feature flags
+/// (e.g. [`crate::Client::get_all_values`]) don't mark the feature flags as evaluated.
+///
+/// Created with [`crate::Client::usage_report`] and passed to the hook set with
+/// [`crate::ClientBuilder::on_usage_report`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use configcat::Client;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = Client::builder("sdk-key").track_usage(true).build().unwrap();
+///
+///     if let Some(report) = client.usage_report() {
+///         println!("never evaluated: {:?}", report.unused);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageReport {
+    /// The start of the period.
+    pub since: DateTime<Utc>,
+    /// The end of the period.
+    pub until: DateTime<Utc>,
+    /// The sorted keys of the feature flags and settings evaluated at least once during the period.
+    pub evaluated: Vec<String>,
+    /// The sorted keys of the feature flags and settings in the config JSON that weren't evaluated during the period.
+    pub unused: Vec<String>,
+}
+
+impl UsageReport {
+    /// Serializes the report to pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Collects the keys evaluated since the start of the current period.
+pub(crate) struct UsageTracker {
+    period: Mutex<(DateTime<Utc>, HashSet<String>)>,
+}
+
+impl UsageTracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            period: Mutex::new((Utc::now(), HashSet::new())),
+        }
+    }
+
+    pub(crate) fn record(&self, key: &str) {
+        let mut period = self.period.lock().unwrap();
+        if !period.1.contains(key) {
+            period.1.insert(key.to_owned());
+        }
+    }
+
+    /// Builds the report of the current period from the keys of the config JSON. When `flush` is set,
+    /// a new period starts.
+    pub(crate) fn report<'a>(
+        &self,
+        config_keys: impl Iterator<Item = &'a str>,
+        ignore_case: bool,
+        flush: bool,
+    ) -> UsageReport {
+        let until = Utc::now();
+        let (since, evaluated) = {
+            let mut period = self.period.lock().unwrap();
+            if flush {
+                std::mem::replace(&mut *period, (until, HashSet::new()))
+            } else {
+                (period.0, period.1.clone())
+            }
+        };
+        let lowercase_evaluated: HashSet<String> = if ignore_case {
+            evaluated.iter().map(|key| key.to_lowercase()).collect()
+        } else {
+            HashSet::new()
+        };
+        let mut unused: Vec<String> = config_keys
+            .filter(|key| {
+                !(evaluated.contains(*key)
+                    || ignore_case && lowercase_evaluated.contains(&key.to_lowercase()))
+            })
+            .map(ToOwned::to_owned)
+            .collect();
+        unused.sort();
+        let mut evaluated: Vec<String> = evaluated.into_iter().collect();
+        evaluated.sort();
+        UsageReport {
+            since,
+            until,
+            evaluated,
+            unused,
+        }
+    }
+}
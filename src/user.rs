@@ -3,12 +3,19 @@ use chrono::{DateTime, Utc};
 use semver::Version;
 use serde::ser::SerializeSeq;
 use serde::{Serialize, Serializer};
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::Index;
+use std::sync::{Arc, OnceLock};
 
 #[derive(Clone, Debug)]
 /// Supported user attribute value types.
+///
+/// Values can be used as [`HashMap`] keys: [`UserValue::Float`]s are compared and hashed
+/// by their bit pattern, where `0.0` equals `-0.0` and every NaN equals each other.
+/// Values of different variants are never equal, e.g. `Int(1)` doesn't equal `UInt(1)`.
 pub enum UserValue {
     /// String user attribute value.
     String(String),
@@ -73,6 +80,15 @@ pub enum UserValue {
 #[derive(Serialize, Clone, Debug)]
 pub struct User {
     attributes: HashMap<String, UserValue>,
+    #[serde(skip)]
+    lazy_attributes: HashMap<String, LazyValue>,
+}
+
+/// A user attribute value computed on first access.
+/// Clones share the closure but not the computed value.
+struct LazyValue {
+    compute: Arc<dyn Fn() -> UserValue + Send + Sync>,
+    value: OnceLock<UserValue>,
 }
 
 impl User {
@@ -95,11 +111,15 @@ impl User {
     pub fn new(identifier: &str) -> Self {
         Self {
             attributes: HashMap::from([(Self::IDENTIFIER.to_owned(), UserValue::from(identifier))]),
+            lazy_attributes: HashMap::new(),
         }
     }
 
     pub(crate) fn from_map(map: HashMap<String, UserValue>) -> Self {
-        Self { attributes: map }
+        Self {
+            attributes: map,
+            lazy_attributes: HashMap::new(),
+        }
     }
 
     /// Sets the email address of the user.
@@ -133,6 +153,22 @@ impl User {
         self
     }
 
+    /// Sets the country of the user to the one `resolver` returns for `ip`.
+    /// The country remains unchanged when `resolver` doesn't know `ip`.
+    ///
+    /// Available with the `geo` feature, see [`GeoResolver`](crate::GeoResolver) for an example.
+    #[cfg(feature = "geo")]
+    pub fn country_from_ip<R: crate::GeoResolver + ?Sized>(
+        self,
+        ip: std::net::IpAddr,
+        resolver: &R,
+    ) -> Self {
+        match resolver.country(ip) {
+            Some(country) => self.country(country.as_str()),
+            None => self,
+        }
+    }
+
     /// Sets a custom attribute of the user for advanced targeting rule definitions (e.g. user role, subscription type, etc.)
     ///
     /// # Examples:
@@ -152,10 +188,50 @@ impl User {
         if key == Self::IDENTIFIER || key == Self::EMAIL || key == Self::COUNTRY {
             return self;
         }
+        self.lazy_attributes.remove(key);
         self.attributes.insert(key.to_owned(), value.into());
         self
     }
 
+    /// Sets a custom attribute of the user whose value is computed by `compute` only when an evaluation
+    /// actually needs it, e.g. when it's expensive to look up and only a few targeting rules reference it.
+    ///
+    /// `compute` is invoked at most once per evaluation, each evaluation starts without the previously computed value.
+    /// Lazy attributes aren't included in the [`Display`] representation of the user (e.g. in the evaluation log).
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    /// use configcat::User;
+    ///
+    /// fn fetch_tier() -> String {
+    ///     "premium".to_owned()
+    /// }
+    ///
+    /// let user = User::new("user-id")
+    ///     .custom_lazy("Tier", || fetch_tier());
+    ///
+    /// assert_eq!("premium", user.get("Tier").unwrap().to_string().as_str());
+    /// ```
+    pub fn custom_lazy<T, F>(mut self, key: &str, compute: F) -> Self
+    where
+        T: Into<UserValue>,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        if key == Self::IDENTIFIER || key == Self::EMAIL || key == Self::COUNTRY {
+            return self;
+        }
+        self.attributes.remove(key);
+        self.lazy_attributes.insert(
+            key.to_owned(),
+            LazyValue {
+                compute: Arc::new(move || compute().into()),
+                value: OnceLock::new(),
+            },
+        );
+        self
+    }
+
     /// Returns a user attribute's [`UserValue`] identified by the given `key`.
     ///
     /// If the attribute doesn't exist, [`None`] is returned.
@@ -170,49 +246,87 @@ impl User {
     /// assert_eq!("user-id", user.get(User::IDENTIFIER).unwrap().to_string().as_str());
     /// ```
     pub fn get(&self, key: &str) -> Option<&UserValue> {
-        self.attributes.get(key)
+        self.attributes.get(key).or_else(|| {
+            self.lazy_attributes
+                .get(key)
+                .map(|lazy| lazy.value.get_or_init(|| (lazy.compute)()))
+        })
+    }
+
+    /// Returns the user to evaluate with, which is a clone with no computed lazy attribute values
+    /// when the user has lazy attributes.
+    pub(crate) fn for_evaluation(&self) -> Cow<'_, User> {
+        if self.lazy_attributes.is_empty() {
+            Cow::Borrowed(self)
+        } else {
+            Cow::Owned(self.clone())
+        }
+    }
+}
+
+impl Clone for LazyValue {
+    fn clone(&self) -> Self {
+        Self {
+            compute: Arc::clone(&self.compute),
+            value: OnceLock::new(),
+        }
+    }
+}
+
+impl Debug for LazyValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.value.get() {
+            Some(value) => write!(f, "Lazy({value:?})"),
+            None => f.write_str("Lazy(<not computed>)"),
+        }
     }
 }
 
 impl UserValue {
-    #![allow(clippy::cast_precision_loss)]
-    pub(crate) fn as_str(&self) -> (String, bool) {
+    /// Returns the canonical string representation of the value, the one used by text comparators
+    /// and when hashing attribute values for sensitive comparators and percentage options.
+    ///
+    /// The formatting matches the other ConfigCat SDKs:
+    /// - Numbers in the `[1e-6, 1e21)` absolute range are formatted in decimal notation without trailing
+    ///   zeros (`0.12345`, `125`); zero is always `0`.
+    /// - Other numbers use exponent notation with an explicit exponent sign (`-1.23456789e+96`, `1e-7`).
+    /// - `NaN`, `Infinity` and `-Infinity` are formatted as such.
+    /// - Dates are formatted as the number of seconds elapsed since the Unix epoch, with millisecond
+    ///   precision (`1680307199.999`).
+    /// - String lists are formatted as JSON arrays (`["read","write"]`).
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    /// use configcat::UserValue;
+    ///
+    /// assert_eq!("-1.23456789e+96", UserValue::Float(-1.23456789e96).canonical_string());
+    /// assert_eq!("1e-7", UserValue::Float(1e-7).canonical_string());
+    /// assert_eq!("0", UserValue::Float(-0.0).canonical_string());
+    /// assert_eq!("Infinity", UserValue::Float(f64::INFINITY).canonical_string());
+    /// ```
+    #[allow(clippy::cast_precision_loss)]
+    pub fn canonical_string(&self) -> String {
         match self {
-            UserValue::String(val) => (val.clone(), false),
-            UserValue::Float(val) => {
-                if val.is_nan() {
-                    ("NaN".to_owned(), true)
-                } else if val.is_infinite() && val.is_sign_positive() {
-                    ("Infinity".to_owned(), true)
-                } else if val.is_infinite() && val.is_sign_negative() {
-                    ("-Infinity".to_owned(), true)
-                } else if (1e-6..1e21).contains(&val.abs()) {
-                    (val.to_string(), true)
-                } else {
-                    let sc = format!("{val:+e}");
-                    if val.abs() > 1.0 {
-                        (sc.replace('e', "e+"), true)
-                    } else {
-                        (sc, true)
-                    }
-                }
-            }
-            UserValue::SemVer(val) => (val.to_string(), true),
-            UserValue::Int(val) => (val.to_string(), true),
-            UserValue::UInt(val) => (val.to_string(), true),
+            UserValue::String(val) => val.clone(),
+            UserValue::Float(val) => canonical_f64_string(*val),
+            UserValue::SemVer(val) => val.to_string(),
+            UserValue::Int(val) => val.to_string(),
+            UserValue::UInt(val) => val.to_string(),
             UserValue::DateTime(val) => {
-                (((val.timestamp_millis() as f64) / 1000.0).to_string(), true)
-            }
-            UserValue::StringVec(val) => {
-                let ser = serde_json::to_string(val);
-                match ser {
-                    Ok(val) => (val, true),
-                    Err(_) => (String::default(), true),
-                }
+                canonical_f64_string((val.timestamp_millis() as f64) / 1000.0)
             }
+            UserValue::StringVec(val) => serde_json::to_string(val).unwrap_or_default(),
         }
     }
 
+    pub(crate) fn as_str(&self) -> (String, bool) {
+        (
+            self.canonical_string(),
+            !matches!(self, UserValue::String(_)),
+        )
+    }
+
     #[allow(clippy::cast_precision_loss)]
     pub(crate) fn as_float(&self) -> Option<f64> {
         match self {
@@ -222,10 +336,7 @@ impl UserValue {
                     "Infinity" | "+Infinity" => Some(f64::INFINITY),
                     "-Infinity" => Some(f64::NEG_INFINITY),
                     "NaN" => Some(f64::NAN),
-                    _ => match trimmed.replace(',', ".").parse() {
-                        Ok(num) => Some(num),
-                        Err(_) => None,
-                    },
+                    _ => trimmed.replace(',', ".").parse().ok(),
                 }
             }
             UserValue::Int(val) => Some(*val as f64),
@@ -246,10 +357,7 @@ impl UserValue {
     pub(crate) fn as_semver(&self) -> Option<Version> {
         match self {
             UserValue::SemVer(val) => Some(val.clone()),
-            UserValue::String(val) => match utils::parse_semver(val) {
-                Ok(version) => Some(version),
-                Err(_) => None,
-            },
+            UserValue::String(val) => utils::parse_semver(val).ok(),
             _ => None,
         }
     }
@@ -259,16 +367,36 @@ impl UserValue {
             UserValue::StringVec(val) => Some(val.clone()),
             UserValue::String(val) => {
                 let result = serde_json::from_str::<Vec<String>>(val);
-                match result {
-                    Ok(vec) => Some(vec),
-                    Err(_) => None,
-                }
+                result.ok()
             }
             _ => None,
         }
     }
 }
 
+fn canonical_f64_string(val: f64) -> String {
+    if val.is_nan() {
+        "NaN".to_owned()
+    } else if val.is_infinite() {
+        if val.is_sign_positive() {
+            "Infinity".to_owned()
+        } else {
+            "-Infinity".to_owned()
+        }
+    } else if val == 0.0 {
+        "0".to_owned()
+    } else if (1e-6..1e21).contains(&val.abs()) {
+        val.to_string()
+    } else {
+        let sc = format!("{val:e}");
+        if val.abs() >= 1.0 {
+            sc.replacen('e', "e+", 1)
+        } else {
+            sc
+        }
+    }
+}
+
 impl Display for User {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match serde_json::to_string(&self.attributes) {
@@ -288,7 +416,41 @@ impl Index<&str> for User {
     type Output = UserValue;
 
     fn index(&self, index: &str) -> &Self::Output {
-        &self.attributes[index]
+        self.get(index).expect("user attribute is missing")
+    }
+}
+
+impl PartialEq for UserValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (UserValue::String(l), UserValue::String(r)) => l == r,
+            (UserValue::Int(l), UserValue::Int(r)) => l == r,
+            (UserValue::UInt(l), UserValue::UInt(r)) => l == r,
+            (UserValue::Float(l), UserValue::Float(r)) => {
+                utils::canonical_f64_bits(*l) == utils::canonical_f64_bits(*r)
+            }
+            (UserValue::DateTime(l), UserValue::DateTime(r)) => l == r,
+            (UserValue::StringVec(l), UserValue::StringVec(r)) => l == r,
+            (UserValue::SemVer(l), UserValue::SemVer(r)) => l == r,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for UserValue {}
+
+impl Hash for UserValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            UserValue::String(val) => val.hash(state),
+            UserValue::Int(val) => val.hash(state),
+            UserValue::UInt(val) => val.hash(state),
+            UserValue::Float(val) => utils::canonical_f64_bits(*val).hash(state),
+            UserValue::DateTime(val) => val.hash(state),
+            UserValue::StringVec(val) => val.hash(state),
+            UserValue::SemVer(val) => val.hash(state),
+        }
     }
 }
 
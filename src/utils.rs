@@ -1,21 +1,52 @@
 use semver::{Error, Version};
 use sha1::{Digest, Sha1};
 use sha2::Sha256;
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::SystemTime;
+use tokio::task::JoinHandle;
+
+use crate::constants::{CONFIG_FORMAT_VERSION, OFFLINE_ENV_VAR};
+use crate::eval::hashing::HashScheme;
 
 pub fn sha1(payload: &str) -> String {
     let hash = Sha1::digest(payload);
     base16ct::lower::encode_string(&hash)
 }
 
-pub fn sha256(payload: &str, salt: &str, ctx_salt: &str) -> String {
-    let mut cont = String::with_capacity(payload.len() + salt.len() + ctx_salt.len());
-    cont.push_str(payload);
-    cont.push_str(salt);
-    cont.push_str(ctx_salt);
-    let hash = Sha256::digest(cont);
+pub fn sha256(payload: &[u8], salt: &str, ctx_salt: &str) -> String {
+    let hash = Sha256::new()
+        .chain_update(payload)
+        .chain_update(salt)
+        .chain_update(ctx_salt)
+        .finalize();
     base16ct::lower::encode_string(&hash)
 }
 
+/// Produces the hash that ConfigCat uses to compare user attributes against confidential
+/// (hashed) comparison values of targeting rules.
+///
+/// The `config_salt` is the salt of the config JSON ([`crate::Config::salt`]), and the `context_salt`
+/// is the key of the feature flag or setting for targeting rule conditions, or the name of the segment
+/// for segment conditions.
+///
+/// # Examples
+///
+/// ```rust
+/// use configcat::hash_comparison_value;
+///
+/// let hashed = hash_comparison_value("jane@example.com", "config-salt", "flagKey");
+/// assert_eq!(hashed.len(), 64);
+/// ```
+pub fn hash_comparison_value(value: &str, config_salt: &str, context_salt: &str) -> String {
+    HashScheme::for_format_version(CONFIG_FORMAT_VERSION).comparison_hash(
+        value.as_bytes(),
+        config_salt,
+        context_salt,
+    )
+}
+
 pub fn parse_semver(input: &str) -> Result<Version, Error> {
     let mut input_mut = input.trim();
     if let Some((first, _)) = input.split_once('+') {
@@ -24,9 +55,97 @@ pub fn parse_semver(input: &str) -> Result<Version, Error> {
     Version::parse(input_mut)
 }
 
+/// Parses `input` like [`parse_semver`], but also accepts a leading `v` / `V` and whitespace around the
+/// build metadata. The returned flag tells whether the leniency was needed to parse the version.
+pub fn parse_semver_lenient(input: &str) -> Result<(Version, bool), Error> {
+    if let Ok(version) = parse_semver(input) {
+        return Ok((version, false));
+    }
+    let trimmed = input.trim();
+    let without_meta = trimmed
+        .split_once('+')
+        .map_or(trimmed, |(first, _)| first)
+        .trim();
+    let without_prefix = without_meta
+        .strip_prefix(['v', 'V'])
+        .unwrap_or(without_meta);
+    Version::parse(without_prefix).map(|version| (version, true))
+}
+
+/// Returns a pseudo-random number in the `(0, 1]` range, good enough for jittering but not for cryptography.
+pub fn random_unit() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(since_epoch) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        hasher.write_u128(since_epoch.as_nanos());
+    }
+    let bits = u32::try_from(hasher.finish() >> 32).unwrap_or_default();
+    (f64::from(bits) + 1.0) / (f64::from(u32::MAX) + 1.0)
+}
+
+/// Returns the bit pattern of `value` with `-0.0` mapped to `0.0` and every NaN mapped to the same NaN,
+/// so floats can be compared and hashed consistently.
+pub fn canonical_f64_bits(value: f64) -> u64 {
+    if value == 0.0 {
+        0.0_f64.to_bits()
+    } else if value.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        value.to_bits()
+    }
+}
+
+/// Masks all but the last 6 characters of the SDK key, so it can be shared for diagnostic purposes.
+pub fn mask_sdk_key(sdk_key: &str) -> String {
+    let visible = 6;
+    let len = sdk_key.chars().count();
+    if len <= visible {
+        return "*".repeat(len);
+    }
+    let mut masked = "*".repeat(len - visible);
+    masked.extend(sdk_key.chars().skip(len - visible));
+    masked
+}
+
+/// Reports whether the SDK's network activity is switched off by the [`OFFLINE_ENV_VAR`] environment variable.
+/// The variable is read on each call, so the switch takes effect without rebuilding the client.
+pub fn is_offline_by_env() -> bool {
+    std::env::var(OFFLINE_ENV_VAR).is_ok_and(|value| is_offline_switch_value(&value))
+}
+
+/// Spawns `future` on the tokio runtime as a task named `name` and returns its handle. The name is visible in runtime
+/// diagnostics (e.g. tokio-console) when the SDK is built with the `tracing` feature and `--cfg tokio_unstable`.
+pub fn spawn_named<F>(name: &str, future: F) -> JoinHandle<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    #[cfg(all(tokio_unstable, feature = "tracing"))]
+    {
+        tokio::task::Builder::new()
+            .name(name)
+            .spawn(future)
+            .expect("spawning a task on the current runtime never fails")
+    }
+    #[cfg(not(all(tokio_unstable, feature = "tracing")))]
+    {
+        _ = name;
+        tokio::spawn(future)
+    }
+}
+
+fn is_offline_switch_value(value: &str) -> bool {
+    let value = value.trim();
+    value == "1" || value.eq_ignore_ascii_case("true")
+}
+
 #[cfg(test)]
 mod utils_test {
+    use crate::utils::canonical_f64_bits;
+    use crate::utils::hash_comparison_value;
+    use crate::utils::is_offline_switch_value;
+    use crate::utils::mask_sdk_key;
     use crate::utils::parse_semver;
+    use crate::utils::parse_semver_lenient;
+    use crate::utils::random_unit;
     use crate::utils::sha1;
     use crate::utils::sha256;
 
@@ -37,9 +156,47 @@ mod utils_test {
             "683231cec21572ae3afd898a1b1487f6b9193ebb"
         );
         assert_eq!(
-            sha256("test_payload", "salt", "ctx_salt"),
+            sha256(b"test_payload", "salt", "ctx_salt"),
             "5ee9b44b3b90bedd9441b256c429f862ceb2ea847a58a8e33d8052da141e47aa"
         );
+        assert_eq!(
+            hash_comparison_value("test_payload", "salt", "ctx_salt"),
+            sha256(b"test_payload", "salt", "ctx_salt")
+        );
+    }
+
+    #[test]
+    fn canonical_float_bits() {
+        assert_eq!(canonical_f64_bits(0.0), canonical_f64_bits(-0.0));
+        assert_eq!(canonical_f64_bits(f64::NAN), canonical_f64_bits(-f64::NAN));
+        assert_ne!(canonical_f64_bits(1.5), canonical_f64_bits(-1.5));
+    }
+
+    #[test]
+    fn masked_sdk_key() {
+        assert_eq!(mask_sdk_key("abc"), "***");
+        assert_eq!(
+            mask_sdk_key("configcat-sdk-1/abcdef"),
+            "****************abcdef"
+        );
+    }
+
+    #[test]
+    fn offline_switch_value() {
+        assert!(is_offline_switch_value("1"));
+        assert!(is_offline_switch_value("true"));
+        assert!(is_offline_switch_value(" TRUE "));
+        assert!(!is_offline_switch_value("0"));
+        assert!(!is_offline_switch_value("false"));
+        assert!(!is_offline_switch_value(""));
+    }
+
+    #[test]
+    fn random_unit_range() {
+        for _ in 0..1000 {
+            let r = random_unit();
+            assert!(r > 0.0 && r <= 1.0);
+        }
     }
 
     #[test]
@@ -48,4 +205,32 @@ mod utils_test {
             .unwrap()
             .eq(&parse_semver("1.0.0-alpha").unwrap()));
     }
+
+    #[test]
+    fn semver_lenient() {
+        let expected = parse_semver("1.2.3").unwrap();
+        assert_eq!(
+            parse_semver_lenient("1.2.3").unwrap(),
+            (expected.clone(), false)
+        );
+        assert_eq!(
+            parse_semver_lenient("1.2.3+build5").unwrap(),
+            (expected.clone(), false)
+        );
+        assert_eq!(
+            parse_semver_lenient("v1.2.3+build5").unwrap(),
+            (expected.clone(), true)
+        );
+        assert_eq!(
+            parse_semver_lenient(" V1.2.3 ").unwrap(),
+            (expected.clone(), true)
+        );
+        assert_eq!(
+            parse_semver_lenient(" 1.2.3 + build5").unwrap(),
+            (expected, true)
+        );
+        assert!(parse_semver("v1.2.3").is_err());
+        assert!(parse_semver_lenient("version 1.2.3").is_err());
+        assert!(parse_semver_lenient("1.2").is_err());
+    }
 }
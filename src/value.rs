@@ -1,5 +1,7 @@
+use crate::utils::canonical_f64_bits;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 
 /// Represents the value of a feature flag or setting.
 ///
@@ -11,7 +13,10 @@ use std::fmt::{Display, Formatter};
 /// let bool_val = Value::Bool(true);
 /// let int_val = Value::Int(42);
 /// ```
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+///
+/// Values can be used as [`std::collections::HashMap`] keys: [`Value::Float`]s are compared and hashed
+/// by their bit pattern, where `0.0` equals `-0.0` and every NaN equals each other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Value {
     /// A bool feature flag's value.
@@ -92,6 +97,72 @@ impl Value {
         }
         None
     }
+
+    /// Converts the value into a user-defined type that implements [`TryFrom<Value>`].
+    ///
+    /// Types with such an implementation can also be evaluated directly with [`crate::Client::get_custom_value`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the `T::try_from` conversion.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use configcat::Value;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum RolloutStage {
+    ///     Internal,
+    ///     Beta,
+    ///     Public,
+    /// }
+    ///
+    /// impl TryFrom<Value> for RolloutStage {
+    ///     type Error = String;
+    ///
+    ///     fn try_from(value: Value) -> Result<Self, Self::Error> {
+    ///         match value.as_int() {
+    ///             Some(0) => Ok(RolloutStage::Internal),
+    ///             Some(1) => Ok(RolloutStage::Beta),
+    ///             Some(2) => Ok(RolloutStage::Public),
+    ///             _ => Err(format!("'{value}' is not a rollout stage")),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let stage = Value::Int(1).try_into_custom::<RolloutStage>();
+    /// assert_eq!(stage, Ok(RolloutStage::Beta));
+    /// ```
+    pub fn try_into_custom<T: TryFrom<Value>>(self) -> Result<T, T::Error> {
+        T::try_from(self)
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Bool(l), Value::Bool(r)) => l == r,
+            (Value::Int(l), Value::Int(r)) => l == r,
+            (Value::Float(l), Value::Float(r)) => canonical_f64_bits(*l) == canonical_f64_bits(*r),
+            (Value::String(l), Value::String(r)) => l == r,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Bool(val) => val.hash(state),
+            Value::Int(val) => val.hash(state),
+            Value::Float(val) => canonical_f64_bits(*val).hash(state),
+            Value::String(val) => val.hash(state),
+        }
+    }
 }
 
 impl Display for Value {
@@ -119,7 +190,7 @@ impl OptionalValueDisplay for Option<&Value> {
 }
 
 /// Represents a primitive type that can describe the value of a feature flag or setting.
-pub trait ValuePrimitive: Into<Value> {
+pub trait ValuePrimitive: TryInto<Value> {
     /// Reads the primitive value from a [`Value`].
     fn from_value(value: &Value) -> Option<Self>;
 }
@@ -141,3 +212,39 @@ primitive_impl!(Value Float as_float f64);
 primitive_impl!(Value Int as_int i64);
 primitive_impl!(Value Bool as_bool bool);
 from_val_to_enum_into!(Value String &str);
+
+impl TryFrom<serde_json::Value> for Value {
+    type Error = serde_json::Value;
+
+    /// Converts a JSON bool, number or text to a [`Value`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the given JSON value back when it has no [`Value`] counterpart: `null`, an array, an object,
+    /// or a whole number that doesn't fit into an [`i64`].
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        match value {
+            serde_json::Value::Bool(val) => Ok(Value::Bool(val)),
+            serde_json::Value::Number(ref num) => match (num.as_i64(), num.as_f64()) {
+                (Some(val), _) => Ok(Value::Int(val)),
+                (None, Some(val)) if num.is_f64() => Ok(Value::Float(val)),
+                _ => Err(value),
+            },
+            serde_json::Value::String(val) => Ok(Value::String(val)),
+            other => Err(other),
+        }
+    }
+}
+
+impl ValuePrimitive for serde_json::Value {
+    /// Reads the value as a [`serde_json::Value`]. Bool, whole number, decimal number and text
+    /// values are mapped to their JSON counterparts, non-finite decimal numbers become `null`.
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bool(val) => Some(serde_json::Value::Bool(*val)),
+            Value::Int(val) => Some(serde_json::Value::from(*val)),
+            Value::Float(val) => Some(serde_json::Value::from(*val)),
+            Value::String(val) => Some(serde_json::Value::String(val.clone())),
+        }
+    }
+}
@@ -1,9 +1,10 @@
 #![allow(dead_code)]
 #![allow(clippy::type_complexity)]
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use configcat::OverrideBehavior::{LocalOnly, LocalOverRemote, RemoteOverLocal};
-use configcat::{Client, FileDataSource, OverrideBehavior, User, UserValue};
+use configcat::{Client, EvalOptions, FileDataSource, OverrideBehavior, RuleOutcome, User, UserValue, Value};
+use std::collections::HashSet;
 use std::str::FromStr;
 
 #[tokio::test]
@@ -29,18 +30,18 @@ async fn prerequisite_flag_overrides() {
 
     for test in tests {
         let mut builder = Client::builder("configcat-sdk-1/JcPbCGl_1E-K9M-fJOyKyQ/JoGwdqJZQ0K2xDy7LnbyOg");
-        if test.3.is_some() {
-            builder = builder.overrides(Box::new(FileDataSource::new("tests/data/test_override_flagdependency_v6.json").unwrap()), test.3.unwrap());
+        if let Some(behavior) = test.3 {
+            builder = builder.overrides(Box::new(FileDataSource::new("tests/data/test_override_flagdependency_v6.json").unwrap()), behavior);
         }
         let client = builder.build().unwrap();
 
         let user = User::new(test.1).email(test.2);
         let details = client.get_flag_details(test.0, Some(user)).await;
 
-        if test.4.is_none() {
-            assert!(details.value.is_none());
+        if let Some(expected) = test.4 {
+            assert_eq!(details.value.unwrap().as_str().unwrap(), expected);
         } else {
-            assert_eq!(details.value.unwrap().as_str().unwrap(), test.4.unwrap());
+            assert!(details.value.is_none());
         }
     }
 }
@@ -60,18 +61,18 @@ async fn segment_overrides() {
 
     for test in tests {
         let mut builder = Client::builder("configcat-sdk-1/JcPbCGl_1E-K9M-fJOyKyQ/h99HYXWWNE2bH8eWyLAVMA");
-        if test.3.is_some() {
-            builder = builder.overrides(Box::new(FileDataSource::new("tests/data/test_override_segments_v6.json").unwrap()), test.3.unwrap());
+        if let Some(behavior) = test.3 {
+            builder = builder.overrides(Box::new(FileDataSource::new("tests/data/test_override_segments_v6.json").unwrap()), behavior);
         }
         let client = builder.build().unwrap();
 
         let user = User::new(test.1).email(test.2);
         let details = client.get_flag_details(test.0, Some(user)).await;
 
-        if test.4.is_none() {
-            assert!(details.value.is_none());
+        if let Some(expected) = test.4 {
+            assert_eq!(details.value.unwrap().as_bool().unwrap(), expected);
         } else {
-            assert_eq!(details.value.unwrap().as_bool().unwrap(), test.4.unwrap());
+            assert!(details.value.is_none());
         }
     }
 }
@@ -94,13 +95,13 @@ async fn matched_eval_rule_percentage_opts() {
 
     for test in tests {
         let mut user = None;
-        if test.1.is_some() {
-            let mut u = User::new(test.1.unwrap());
-            if test.2.is_some() {
-                u = u.email(test.2.unwrap());
+        if let Some(id) = test.1 {
+            let mut u = User::new(id);
+            if let Some(email) = test.2 {
+                u = u.email(email);
             }
-            if test.3.is_some() {
-                u = u.custom("PercentageBase", test.3.unwrap());
+            if let Some(percentage_base) = test.3 {
+                u = u.custom("PercentageBase", percentage_base);
             }
             user = Some(u);
         }
@@ -145,6 +146,35 @@ async fn comp_attr_canonical_str_representation() {
     }
 }
 
+#[test]
+fn canonical_string() {
+    let tests: Vec<(UserValue, &str)> = vec![
+        (UserValue::Float(0.12345), "0.12345"),
+        (UserValue::Float(0.0), "0"),
+        (UserValue::Float(-0.0), "0"),
+        (UserValue::Float(1e-6), "0.000001"),
+        (UserValue::Float(1e-7), "1e-7"),
+        (UserValue::Float(1e20), "100000000000000000000"),
+        (UserValue::Float(1e21), "1e+21"),
+        (UserValue::Float(-1.23456789e96), "-1.23456789e+96"),
+        (UserValue::Float(-12345.6789E-100), "-1.23456789e-96"),
+        (UserValue::Float(f64::NAN), "NaN"),
+        (UserValue::Float(f64::INFINITY), "Infinity"),
+        (UserValue::Float(f64::NEG_INFINITY), "-Infinity"),
+        (UserValue::Int(-125), "-125"),
+        (UserValue::UInt(125), "125"),
+        (DateTime::<Utc>::from_str("2023-03-31T23:59:59.999Z").unwrap().into(), "1680307199.999"),
+        (DateTime::<Utc>::from_str("2023-04-01T00:00:00Z").unwrap().into(), "1680307200"),
+        (vec!["read", "Write", " eXecute "].into(), r#"["read","Write"," eXecute "]"#),
+        (UserValue::StringVec(vec![]), "[]"),
+        ("text".into(), "text"),
+    ];
+
+    for test in tests {
+        assert_eq!(test.0.canonical_string(), test.1);
+    }
+}
+
 #[tokio::test]
 async fn spec_chars() {
     let tests: Vec<(&str, &str, &str)> = vec![("specialCharacters", "äöüÄÖÜçéèñışğâ¢™✓😀", "äöüÄÖÜçéèñışğâ¢™✓😀"), ("specialCharactersHashed", "äöüÄÖÜçéèñışğâ¢™✓😀", "äöüÄÖÜçéèñışğâ¢™✓😀")];
@@ -394,3 +424,79 @@ async fn attr_val_conv_str_vec() {
         assert_eq!(details.value.unwrap().as_str().unwrap(), test.4);
     }
 }
+
+#[tokio::test]
+async fn rule_outcomes() {
+    let client = Client::builder("local").overrides(Box::new(FileDataSource::new("tests/data/comparison_attribute_trimming.json").unwrap()), LocalOnly).build().unwrap();
+
+    let details = client.get_flag_details("arraycontainsanyof", Some(User::new("12345").country("[\"USA\"]"))).await;
+    assert_eq!(details.matched_rule_index, Some(0));
+    assert_eq!(details.rule_outcomes, vec![RuleOutcome::Matched]);
+
+    let details = client.get_flag_details("arraycontainsanyof", None).await;
+    assert_eq!(details.matched_rule_index, None);
+    assert_eq!(details.rule_outcomes, vec![RuleOutcome::UserMissing]);
+
+    let details = client.get_flag_details("arraycontainsanyof", Some(User::new("12345"))).await;
+    assert_eq!(details.matched_rule_index, None);
+    assert_eq!(details.rule_outcomes, vec![RuleOutcome::AttributeMissing("Country".to_owned())]);
+
+    let details = client.get_flag_details("numberequals", Some(User::new("12345").custom("Number", "not-a-number"))).await;
+    assert_eq!(details.matched_rule_index, None);
+    assert_eq!(details.rule_outcomes, vec![RuleOutcome::AttributeInvalid("Number".to_owned())]);
+}
+
+#[tokio::test]
+async fn eval_options_now() {
+    let client = Client::builder("local").overrides(Box::new(FileDataSource::new("tests/data/comparison_attribute_trimming.json").unwrap()), LocalOnly).build().unwrap();
+    let future = EvalOptions {
+        now: Some(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap()),
+        ..EvalOptions::default()
+    };
+    let past = EvalOptions {
+        now: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+        ..EvalOptions::default()
+    };
+
+    let details = client.get_value_details("dateafter", String::default(), Some(User::new("12345"))).await;
+    assert_eq!(details.value, "no trim");
+    assert_eq!(details.rule_outcomes, vec![RuleOutcome::AttributeMissing("Date".to_owned())]);
+
+    let details = client.get_value_details_with_options("dateafter", String::default(), Some(User::new("12345")), &future).await;
+    assert_eq!(details.value, "19 trim");
+
+    let details = client.get_value_details_with_options("dateafter", String::default(), Some(User::new("12345").custom("Date", "1893456000")), &past).await;
+    assert_eq!(details.value, "no trim");
+
+    let details = client.get_value_details_with_options("datebefore", String::default(), Some(User::new("12345")), &past).await;
+    assert_eq!(details.value, "18 trim");
+}
+
+#[tokio::test]
+async fn eval_options_profile() {
+    let client = Client::builder("local").overrides(Box::new(FileDataSource::new("tests/data/test_override_segments_v6.json").unwrap()), LocalOnly).build().unwrap();
+    let profile = EvalOptions { profile: true, ..EvalOptions::default() };
+
+    let details = client.get_value_details("developerAndBetaUserSegment", false, Some(User::new("1").email("john@example.com"))).await;
+    assert!(details.value);
+    assert!(details.condition_timings.is_empty());
+
+    let details = client.get_value_details_with_options("developerAndBetaUserSegment", false, Some(User::new("1").email("john@example.com")), &profile).await;
+    assert!(details.value);
+    let timings: Vec<(&str, usize, usize, &str)> = details.condition_timings.iter().map(|t| (t.key.as_str(), t.rule_index, t.condition_index, t.comparator.as_str())).collect();
+    assert_eq!(timings, vec![("developerAndBetaUserSegment", 0, 0, "IS IN SEGMENT"), ("developerAndBetaUserSegment", 0, 1, "IS NOT IN SEGMENT")]);
+
+    let details = client.get_value_details_with_options("developerAndBetaUserSegment", false, Some(User::new("2").email("kate@example.com")), &profile).await;
+    assert!(!details.value);
+    assert_eq!(details.condition_timings.len(), 1);
+    assert_eq!(details.condition_timings[0].comparator, "IS IN SEGMENT");
+}
+
+#[test]
+fn value_hash() {
+    let values = HashSet::from([Value::Float(0.0), Value::Float(-0.0), Value::Float(f64::NAN), Value::Float(f64::NAN), Value::Int(0), Value::Bool(false)]);
+    assert_eq!(values.len(), 4);
+
+    let user_values = HashSet::from([UserValue::Float(1.5), UserValue::Float(1.5), UserValue::Int(1), UserValue::UInt(1), UserValue::from("1")]);
+    assert_eq!(user_values.len(), 4);
+}
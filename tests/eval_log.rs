@@ -5,9 +5,10 @@ use std::collections::HashMap;
 use std::fs;
 
 use configcat::OverrideBehavior::{LocalOnly, LocalOverRemote};
-use configcat::{Client, FileDataSource, MapDataSource, PollingMode, User, UserValue, Value};
+use configcat::{Client, FileDataSource, LogArea, MapDataSource, PollingMode, User, UserValue, Value};
+use log::LevelFilter;
 
-use crate::utils::{log_record_init, rand_sdk_key, RecordingLogger};
+use crate::utils::{log_record_init, produce_mock_path, rand_sdk_key, RecordingLogger};
 
 mod utils;
 
@@ -57,17 +58,69 @@ async fn prerequisite_comp_val_mismatch() {
         let client = Client::builder("configcat-sdk-1/JcPbCGl_1E-K9M-fJOyKyQ/JoGwdqJZQ0K2xDy7LnbyOg").overrides(Box::new(MapDataSource::from([(test.1, test.2)])), LocalOverRemote).build().unwrap();
 
         let details = client.get_flag_details(test.0, None).await;
-        if test.3.is_none() {
+        if let Some(expected) = test.3 {
+            assert_eq!(details.value.unwrap().as_str().unwrap(), expected);
+        } else {
             assert!(details.value.is_none());
 
             let logs = RecordingLogger::LOGS.take();
             assert!(logs.contains("Type mismatch between comparison value"));
-        } else {
-            assert_eq!(details.value.unwrap().as_str().unwrap(), test.3.unwrap());
         }
     }
 }
 
+#[tokio::test]
+async fn eval_log_area_level() {
+    log_record_init();
+
+    let client = Client::builder("local").overrides(Box::new(MapDataSource::from([("flag", Value::Bool(true))])), LocalOnly).build().unwrap();
+    _ = client.get_value("flag", false, None).await;
+    assert!(RecordingLogger::LOGS.take().contains("INFO [5000] Evaluating 'flag'"));
+
+    let client = Client::builder("local").overrides(Box::new(MapDataSource::from([("flag", Value::Bool(true))])), LocalOnly).log_level(LogArea::Evaluation, LevelFilter::Warn).build().unwrap();
+    _ = client.get_value("flag", false, None).await;
+    _ = client.get_value("missing", false, None).await;
+    let logs = RecordingLogger::LOGS.take();
+    assert!(!logs.contains("[5000]"));
+    assert!(logs.contains("Failed to evaluate setting 'missing'"));
+}
+
+#[tokio::test]
+async fn percentage_options_issues_logged_once() {
+    log_record_init();
+
+    let mut server = mockito::Server::new_async().await;
+    let (sdk_key, path) = produce_mock_path();
+    let body = r#"{"f":{"incomplete":{"t":1,"v":{"s":"a"},"p":[{"p":40,"v":{"s":"a"}},{"p":55,"v":{"s":"b"}}]}}}"#;
+    server.mock("GET", path.as_str()).with_status(200).with_header("ETag", "etag1").with_body(body).create_async().await;
+
+    let client = Client::builder(sdk_key.as_str()).base_url(server.url().as_str()).polling_mode(PollingMode::Manual).build().unwrap();
+    client.refresh().await.unwrap();
+    client.refresh().await.unwrap();
+    assert_eq!(RecordingLogger::LOGS.take().matches("WARNING [3009]").count(), 1);
+
+    let client = Client::builder(sdk_key.as_str()).base_url(server.url().as_str()).polling_mode(PollingMode::Manual).log_level(LogArea::Fetch, LevelFilter::Error).build().unwrap();
+    client.refresh().await.unwrap();
+    assert!(!RecordingLogger::LOGS.take().contains("[3009]"));
+}
+
+#[tokio::test]
+async fn evaluation_logging_disabled() {
+    log_record_init();
+
+    let client = Client::builder("local")
+        .overrides(Box::new(MapDataSource::from([("flag", Value::Bool(true))])), LocalOnly)
+        .log_level(LogArea::Evaluation, LevelFilter::Trace)
+        .evaluation_logging(false)
+        .build()
+        .unwrap();
+    _ = client.get_value("flag", false, None).await;
+    _ = client.get_value("missing", false, None).await;
+    let logs = RecordingLogger::LOGS.take();
+    assert!(!logs.contains("[5000]"));
+    assert!(logs.contains("Failed to evaluate setting 'missing'"));
+}
+
 #[tokio::test]
 async fn eval_log() {
     log_record_init();
@@ -0,0 +1,61 @@
+use axum_core::extract::{FromRequestParts, OptionalFromRequestParts};
+use configcat::{AttributeSource, ConfigCatUser, User, UserMapping, UserRejection, UserValue};
+use http::request::Parts;
+use http::Request;
+
+// {"sub":"user-1","email":"jane@example.com","roles":["admin","dev"],"age":42}
+const TOKEN: &str = "Bearer eyJhbGciOiJub25lIn0.eyJzdWIiOiJ1c2VyLTEiLCJlbWFpbCI6ImphbmVAZXhhbXBsZS5jb20iLCJyb2xlcyI6WyJhZG1pbiIsImRldiJdLCJhZ2UiOjQyfQ.";
+
+fn parts(headers: &[(&str, &str)], mapping: Option<UserMapping>) -> Parts {
+    let mut builder = Request::builder();
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    if let Some(mapping) = mapping {
+        builder = builder.extension(mapping);
+    }
+    builder.body(()).unwrap().into_parts().0
+}
+
+#[tokio::test]
+async fn user_from_claims_headers_and_cookies() {
+    let mapping = UserMapping::new(AttributeSource::Claim("sub".to_owned()))
+        .email(AttributeSource::Claim("email".to_owned()))
+        .country(AttributeSource::Header("X-Country".to_owned()))
+        .custom("Roles", AttributeSource::Claim("roles".to_owned()))
+        .custom("Age", AttributeSource::Claim("age".to_owned()))
+        .custom("Plan", AttributeSource::Cookie("plan".to_owned()))
+        .custom("Missing", AttributeSource::Header("X-Missing".to_owned()));
+    let mut parts = parts(&[("Authorization", TOKEN), ("X-Country", "Hungary"), ("Cookie", "session=abc; plan=pro")], Some(mapping));
+
+    let ConfigCatUser(user) = <ConfigCatUser as FromRequestParts<()>>::from_request_parts(&mut parts, &()).await.unwrap();
+
+    assert_eq!(user[User::IDENTIFIER].to_string(), "user-1");
+    assert_eq!(user[User::EMAIL].to_string(), "jane@example.com");
+    assert_eq!(user[User::COUNTRY].to_string(), "Hungary");
+    assert!(matches!(user.get("Roles"), Some(UserValue::StringVec(roles)) if *roles == vec!["admin".to_owned(), "dev".to_owned()]));
+    assert!(matches!(user.get("Age"), Some(UserValue::Int(42))));
+    assert_eq!(user["Plan"].to_string(), "pro");
+    assert!(user.get("Missing").is_none());
+}
+
+#[tokio::test]
+async fn missing_identifier() {
+    let mapping = UserMapping::new(AttributeSource::Header("X-User-Id".to_owned()));
+
+    let mut required = parts(&[], Some(mapping.clone()));
+    let result = <ConfigCatUser as FromRequestParts<()>>::from_request_parts(&mut required, &()).await;
+    assert!(matches!(result, Err(UserRejection::MissingIdentifier)));
+
+    let mut optional = parts(&[], Some(mapping));
+    let result = <ConfigCatUser as OptionalFromRequestParts<()>>::from_request_parts(&mut optional, &()).await;
+    assert!(result.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn missing_mapping() {
+    let mut parts = parts(&[("X-User-Id", "user-1")], None);
+
+    let result = <ConfigCatUser as FromRequestParts<()>>::from_request_parts(&mut parts, &()).await;
+    assert!(matches!(result, Err(UserRejection::MissingMapping)));
+}
@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+
+use crate::utils::{construct_bool_json_payload, produce_mock_path};
+use configcat::ffi::{configcat_client_free, configcat_client_new, configcat_get_value, configcat_refresh, configcat_string_free, ConfigCatClient};
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+mod utils;
+
+fn c_str(str: &str) -> CString {
+    CString::new(str).unwrap()
+}
+
+unsafe fn take_string(str: *mut c_char) -> String {
+    assert!(!str.is_null());
+    let result = CStr::from_ptr(str).to_str().unwrap().to_owned();
+    configcat_string_free(str);
+    result
+}
+
+unsafe fn get_value(client: *mut ConfigCatClient, key: &str, default_json: &str, user_json: Option<&str>) -> Result<String, String> {
+    let user_json = user_json.map(c_str);
+    let mut error = ptr::null_mut();
+    let value = configcat_get_value(client, c_str(key).as_ptr(), c_str(default_json).as_ptr(), user_json.as_ref().map_or(ptr::null(), |user| user.as_ptr()), &mut error);
+    if value.is_null() {
+        Err(take_string(error))
+    } else {
+        Ok(take_string(value))
+    }
+}
+
+#[test]
+fn ffi_client() {
+    let mut server = mockito::Server::new();
+    let (sdk_key, path) = produce_mock_path();
+    let m = server.mock("GET", path.as_str()).with_status(200).with_body(construct_bool_json_payload("testKey", true)).create();
+
+    unsafe {
+        let options = c_str(&format!(r#"{{"sdk_key": "{sdk_key}", "base_url": "{}", "polling_mode": "manual"}}"#, server.url()));
+        let mut error = ptr::null_mut();
+        let client = configcat_client_new(options.as_ptr(), &mut error);
+        assert!(!client.is_null());
+        assert!(error.is_null());
+
+        assert_eq!(get_value(client, "testKey", "false", None).unwrap(), "false");
+        assert_eq!(configcat_refresh(client, &mut error), 0);
+        assert_eq!(get_value(client, "testKey", "false", None).unwrap(), "true");
+        assert_eq!(get_value(client, "testKey", "false", Some(r#"{"Identifier": "id", "Email": "john@example.com", "Age": 30, "Tags": ["beta"]}"#)).unwrap(), "true");
+        assert_eq!(get_value(client, "missingKey", r#""default""#, None).unwrap(), r#""default""#);
+
+        assert_eq!(get_value(client, "testKey", "not json", None).unwrap_err(), "The default value JSON is invalid. (expected ident at line 1 column 2)");
+        assert_eq!(get_value(client, "testKey", "false", Some(r#"{"Email": "john@example.com"}"#)).unwrap_err(), "The user JSON is missing the 'Identifier' attribute.");
+        assert_eq!(get_value(client, "testKey", "false", Some(r#"{"Identifier": "id", "Tags": [1]}"#)).unwrap_err(), "The user attribute 'Tags' must contain texts only.");
+
+        configcat_client_free(client);
+    }
+    m.assert();
+}
+
+#[test]
+fn ffi_errors() {
+    unsafe {
+        let mut error = ptr::null_mut();
+        assert!(configcat_client_new(c_str(r#"{"polling_mode": "manual"}"#).as_ptr(), &mut error).is_null());
+        assert_eq!(take_string(error), "The SDK Key is missing from the options.");
+
+        let mut error = ptr::null_mut();
+        assert!(configcat_client_new(ptr::null(), &mut error).is_null());
+        assert_eq!(take_string(error), "The options JSON is NULL.");
+
+        assert!(configcat_client_new(c_str("[]").as_ptr(), ptr::null_mut()).is_null());
+
+        let mut error = ptr::null_mut();
+        assert_eq!(configcat_refresh(ptr::null_mut(), &mut error), -1);
+        assert_eq!(take_string(error), "The client is NULL.");
+
+        configcat_client_free(ptr::null_mut());
+        configcat_string_free(ptr::null_mut());
+    }
+}
+
+#[tokio::test]
+async fn ffi_panic_in_runtime() {
+    unsafe {
+        let client = configcat_client_new(c_str(r#"{"sdk_key": "configcat-sdk-1/PKDVCLf-Hq-h-kCzMp-L7Q/HhOWfwVtZ0mb30i9wi17GQ", "polling_mode": "manual", "offline": true}"#).as_ptr(), ptr::null_mut());
+        assert!(!client.is_null());
+
+        // blocking inside a tokio runtime panics, the panic is reported as an error instead of unwinding
+        let error = get_value(client, "testKey", "false", None).unwrap_err();
+        assert!(error.starts_with("The SDK panicked."), "{error}");
+
+        // dropping the client's runtime inside a tokio runtime panics too, so it's released on another thread
+        let client = client as usize;
+        std::thread::spawn(move || configcat_client_free(client as *mut ConfigCatClient)).join().unwrap();
+    }
+}
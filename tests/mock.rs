@@ -0,0 +1,144 @@
+#[cfg(feature = "network")]
+use configcat::ClientCacheState;
+use configcat::{CircuitState, Client, ClientBuilder, ConfigCache, ErrorKind, MemoryMode, MockResponse, PollingMode, ScriptedFetcher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const SDK_KEY: &str = "configcat-sdk-1/abcdefghijklmnopqrstuv/abcdefghijklmnopqrstuv";
+
+fn config_json(value: bool) -> String {
+    format!(r#"{{"f":{{"flag":{{"t":0,"v":{{"b":{value}}}}}}}}}"#)
+}
+
+fn builder(fetcher: &Arc<ScriptedFetcher>) -> ClientBuilder {
+    Client::builder(SDK_KEY).mock_fetcher(fetcher.clone())
+}
+
+#[derive(Clone, Default)]
+struct RecordingCache {
+    values: Arc<Mutex<Vec<String>>>,
+    reads: Arc<AtomicUsize>,
+}
+
+impl ConfigCache for RecordingCache {
+    fn read(&self, _key: &str) -> Option<String> {
+        self.reads.fetch_add(1, Ordering::SeqCst);
+        self.values.lock().unwrap().last().cloned()
+    }
+
+    fn write(&self, _key: &str, value: &str) {
+        self.values.lock().unwrap().push(value.to_owned());
+    }
+}
+
+#[tokio::test]
+async fn scripted_manual_refresh() {
+    let fetcher = Arc::new(ScriptedFetcher::new([
+        MockResponse::fetched(&config_json(true), "etag1"),
+        MockResponse::NotModified,
+        MockResponse::failed(ErrorKind::HttpRequestTimeout, "Request timed out."),
+        MockResponse::fetched(&config_json(false), "etag2"),
+    ]));
+    let cache = RecordingCache::default();
+    let client = builder(&fetcher).polling_mode(PollingMode::Manual).cache(Box::new(cache.clone())).build().unwrap();
+
+    client.refresh().await.unwrap();
+    assert!(client.get_value("flag", false, None).await);
+    assert!(cache.values.lock().unwrap().last().unwrap().contains("\netag1\n"));
+    client.refresh().await.unwrap();
+    let err = client.refresh().await.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HttpRequestTimeout);
+    assert!(client.get_value("flag", false, None).await);
+    client.refresh().await.unwrap();
+    assert!(!client.get_value("flag", true, None).await);
+
+    assert_eq!(fetcher.requests(), vec!["", "etag1", "etag1", "etag1"]);
+    let counters = client.fetch_counters();
+    assert_eq!((counters.fetched, counters.not_modified, counters.failed, counters.revision), (2, 1, 1, 2));
+}
+
+#[cfg(feature = "network")]
+#[tokio::test]
+async fn scripted_auto_poll() {
+    let fetcher = Arc::new(ScriptedFetcher::new([MockResponse::fetched(&config_json(true), "etag1")]));
+    let cache = RecordingCache::default();
+    let client = builder(&fetcher).polling_mode(PollingMode::AutoPoll(Duration::from_millis(100))).cache(Box::new(cache.clone())).build().unwrap();
+
+    let report = client.wait_for_ready(Duration::from_secs(5)).await.unwrap();
+    assert_eq!(report.state, ClientCacheState::HasUpToDateFlagData);
+    assert!(client.get_value("flag", false, None).await);
+
+    fetcher.push(MockResponse::fetched(&config_json(false), "etag2"));
+    tokio::time::sleep(Duration::from_millis(350)).await;
+    assert!(!client.get_value("flag", true, None).await);
+    assert!(fetcher.requests().len() >= 3);
+    assert!(cache.values.lock().unwrap().last().unwrap().contains("\netag2\n"));
+}
+
+#[tokio::test]
+async fn dropped_config_json() {
+    let fetcher = Arc::new(ScriptedFetcher::new([MockResponse::fetched(&config_json(true), "etag1"), MockResponse::NotModified]));
+    let cache = RecordingCache::default();
+    let client = builder(&fetcher).polling_mode(PollingMode::Manual).cache(Box::new(cache.clone())).memory_mode(MemoryMode::Bounded).build().unwrap();
+
+    client.refresh().await.unwrap();
+    let reads = cache.reads.load(Ordering::SeqCst);
+    // the config JSON dropped from memory is restored from the cache read before the refresh
+    client.refresh().await.unwrap();
+    assert_eq!(cache.reads.load(Ordering::SeqCst), reads + 1);
+    assert!(client.get_value("flag", false, None).await);
+    let values = cache.values.lock().unwrap().clone();
+    assert_eq!(values.len(), 2);
+    assert!(values[1].ends_with(&format!("\netag1\n{}", config_json(true))));
+    let entry = client.export_cache_entry().await.unwrap();
+    assert_eq!(entry.config_json, config_json(true));
+
+    let fetcher = Arc::new(ScriptedFetcher::new([MockResponse::fetched(&config_json(true), "etag1")]));
+    let client = builder(&fetcher).polling_mode(PollingMode::Manual).memory_mode(MemoryMode::Bounded).build().unwrap();
+    client.refresh().await.unwrap();
+    assert!(client.get_value("flag", false, None).await);
+    assert!(client.export_cache_entry().await.is_none());
+}
+
+#[tokio::test]
+async fn scripted_invalid_config_json() {
+    let fetcher = Arc::new(ScriptedFetcher::new([MockResponse::fetched("{", "etag1")]));
+    let client = builder(&fetcher).polling_mode(PollingMode::Manual).build().unwrap();
+
+    let err = client.refresh().await.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidHttpResponseContent);
+    assert!(!client.get_value("flag", false, None).await);
+}
+
+#[tokio::test]
+async fn circuit_breaker() {
+    let timeout = MockResponse::failed(ErrorKind::HttpRequestTimeout, "Request timed out.");
+    let fetcher = Arc::new(ScriptedFetcher::new([timeout.clone(), timeout.clone(), timeout, MockResponse::fetched(&config_json(true), "etag1")]));
+    let states = Arc::new(Mutex::new(Vec::new()));
+    let recorded = states.clone();
+    let client = builder(&fetcher)
+        .polling_mode(PollingMode::Manual)
+        .circuit_breaker(2, Duration::from_millis(100))
+        .on_circuit_state_changed(move |state| recorded.lock().unwrap().push(state))
+        .build()
+        .unwrap();
+
+    assert_eq!(client.refresh().await.unwrap_err().kind, ErrorKind::HttpRequestTimeout);
+    assert_eq!(client.refresh().await.unwrap_err().kind, ErrorKind::HttpRequestTimeout);
+    assert_eq!(client.refresh().await.unwrap_err().kind, ErrorKind::CircuitOpen);
+    assert_eq!(fetcher.requests().len(), 2);
+    assert_eq!(*states.lock().unwrap(), vec![CircuitState::Open]);
+
+    // the failed probe opens the circuit again
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(client.refresh().await.unwrap_err().kind, ErrorKind::HttpRequestTimeout);
+    assert_eq!(client.refresh().await.unwrap_err().kind, ErrorKind::CircuitOpen);
+    assert_eq!(fetcher.requests().len(), 3);
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    client.refresh().await.unwrap();
+    assert!(client.get_value("flag", false, None).await);
+    assert_eq!(fetcher.requests().len(), 4);
+    assert_eq!(*states.lock().unwrap(), vec![CircuitState::Open, CircuitState::HalfOpen, CircuitState::Open, CircuitState::HalfOpen, CircuitState::Closed]);
+}
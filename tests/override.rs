@@ -1,12 +1,15 @@
 #![allow(dead_code)]
 
 use crate::utils::{construct_bool_json_payload, produce_mock_path};
+use chrono::{TimeDelta, Utc};
 use configcat::OverrideBehavior::{LocalOnly, LocalOverRemote, RemoteOverLocal};
 use configcat::Value::{Bool, Float, Int};
-use configcat::{Client, ClientCacheState, FileDataSource, MapDataSource, Value};
+use configcat::{AsyncOverrideDataSource, BucketingStrategy, Client, ClientCacheState, ClientDataSource, EvalOptions, FileDataSource, MapDataSource, OverrideError, OverrideLoadFuture, PollingMode, Ramp, RampDataSource, SemverParsing, Setting, TextNormalization, User, Value, WhitespaceTrimming};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 mod utils;
@@ -33,6 +36,16 @@ async fn file_complex() {
     assert_eq!(client.get_value("stringSetting", String::default(), None).await, "test".to_owned());
 }
 
+#[test]
+fn file_errors() {
+    assert!(matches!(FileDataSource::new("tests/data/missing.json"), Err(OverrideError::Io(_))));
+    assert!(matches!(FileDataSource::new("tests/data/test_json_invalid_value.json"), Err(OverrideError::InvalidValue { key }) if key == "objectSetting"));
+    match FileDataSource::new("tests/data/test_yaml.yml") {
+        Err(OverrideError::FullConfigParse(err)) => assert_eq!(err.line(), 1),
+        _ => panic!("expected a config JSON parse error"),
+    }
+}
+
 #[tokio::test]
 async fn map() {
     let mut server = mockito::Server::new_async().await;
@@ -53,7 +66,7 @@ async fn map() {
         .build()
         .unwrap();
 
-    assert!(matches!(client.wait_for_ready(Duration::from_secs(5)).await.unwrap(), ClientCacheState::HasLocalOverrideFlagDataOnly));
+    assert!(matches!(client.wait_for_ready_state(Duration::from_secs(5)).await.unwrap(), ClientCacheState::HasLocalOverrideFlagDataOnly));
     assert!(client.get_value("enabledFeature", false, None).await);
     assert!(!client.get_value("disabledFeature", true, None).await);
     assert_eq!(client.get_value("intSetting", 0, None).await, 5);
@@ -63,6 +76,28 @@ async fn map() {
     m.assert_async().await;
 }
 
+#[tokio::test]
+async fn map_conversions() {
+    let source = MapDataSource::try_from(serde_json::json!({"enabledFeature": true, "intSetting": 5, "doubleSetting": 1.2, "stringSetting": "test"})).unwrap();
+    let client = Client::builder("local").overrides(Box::new(source), LocalOnly).build().unwrap();
+
+    assert!(client.get_value("enabledFeature", false, None).await);
+    assert_eq!(client.get_value("intSetting", 0, None).await, 5);
+    assert_eq!(client.get_value("doubleSetting", 0.0, None).await, 1.2);
+    assert_eq!(client.get_value("stringSetting", String::default(), None).await, "test".to_owned());
+
+    let source = MapDataSource::from(BTreeMap::from([("enabledFeature".to_owned(), Bool(true)), ("intSetting".to_owned(), Int(5))]));
+    let client = Client::builder("local").overrides(Box::new(source), LocalOnly).build().unwrap();
+
+    assert!(client.get_value("enabledFeature", false, None).await);
+    assert_eq!(client.get_value("intSetting", 0, None).await, 5);
+
+    assert!(matches!(MapDataSource::try_from(serde_json::json!([true])), Err(OverrideError::SimplifiedParse(_))));
+    assert!(matches!(MapDataSource::try_from(serde_json::json!({"enabledFeature": true, "nullSetting": null})), Err(OverrideError::InvalidValue { key }) if key == "nullSetting"));
+    assert!(matches!(MapDataSource::try_from(serde_json::json!({"arraySetting": [1, 2]})), Err(OverrideError::InvalidValue { key }) if key == "arraySetting"));
+    assert!(matches!(MapDataSource::try_from(serde_json::json!({"objectSetting": {"value": 5}})), Err(OverrideError::InvalidValue { key }) if key == "objectSetting"));
+}
+
 #[tokio::test]
 async fn local_over_remote() {
     let mut server = mockito::Server::new_async().await;
@@ -81,6 +116,34 @@ async fn local_over_remote() {
     m.assert_async().await;
 }
 
+#[tokio::test]
+async fn override_report() {
+    let mut server = mockito::Server::new_async().await;
+    let (sdk_key, path) = produce_mock_path();
+    let m = server.mock("GET", path.as_str()).with_status(200).with_body(construct_bool_json_payload("fakeKey", false)).create_async().await;
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let hook_reports = Arc::clone(&reports);
+    let client = Client::builder(sdk_key.as_str())
+        .base_url(server.url().as_str())
+        .overrides(Box::new(MapDataSource::from([("fakeKey", Bool(true)), ("nonexisting", Bool(true))])), LocalOverRemote)
+        .on_overrides_applied(move |report| hook_reports.lock().unwrap().push(report.clone()))
+        .build()
+        .unwrap();
+
+    assert!(client.override_report().is_none());
+    assert!(client.get_value("fakeKey", false, None).await);
+
+    let report = client.override_report().unwrap();
+    assert_eq!(report.behavior, LocalOverRemote);
+    assert_eq!(report.overridden, vec!["fakeKey".to_owned()]);
+    assert!(report.ignored.is_empty());
+    assert_eq!(report.added, vec!["nonexisting".to_owned()]);
+    assert_eq!(*reports.lock().unwrap(), vec![(*report).clone()]);
+
+    m.assert_async().await;
+}
+
 #[tokio::test]
 async fn remote_over_local() {
     let mut server = mockito::Server::new_async().await;
@@ -99,6 +162,181 @@ async fn remote_over_local() {
     m.assert_async().await;
 }
 
+#[tokio::test]
+async fn ramp() {
+    let now = Utc::now();
+    let ramps = vec![
+        Ramp::new("started").step(now - TimeDelta::hours(2), 0).step(now - TimeDelta::hours(1), 50).step(now + TimeDelta::hours(1), 100),
+        Ramp::new("finished").step(now - TimeDelta::hours(1), 5).step(now - TimeDelta::minutes(1), 100),
+        Ramp::new("pending").step(now + TimeDelta::hours(1), 100),
+    ];
+    let client = Client::builder("local").async_overrides(Box::new(RampDataSource::new(ramps)), LocalOnly, Duration::from_secs(60)).build().unwrap();
+    client.wait_for_ready(Duration::from_secs(5)).await.unwrap();
+
+    let mut enabled = 0;
+    for i in 0..1000 {
+        let user = User::new(format!("user{i}").as_str());
+        if client.get_value("started", false, Some(user.clone())).await {
+            enabled += 1;
+        }
+        assert!(client.get_value("finished", false, Some(user.clone())).await);
+    }
+    assert!((400..600).contains(&enabled));
+    assert!(!client.get_value("started", true, None).await);
+    assert!(client.get_flag_details("pending", None).await.is_default_value);
+}
+
+#[tokio::test]
+async fn text_normalization() {
+    let build = |normalization| Client::builder("local").overrides(Box::new(FileDataSource::new("tests/data/test_text_normalization.json").unwrap()), LocalOnly).text_normalization(normalization).build().unwrap();
+    let precomposed = User::new("id").custom("City", "Caf\u{e9} Royal");
+    let decomposed = User::new("id").custom("City", "Cafe\u{301}");
+
+    let client = build(TextNormalization::None);
+    assert!(!client.get_value("cityEquals", false, Some(decomposed.clone())).await);
+    assert!(!client.get_value("cityStartsWith", false, Some(precomposed.clone())).await);
+
+    let options = EvalOptions {
+        text_normalization: Some(TextNormalization::Nfc),
+        ..EvalOptions::default()
+    };
+    assert!(client.get_value_details_with_options("cityEquals", false, Some(decomposed.clone()), &options).await.value);
+
+    let client = build(TextNormalization::Nfc);
+    assert!(client.get_value("cityEquals", false, Some(decomposed)).await);
+    assert!(client.get_value("cityStartsWith", false, Some(precomposed)).await);
+}
+
+#[tokio::test]
+async fn whitespace_trimming() {
+    let build = |trimming| Client::builder("local").overrides(Box::new(FileDataSource::new("tests/data/test_whitespace_trimming.json").unwrap()), LocalOnly).whitespace_trimming(trimming).build().unwrap();
+    let plan = |plan: &str| Some(User::new("id").custom("Plan", plan));
+
+    let client = build(WhitespaceTrimming::None);
+    assert!(!client.get_value("planEquals", false, plan(" enterprise\n")).await);
+    assert!(client.get_value("planIsOneOf", false, plan(" enterprise")).await);
+    assert!(!client.get_value("planIsOneOf", false, plan("pro")).await);
+
+    let client = build(WhitespaceTrimming::UserAttributes);
+    assert!(client.get_value("planEquals", false, plan(" enterprise\n")).await);
+    assert!(!client.get_value("planIsOneOf", false, plan(" enterprise")).await);
+
+    let client = build(WhitespaceTrimming::All);
+    assert!(client.get_value("planIsOneOf", false, plan(" enterprise")).await);
+    assert!(client.get_value("planIsOneOf", false, plan("pro\t")).await);
+}
+
+#[tokio::test]
+async fn bucketing_strategy() {
+    let build = |strategy| Client::builder("local").overrides(Box::new(FileDataSource::new("tests/data/test_bucketing_strategy.json").unwrap()), LocalOnly).bucketing_strategy(strategy).build().unwrap();
+    let tenant = |id: i64| Some(User::new("id").custom("TenantId", id));
+
+    let client = build(BucketingStrategy::NumericModulo);
+    assert!(client.get_value("tenantRollout", false, tenant(1029)).await);
+    assert!(client.get_value("tenantRollout", false, tenant(-71)).await);
+    assert!(!client.get_value("tenantRollout", false, tenant(30)).await);
+    assert!(!client.get_value("tenantRollout", false, tenant(2099)).await);
+    assert!(client.get_value("tenantRollout", false, Some(User::new("id").custom("TenantId", "12"))).await);
+
+    let hashed = build(BucketingStrategy::Hash);
+    let non_numeric = Some(User::new("id").custom("TenantId", "tenant-a"));
+    assert_eq!(client.get_value("tenantRollout", false, non_numeric.clone()).await, hashed.get_value("tenantRollout", false, non_numeric).await);
+}
+
+#[tokio::test]
+async fn semver_parsing() {
+    let build = |parsing| Client::builder("local").overrides(Box::new(FileDataSource::new("tests/data/test_semver_parsing.json").unwrap()), LocalOnly).semver_parsing(parsing).build().unwrap();
+    let version = |version: &str| Some(User::new("id").custom("Version", version));
+
+    let client = build(SemverParsing::Strict);
+    assert!(client.get_value("versionAtLeast", false, version("1.2.3+build5")).await);
+    assert!(!client.get_value("versionAtLeast", false, version("v1.2.3+build5")).await);
+    assert!(!client.get_value("versionIsOneOf", false, version("2.0.0")).await);
+
+    let client = build(SemverParsing::Lenient);
+    assert!(client.get_value("versionAtLeast", false, version("v1.2.3+build5")).await);
+    assert!(!client.get_value("versionAtLeast", false, version("V1.1.9")).await);
+    assert!(client.get_value("versionIsOneOf", false, version("2.0.0")).await);
+    assert!(client.get_value("versionIsOneOf", false, version("v2.0.0 + build5")).await);
+}
+
+#[tokio::test]
+async fn async_local_only() {
+    let client = Client::builder("local").async_overrides(Box::new(CountingSource::default()), LocalOnly, Duration::from_millis(100)).build().unwrap();
+
+    let report = client.wait_for_ready(Duration::from_secs(5)).await.unwrap();
+    assert!(matches!(report.state, ClientCacheState::HasLocalOverrideFlagDataOnly));
+    assert_eq!(client.get_value("loads", 0, None).await, 1);
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    assert!(client.get_value("loads", 0, None).await > 1);
+}
+
+#[tokio::test]
+async fn async_local_over_remote() {
+    let mut server = mockito::Server::new_async().await;
+    let (sdk_key, path) = produce_mock_path();
+    let m = server.mock("GET", path.as_str()).with_status(200).with_body(construct_bool_json_payload("fakeKey", false)).create_async().await;
+
+    let client = Client::builder(sdk_key.as_str()).base_url(server.url().as_str()).async_overrides(Box::new(CountingSource::default()), LocalOverRemote, Duration::from_millis(100)).build().unwrap();
+
+    client.wait_for_ready(Duration::from_secs(5)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!client.get_value("fakeKey", true, None).await);
+    let first = client.get_value("loads", 0, None).await;
+    assert!(first >= 1);
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    assert!(client.get_value("loads", 0, None).await > first);
+
+    m.assert_async().await;
+}
+
+#[tokio::test]
+async fn client_data_source() {
+    let mut server = mockito::Server::new_async().await;
+    let (sdk_key, path) = produce_mock_path();
+    let m = server.mock("GET", path.as_str()).with_status(200).with_body(construct_bool_json_payload("fakeKey", false)).create_async().await;
+
+    let platform = Client::builder("local").overrides(Box::new(MapDataSource::from([("fakeKey", Bool(true)), ("platformKey", Int(3))])), LocalOnly).build().unwrap();
+    let service = Client::builder(sdk_key.as_str())
+        .base_url(server.url().as_str())
+        .async_overrides(Box::new(ClientDataSource::new(Arc::new(platform))), RemoteOverLocal, Duration::from_secs(60))
+        .build()
+        .unwrap();
+
+    service.wait_for_ready(Duration::from_secs(5)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!service.get_value("fakeKey", true, None).await);
+    assert_eq!(service.get_value("platformKey", 0, None).await, 3);
+
+    m.assert_async().await;
+}
+
+#[tokio::test]
+async fn environment_cascading() {
+    let mut server = mockito::Server::new_async().await;
+    let (production_key, production_path) = produce_mock_path();
+    let (staging_key, staging_path) = produce_mock_path();
+    let production_body = |val: i64| format!(r#"{{"f":{{"inherited":{{"t":2,"v":{{"i":{val}}}}},"overridden":{{"t":0,"v":{{"b":false}}}}}}}}"#);
+    let m1 = server.mock("GET", production_path.as_str()).with_status(200).with_header("ETag", "etag1").with_body(production_body(1)).create_async().await;
+    let _m2 = server.mock("GET", staging_path.as_str()).with_status(200).with_body(construct_bool_json_payload("overridden", true)).create_async().await;
+
+    let production = ClientDataSource::from_builder(Client::builder(production_key.as_str()).base_url(server.url().as_str()).polling_mode(PollingMode::AutoPoll(Duration::from_millis(100)))).unwrap();
+    let staging = Client::builder(staging_key.as_str()).base_url(server.url().as_str()).async_overrides(Box::new(production), RemoteOverLocal, Duration::from_millis(100)).build().unwrap();
+
+    staging.wait_for_ready(Duration::from_secs(5)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    assert!(staging.get_value("overridden", false, None).await);
+    assert_eq!(staging.get_value("inherited", 0, None).await, 1);
+
+    m1.remove_async().await;
+    let _m3 = server.mock("GET", production_path.as_str()).with_status(200).with_header("ETag", "etag2").with_body(production_body(2)).create_async().await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(staging.get_value("inherited", 0, None).await, 2);
+    assert!(staging.get_value("overridden", false, None).await);
+}
+
 #[tokio::test]
 async fn external_serde() {
     let content_result = fs::read_to_string("tests/data/test_yaml.yml").unwrap();
@@ -119,3 +357,37 @@ async fn external_serde() {
 struct YamlOverrides {
     pub flag_overrides: HashMap<String, Value>,
 }
+
+#[derive(Default)]
+struct CountingSource {
+    loads: AtomicUsize,
+}
+
+impl AsyncOverrideDataSource for CountingSource {
+    fn load(&self) -> OverrideLoadFuture<'_> {
+        Box::pin(async {
+            let loads = self.loads.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(HashMap::from([("loads".to_owned(), Setting::from(&Value::Int(loads as i64)))]))
+        })
+    }
+}
+
+#[tokio::test]
+async fn lazy_attributes() {
+    let client = Client::builder("local").overrides(Box::new(FileDataSource::new("tests/data/test_lazy_attributes.json").unwrap()), LocalOnly).build().unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let user = User::new("id").custom_lazy("Tier", move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        "premium"
+    });
+
+    assert!(client.get_value("untargeted", false, Some(user.clone())).await);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    assert!(client.get_value("tierTargeted", false, Some(user.clone())).await);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    assert!(client.get_value("tierTargeted", false, Some(user)).await);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,131 @@
+#![allow(dead_code)]
+
+use crate::utils::{construct_bool_json_payload, produce_mock_path};
+use configcat::OverrideBehavior::LocalOnly;
+use configcat::{Client, ClientCacheState, MapDataSource, PollingMode, User, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod utils;
+
+#[tokio::test]
+async fn pool_single_download() {
+    let mut server = mockito::Server::new_async().await;
+    let (sdk_key, path) = produce_mock_path();
+    let m = server.mock("GET", path.as_str()).with_status(200).with_header("ETag", "etag1").with_body(construct_bool_json_payload("flag", true)).expect(1).create_async().await;
+
+    let pool = Client::builder(sdk_key.as_str()).base_url(server.url().as_str()).polling_mode(PollingMode::AutoPoll(Duration::from_secs(60))).build_pool(4).unwrap();
+    pool.wait_for_ready(Duration::from_secs(5)).await.unwrap();
+
+    assert_eq!(pool.size(), 4);
+    for i in 0..20 {
+        assert!(pool.get_value("flag", false, Some(User::new(format!("user{i}").as_str()))).await);
+    }
+
+    m.assert_async().await;
+}
+
+#[tokio::test]
+async fn pool_consistent_routing() {
+    let pool = Client::builder("local").overrides(Box::new(MapDataSource::from([("flag", Value::Bool(true))])), LocalOnly).build_pool(8).unwrap();
+
+    let user = User::new("user-id");
+    let first: *const Client = pool.client("flag", Some(&user));
+    for _ in 0..10 {
+        assert!(std::ptr::eq(first, pool.client("flag", Some(&user))));
+    }
+    assert!(pool.get_value("flag", false, Some(user)).await);
+}
+
+#[tokio::test]
+async fn pool_size_at_least_one() {
+    let pool = Client::builder("local").overrides(Box::new(MapDataSource::from([("flag", Value::Bool(true))])), LocalOnly).build_pool(0).unwrap();
+
+    assert_eq!(pool.size(), 1);
+    assert!(pool.get_value("flag", false, None).await);
+}
+
+#[tokio::test]
+async fn pool_lazy_load_refresh() {
+    let mut server = mockito::Server::new_async().await;
+    let (sdk_key, path) = produce_mock_path();
+    let pool = Client::builder(sdk_key.as_str()).base_url(server.url().as_str()).polling_mode(PollingMode::LazyLoad(Duration::from_millis(200))).build_pool(4).unwrap();
+
+    // one user for each client of the pool
+    let mut users = Vec::<User>::new();
+    for i in 0..100 {
+        let user = User::new(format!("user{i}").as_str());
+        if !users.iter().any(|u| std::ptr::eq(pool.client("flag", Some(u)), pool.client("flag", Some(&user)))) {
+            users.push(user);
+        }
+    }
+    assert_eq!(users.len(), 4);
+
+    // after the TTL, the first evaluation gets the new value whichever client it lands on
+    for (i, user) in users.into_iter().enumerate() {
+        let value = i % 2 == 0;
+        let m = server.mock("GET", path.as_str()).with_status(200).with_header("ETag", format!("etag{i}").as_str()).with_body(construct_bool_json_payload("flag", value)).create_async().await;
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(pool.get_value("flag", !value, Some(user)).await, value);
+        m.remove_async().await;
+    }
+}
+
+#[tokio::test]
+async fn pool_hooks_called_once() {
+    let mut server = mockito::Server::new_async().await;
+    let (sdk_key, path) = produce_mock_path();
+    let m = server
+        .mock("GET", path.as_str())
+        .with_status(200)
+        .with_header("ETag", "etag1")
+        .with_body(r#"{"p":{"u":"https://cdn-global.configcat.com","r":0,"m":"99.0.0"},"f":{"flag":{"t":0,"v":{"b":true}}}}"#)
+        .create_async()
+        .await;
+
+    let cache_states = Arc::new(Mutex::new(Vec::new()));
+    let upgrades = Arc::new(AtomicUsize::new(0));
+    let (states, upgrade_count) = (Arc::clone(&cache_states), Arc::clone(&upgrades));
+    let pool = Client::builder(sdk_key.as_str())
+        .base_url(server.url().as_str())
+        .polling_mode(PollingMode::AutoPoll(Duration::from_secs(60)))
+        .on_cache_state_changed(move |state| states.lock().unwrap().push(state))
+        .on_sdk_upgrade_required(move |_| {
+            upgrade_count.fetch_add(1, Ordering::SeqCst);
+        })
+        .build_pool(4)
+        .unwrap();
+    pool.wait_for_ready(Duration::from_secs(5)).await.unwrap();
+    for i in 0..20 {
+        assert!(pool.get_value("flag", false, Some(User::new(format!("user{i}").as_str()))).await);
+    }
+
+    assert_eq!(*cache_states.lock().unwrap(), vec![ClientCacheState::HasUpToDateFlagData]);
+    assert_eq!(upgrades.load(Ordering::SeqCst), 1);
+    m.assert_async().await;
+}
+
+#[tokio::test]
+async fn pool_usage_report() {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let hook_reports = Arc::clone(&reports);
+    let keys = (0..10).map(|i| format!("flag{i}")).collect::<Vec<String>>();
+    let pool = Client::builder("local")
+        .overrides(Box::new(MapDataSource::from(keys.iter().map(|key| (key.clone(), Value::Bool(true))).collect::<HashMap<String, Value>>())), LocalOnly)
+        .on_usage_report(Duration::from_millis(100), move |report| hook_reports.lock().unwrap().push(report.clone()))
+        .build_pool(4)
+        .unwrap();
+
+    // the keys are spread across the clients of the pool
+    for key in &keys {
+        assert!(pool.get_value(key, false, None).await);
+    }
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].evaluated.len(), keys.len());
+    assert!(reports[0].unused.is_empty());
+}